@@ -1,3 +1,16 @@
 fn main() {
-    tauri_build::build()
+    // Listed commands are denied unless a capability grants them, the rest
+    // stay available to every window
+    tauri_build::try_build(tauri_build::Attributes::new().app_manifest(
+        tauri_build::AppManifest::new().commands(&[
+            "empty_trash",
+            "run_cleanup",
+            "export_identity",
+            "import_identity",
+            "rotate_identity",
+            "revoke_share",
+            "resolve_catalog_mismatch",
+        ]),
+    ))
+    .expect("failed to run tauri build script");
 }
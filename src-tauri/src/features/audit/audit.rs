@@ -0,0 +1,223 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use specta::Type;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri::AppHandle;
+use tokio::sync::{Mutex, OnceCell};
+
+use crate::features::shared::{app_data_file, StorageError};
+
+pub const AUDIT_LOG_FILE_NAME: &str = "audit_log.jsonl";
+/// `prev_hash` of the first entry.
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// One transfer operation in the audit log.
+///
+/// `hash` covers every other field including `prev_hash`, so editing,
+/// removing or reordering entries breaks the chain from that point on.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct AuditEntry {
+    pub sequence: u64,
+    /// Milliseconds since the Unix epoch.
+    pub timestamp: i64,
+    /// OS account the app runs under.
+    pub actor: String,
+    pub command: String,
+    /// SHA-256 of the arguments, so file paths aren't kept in the clear.
+    pub arguments_hash: String,
+    /// `ok` or the error message.
+    pub result: String,
+    pub prev_hash: String,
+    pub hash: String,
+}
+
+impl AuditEntry {
+    fn compute_hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(
+            format!(
+                "{}|{}|{}|{}|{}|{}|{}",
+                self.sequence,
+                self.timestamp,
+                self.actor,
+                self.command,
+                self.arguments_hash,
+                self.result,
+                self.prev_hash
+            )
+            .as_bytes(),
+        );
+        hex::encode(hasher.finalize())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct AuditLogExport {
+    pub entries: usize,
+    /// Whether every entry still links to the one before it.
+    pub chain_intact: bool,
+}
+
+fn hash_arguments(arguments: &[&str]) -> String {
+    let mut hasher = Sha256::new();
+    for argument in arguments {
+        hasher.update((argument.len() as u64).to_le_bytes());
+        hasher.update(argument.as_bytes());
+    }
+    hex::encode(hasher.finalize())
+}
+
+fn current_actor() -> String {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+fn read_entries(log_file: &Path) -> Result<Vec<AuditEntry>, StorageError> {
+    let contents = match std::fs::read_to_string(log_file) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(StorageError::Io(e.to_string())),
+    };
+    contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str(line).map_err(|e| StorageError::Io(e.to_string())))
+        .collect()
+}
+
+/// Whether every entry hashes correctly and links to the one before it.
+pub fn verify_chain(entries: &[AuditEntry]) -> bool {
+    let mut prev_hash = GENESIS_HASH.to_string();
+    for (index, entry) in entries.iter().enumerate() {
+        if entry.sequence != index as u64
+            || entry.prev_hash != prev_hash
+            || entry.compute_hash() != entry.hash
+        {
+            return false;
+        }
+        prev_hash = entry.hash.clone();
+    }
+    true
+}
+
+/// Append-only, hash-chained log of transfer operations.
+///
+/// Kept apart from the recent items and catalog, which get rewritten and
+/// pruned; this file only ever grows.
+pub struct AuditLog {
+    /// Sequence and hash of the next entry's predecessor.
+    head: Mutex<(u64, String)>,
+    log_file: PathBuf,
+}
+
+impl AuditLog {
+    pub fn load(log_file: PathBuf) -> Self {
+        let entries = read_entries(&log_file).unwrap_or_else(|e| {
+            eprintln!("Failed to read audit log: {}", e);
+            Vec::new()
+        });
+        if !verify_chain(&entries) {
+            eprintln!("Audit log chain is broken, it may have been tampered with");
+        }
+        let head = entries
+            .last()
+            .map(|entry| (entry.sequence + 1, entry.hash.clone()))
+            .unwrap_or((0, GENESIS_HASH.to_string()));
+
+        Self {
+            head: Mutex::new(head),
+            log_file,
+        }
+    }
+
+    pub async fn append(
+        &self,
+        command: &str,
+        arguments: &[&str],
+        result: String,
+    ) -> Result<(), StorageError> {
+        let mut head = self.head.lock().await;
+        let mut entry = AuditEntry {
+            sequence: head.0,
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            actor: current_actor(),
+            command: command.to_string(),
+            arguments_hash: hash_arguments(arguments),
+            result,
+            prev_hash: head.1.clone(),
+            hash: String::new(),
+        };
+        entry.hash = entry.compute_hash();
+
+        let line = serde_json::to_string(&entry).map_err(|e| StorageError::Io(e.to_string()))?;
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.log_file)
+            .map_err(|e| StorageError::Io(e.to_string()))?;
+        writeln!(file, "{}", line).map_err(|e| StorageError::Io(e.to_string()))?;
+        file.sync_data()
+            .map_err(|e| StorageError::Io(e.to_string()))?;
+
+        *head = (entry.sequence + 1, entry.hash);
+        Ok(())
+    }
+
+    /// Copies the log to `destination` and reports whether its chain holds.
+    pub async fn export(&self, destination: &Path) -> Result<AuditLogExport, StorageError> {
+        // Hold the head so no entry gets appended halfway through the copy
+        let _head = self.head.lock().await;
+        let entries = read_entries(&self.log_file)?;
+        if entries.is_empty() {
+            std::fs::write(destination, "").map_err(|e| StorageError::Io(e.to_string()))?;
+        } else {
+            std::fs::copy(&self.log_file, destination)
+                .map_err(|e| StorageError::Io(e.to_string()))?;
+        }
+
+        Ok(AuditLogExport {
+            entries: entries.len(),
+            chain_intact: verify_chain(&entries),
+        })
+    }
+}
+
+// Global audit log instance
+pub static AUDIT_LOG: OnceCell<Arc<AuditLog>> = OnceCell::const_new();
+
+pub async fn get_audit_log_with_handle(
+    app_handle: &AppHandle,
+) -> Result<Arc<AuditLog>, StorageError> {
+    AUDIT_LOG
+        .get_or_try_init(|| async {
+            let log_file = app_data_file(app_handle, AUDIT_LOG_FILE_NAME)?;
+            Ok(Arc::new(AuditLog::load(log_file)))
+        })
+        .await
+        .map(Arc::clone)
+}
+
+/// Appends an operation to the audit log; failures are logged, not raised,
+/// so auditing never blocks a transfer.
+pub async fn record_audit<T>(
+    app_handle: &AppHandle,
+    command: &str,
+    arguments: &[&str],
+    result: &Result<T, StorageError>,
+) {
+    let result = match result {
+        Ok(_) => "ok".to_string(),
+        Err(e) => e.to_string(),
+    };
+    match get_audit_log_with_handle(app_handle).await {
+        Ok(log) => {
+            if let Err(e) = log.append(command, arguments, result).await {
+                eprintln!("Failed to write audit log: {}", e);
+            }
+        }
+        Err(e) => eprintln!("Failed to open audit log: {}", e),
+    }
+}
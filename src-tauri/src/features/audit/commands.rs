@@ -0,0 +1,17 @@
+use crate::features::audit::{get_audit_log_with_handle, AuditLogExport};
+use crate::features::shared::{map_storage_error, validate_user_path, CommandError};
+use tauri::AppHandle;
+
+/// Copies the audit log to `path`, reporting whether its hash chain is intact.
+#[tauri::command]
+#[specta::specta]
+pub async fn export_audit_log(
+    path: String,
+    app_handle: AppHandle,
+) -> Result<AuditLogExport, CommandError> {
+    let path = validate_user_path(&app_handle, &path).map_err(map_storage_error)?;
+    let log = get_audit_log_with_handle(&app_handle)
+        .await
+        .map_err(map_storage_error)?;
+    log.export(&path).await.map_err(map_storage_error)
+}
@@ -0,0 +1,5 @@
+pub mod audit;
+pub mod commands;
+
+pub use audit::*;
+pub use commands::*;
@@ -0,0 +1,101 @@
+use codex_bindings::DebugInfo;
+use std::future::Future;
+use std::path::Path;
+
+use crate::features::shared::{ProviderInfo, StorageError};
+use crate::features::storage::StorageUsage;
+
+/// Called with `(bytes_processed, total_bytes)` as a transfer advances.
+pub type TransferProgressCallback = Box<dyn Fn(usize, Option<usize>) + Send + Sync + 'static>;
+
+/// Everything the storage manager needs from a node.
+///
+/// Implemented by the embedded codex-bindings node; other implementations
+/// (e.g. a mock for tests) can be plugged into `StorageManager` instead.
+pub trait StorageBackend: Clone + Send + Sync + 'static {
+    type Config: Clone + Send + Sync + 'static;
+
+    fn create(config: Self::Config) -> Result<Self, StorageError>;
+
+    fn is_started(&self) -> bool;
+
+    fn start(&mut self) -> impl Future<Output = Result<(), StorageError>> + Send;
+
+    fn stop(&mut self) -> impl Future<Output = Result<(), StorageError>> + Send;
+
+    fn peer_id(&self) -> impl Future<Output = Result<String, StorageError>> + Send;
+
+    fn version(&self) -> impl Future<Output = Result<String, StorageError>> + Send;
+
+    fn repo(&self) -> impl Future<Output = Result<String, StorageError>> + Send;
+
+    fn connect(
+        &self,
+        peer_id: &str,
+        addresses: &[String],
+    ) -> impl Future<Output = Result<(), StorageError>> + Send;
+
+    fn debug(&self) -> impl Future<Output = Result<DebugInfo, StorageError>> + Send;
+
+    /// Uploads a file and returns the CID of the resulting dataset.
+    ///
+    /// `block_size` is a hint, `None` keeps the node's default.
+    fn upload(
+        &self,
+        file_path: &Path,
+        block_size: Option<usize>,
+        on_progress: TransferProgressCallback,
+    ) -> impl Future<Output = Result<String, StorageError>> + Send;
+
+    /// Downloads a dataset to `save_path` and returns the number of bytes written.
+    fn download(
+        &self,
+        cid: &str,
+        save_path: &Path,
+        on_progress: TransferProgressCallback,
+    ) -> impl Future<Output = Result<usize, StorageError>> + Send;
+
+    fn space(&self) -> impl Future<Output = Result<StorageUsage, StorageError>> + Send;
+
+    fn exists(&self, cid: &str) -> impl Future<Output = Result<bool, StorageError>> + Send;
+
+    /// CIDs of all manifests stored in the local repo.
+    fn manifests(&self) -> impl Future<Output = Result<Vec<String>, StorageError>> + Send;
+
+    /// Removes a dataset's blocks from the local repo.
+    fn delete(&self, cid: &str) -> impl Future<Output = Result<(), StorageError>> + Send;
+
+    /// Fetches a dataset from the network into the local repo.
+    fn fetch(&self, cid: &str) -> impl Future<Output = Result<(), StorageError>> + Send;
+
+    /// Whether `find_providers` can ask the DHT on this node.
+    fn supports_provider_lookup(&self) -> bool;
+
+    /// Looks up peers providing a dataset, returning at most `limit` of them.
+    fn find_providers(
+        &self,
+        cid: &str,
+        limit: usize,
+    ) -> impl Future<Output = Result<Vec<ProviderInfo>, StorageError>> + Send;
+
+    /// File name recorded in the dataset's manifest, if any. Never fetches
+    /// the dataset itself; `None` if the manifest can't be read without.
+    fn dataset_name(
+        &self,
+        cid: &str,
+    ) -> impl Future<Output = Result<Option<String>, StorageError>> + Send;
+
+    /// Size in bytes recorded in the dataset's manifest, if any. Never
+    /// fetches the dataset itself; `None` if the manifest can't be read
+    /// without.
+    fn dataset_size(
+        &self,
+        cid: &str,
+    ) -> impl Future<Output = Result<Option<u64>, StorageError>> + Send;
+
+    /// Whether `announce` can push a dataset to the DHT on this node.
+    fn supports_announce(&self) -> bool;
+
+    /// Advertises a locally stored dataset to the DHT right away.
+    fn announce(&self, cid: &str) -> impl Future<Output = Result<(), StorageError>> + Send;
+}
@@ -0,0 +1,182 @@
+use codex_bindings::{
+    connect, debug, delete, download_stream, exists, fetch, manifests, space, upload_file,
+    CodexConfig, CodexNode, DebugInfo, DownloadStreamOptions, Manifest, UploadOptions,
+};
+use std::path::Path;
+
+use crate::features::backend::{StorageBackend, TransferProgressCallback};
+use crate::features::shared::{classify_node_failure, ProviderInfo, StorageError};
+use crate::features::storage::StorageUsage;
+
+impl StorageBackend for CodexNode {
+    type Config = CodexConfig;
+
+    fn create(config: CodexConfig) -> Result<Self, StorageError> {
+        CodexNode::new(config)
+            .map_err(|e| classify_node_failure(e.to_string(), StorageError::NodeCreation))
+    }
+
+    fn is_started(&self) -> bool {
+        CodexNode::is_started(self)
+    }
+
+    async fn start(&mut self) -> Result<(), StorageError> {
+        CodexNode::start(self)
+            .map_err(|e| classify_node_failure(e.to_string(), StorageError::NodeStart))
+    }
+
+    async fn stop(&mut self) -> Result<(), StorageError> {
+        CodexNode::stop(self).map_err(|e| StorageError::Configuration(e.to_string()))
+    }
+
+    async fn peer_id(&self) -> Result<String, StorageError> {
+        CodexNode::peer_id(self).map_err(|e| StorageError::Configuration(e.to_string()))
+    }
+
+    async fn version(&self) -> Result<String, StorageError> {
+        CodexNode::version(self).map_err(|e| StorageError::Configuration(e.to_string()))
+    }
+
+    async fn repo(&self) -> Result<String, StorageError> {
+        CodexNode::repo(self).map_err(|e| StorageError::Configuration(e.to_string()))
+    }
+
+    async fn connect(&self, peer_id: &str, addresses: &[String]) -> Result<(), StorageError> {
+        connect(self, peer_id, addresses)
+            .await
+            .map_err(|e| StorageError::Configuration(e.to_string()))
+    }
+
+    async fn debug(&self) -> Result<DebugInfo, StorageError> {
+        debug(self)
+            .await
+            .map_err(|e| StorageError::Configuration(e.to_string()))
+    }
+
+    async fn upload(
+        &self,
+        file_path: &Path,
+        block_size: Option<usize>,
+        on_progress: TransferProgressCallback,
+    ) -> Result<String, StorageError> {
+        let mut options = UploadOptions::new()
+            .filepath(file_path)
+            .on_progress(move |progress| {
+                on_progress(progress.bytes_uploaded, progress.total_bytes)
+            });
+        if let Some(block_size) = block_size {
+            options = options.chunk_size(block_size);
+        }
+
+        upload_file(self, options)
+            .await
+            .map(|result| result.cid)
+            .map_err(|e| StorageError::Upload(e.to_string()))
+    }
+
+    async fn download(
+        &self,
+        cid: &str,
+        save_path: &Path,
+        on_progress: TransferProgressCallback,
+    ) -> Result<usize, StorageError> {
+        let options = DownloadStreamOptions::new(cid)
+            .filepath(save_path)
+            .on_progress(move |progress| {
+                on_progress(progress.bytes_downloaded, progress.total_bytes)
+            });
+
+        download_stream(self, cid, options)
+            .await
+            .map(|result| result.size)
+            .map_err(|e| StorageError::Download(e.to_string()))
+    }
+
+    async fn space(&self) -> Result<StorageUsage, StorageError> {
+        let space = space(self)
+            .await
+            .map_err(|e| StorageError::Configuration(e.to_string()))?;
+
+        Ok(StorageUsage {
+            used_bytes: space.quota_used_bytes + space.quota_reserved_bytes,
+            quota_bytes: space.quota_max_bytes,
+            total_blocks: space.total_blocks,
+        })
+    }
+
+    async fn exists(&self, cid: &str) -> Result<bool, StorageError> {
+        exists(self, cid)
+            .await
+            .map_err(|e| StorageError::InvalidCid(e.to_string()))
+    }
+
+    async fn manifests(&self) -> Result<Vec<String>, StorageError> {
+        manifests(self)
+            .await
+            .map(|manifests| manifests.into_iter().map(|manifest| manifest.cid).collect())
+            .map_err(|e| StorageError::Configuration(e.to_string()))
+    }
+
+    async fn fetch(&self, cid: &str) -> Result<(), StorageError> {
+        fetch(self, cid)
+            .await
+            .map(|_| ())
+            .map_err(|e| StorageError::Download(e.to_string()))
+    }
+
+    async fn find_providers(
+        &self,
+        _cid: &str,
+        _limit: usize,
+    ) -> Result<Vec<ProviderInfo>, StorageError> {
+        Err(StorageError::Unsupported(
+            "codex-bindings has no DHT provider lookup".to_string(),
+        ))
+    }
+
+    fn supports_provider_lookup(&self) -> bool {
+        false
+    }
+
+    fn supports_announce(&self) -> bool {
+        false
+    }
+
+    async fn announce(&self, _cid: &str) -> Result<(), StorageError> {
+        Err(StorageError::Unsupported(
+            "codex-bindings has no explicit DHT announce".to_string(),
+        ))
+    }
+
+    /// Read from the local manifests only, `fetch` would pull in the whole
+    /// dataset.
+    async fn dataset_name(&self, cid: &str) -> Result<Option<String>, StorageError> {
+        Ok(local_manifest(self, cid)
+            .await?
+            .map(|manifest| manifest.filename)
+            .filter(|name| !name.is_empty()))
+    }
+
+    /// Read from the local manifests only, `fetch` would pull in the whole
+    /// dataset.
+    async fn dataset_size(&self, cid: &str) -> Result<Option<u64>, StorageError> {
+        Ok(local_manifest(self, cid)
+            .await?
+            .map(|manifest| manifest.dataset_size as u64))
+    }
+
+    async fn delete(&self, cid: &str) -> Result<(), StorageError> {
+        delete(self, cid)
+            .await
+            .map_err(|e| StorageError::Configuration(e.to_string()))
+    }
+}
+
+/// The manifest of `cid` if the repo holds it. codex-bindings has no
+/// manifest-only network lookup, so datasets not stored yet have none.
+async fn local_manifest(node: &CodexNode, cid: &str) -> Result<Option<Manifest>, StorageError> {
+    manifests(node)
+        .await
+        .map(|manifests| manifests.into_iter().find(|manifest| manifest.cid == cid))
+        .map_err(|e| StorageError::Configuration(e.to_string()))
+}
@@ -0,0 +1,253 @@
+use codex_bindings::DebugInfo;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::features::backend::{StorageBackend, TransferProgressCallback};
+use crate::features::shared::{ProviderInfo, StorageError};
+use crate::features::storage::StorageUsage;
+
+/// Behaviour of a `MockBackend`, fixed when the backend is created.
+#[derive(Debug, Clone)]
+pub struct MockConfig {
+    pub fail_create: bool,
+    pub fail_start: bool,
+    pub fail_transfers: bool,
+    /// Number of progress callbacks reported per transfer.
+    pub progress_steps: usize,
+    /// Delay between progress callbacks, to simulate slow peers.
+    pub step_delay: Duration,
+    pub quota_bytes: u64,
+}
+
+impl Default for MockConfig {
+    fn default() -> Self {
+        Self {
+            fail_create: false,
+            fail_start: false,
+            fail_transfers: false,
+            progress_steps: 4,
+            step_delay: Duration::ZERO,
+            quota_bytes: 1024 * 1024 * 1024,
+        }
+    }
+}
+
+/// In-process stand-in for the native node.
+///
+/// Datasets live in memory and transfers report progress deterministically,
+/// which makes manager, progress and queue logic testable without codex.
+#[derive(Debug, Clone)]
+pub struct MockBackend {
+    config: MockConfig,
+    started: Arc<AtomicBool>,
+    next_cid: Arc<AtomicUsize>,
+    datasets: Arc<Mutex<HashMap<String, usize>>>,
+    peers: Arc<Mutex<Vec<String>>>,
+}
+
+impl MockBackend {
+    pub fn connected_peers(&self) -> Vec<String> {
+        self.peers.lock().unwrap().clone()
+    }
+
+    async fn simulate_transfer(&self, total: usize, on_progress: &TransferProgressCallback) {
+        let steps = self.config.progress_steps.max(1);
+        for step in 1..=steps {
+            if !self.config.step_delay.is_zero() {
+                tokio::time::sleep(self.config.step_delay).await;
+            }
+            on_progress(total * step / steps, Some(total));
+            // Let the progress forwarding tasks run before the next step
+            tokio::task::yield_now().await;
+        }
+    }
+}
+
+impl StorageBackend for MockBackend {
+    type Config = MockConfig;
+
+    fn create(config: MockConfig) -> Result<Self, StorageError> {
+        if config.fail_create {
+            return Err(StorageError::NodeCreation(
+                "mock node creation failure".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            config,
+            started: Arc::new(AtomicBool::new(false)),
+            next_cid: Arc::new(AtomicUsize::new(0)),
+            datasets: Arc::new(Mutex::new(HashMap::new())),
+            peers: Arc::new(Mutex::new(Vec::new())),
+        })
+    }
+
+    fn is_started(&self) -> bool {
+        self.started.load(Ordering::SeqCst)
+    }
+
+    async fn start(&mut self) -> Result<(), StorageError> {
+        if self.config.fail_start {
+            return Err(StorageError::NodeStart(
+                "mock node start failure".to_string(),
+            ));
+        }
+        self.started.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    async fn stop(&mut self) -> Result<(), StorageError> {
+        self.started.store(false, Ordering::SeqCst);
+        Ok(())
+    }
+
+    async fn peer_id(&self) -> Result<String, StorageError> {
+        Ok("mock-peer-id".to_string())
+    }
+
+    async fn version(&self) -> Result<String, StorageError> {
+        Ok("mock".to_string())
+    }
+
+    async fn repo(&self) -> Result<String, StorageError> {
+        Ok("mock-repo".to_string())
+    }
+
+    async fn connect(&self, peer_id: &str, _addresses: &[String]) -> Result<(), StorageError> {
+        self.peers.lock().unwrap().push(peer_id.to_string());
+        Ok(())
+    }
+
+    async fn debug(&self) -> Result<DebugInfo, StorageError> {
+        Err(StorageError::Configuration(
+            "Debug info is not available from the mock backend".to_string(),
+        ))
+    }
+
+    async fn upload(
+        &self,
+        file_path: &Path,
+        _block_size: Option<usize>,
+        on_progress: TransferProgressCallback,
+    ) -> Result<String, StorageError> {
+        let size = std::fs::metadata(file_path)
+            .map_err(|e| StorageError::Io(e.to_string()))?
+            .len() as usize;
+
+        self.simulate_transfer(size, &on_progress).await;
+
+        if self.config.fail_transfers {
+            return Err(StorageError::Upload("mock upload failure".to_string()));
+        }
+
+        let cid = format!("mock-cid-{}", self.next_cid.fetch_add(1, Ordering::SeqCst));
+        self.datasets.lock().unwrap().insert(cid.clone(), size);
+        Ok(cid)
+    }
+
+    async fn download(
+        &self,
+        cid: &str,
+        save_path: &Path,
+        on_progress: TransferProgressCallback,
+    ) -> Result<usize, StorageError> {
+        let size = self
+            .datasets
+            .lock()
+            .unwrap()
+            .get(cid)
+            .copied()
+            .ok_or_else(|| StorageError::Download(format!("{} not found", cid)))?;
+
+        self.simulate_transfer(size, &on_progress).await;
+
+        if self.config.fail_transfers {
+            return Err(StorageError::Download("mock download failure".to_string()));
+        }
+
+        std::fs::write(save_path, vec![0u8; size]).map_err(|e| StorageError::Io(e.to_string()))?;
+        Ok(size)
+    }
+
+    async fn space(&self) -> Result<StorageUsage, StorageError> {
+        let datasets = self.datasets.lock().unwrap();
+        Ok(StorageUsage {
+            used_bytes: datasets.values().map(|size| *size as u64).sum(),
+            quota_bytes: self.config.quota_bytes,
+            total_blocks: datasets.len() as u64,
+        })
+    }
+
+    async fn exists(&self, cid: &str) -> Result<bool, StorageError> {
+        Ok(self.datasets.lock().unwrap().contains_key(cid))
+    }
+
+    async fn manifests(&self) -> Result<Vec<String>, StorageError> {
+        Ok(self.datasets.lock().unwrap().keys().cloned().collect())
+    }
+
+    async fn fetch(&self, cid: &str) -> Result<(), StorageError> {
+        if self.datasets.lock().unwrap().contains_key(cid) {
+            Ok(())
+        } else {
+            Err(StorageError::Download(format!("{} not found", cid)))
+        }
+    }
+
+    fn supports_provider_lookup(&self) -> bool {
+        true
+    }
+
+    /// The mock node is the only provider of its own datasets.
+    async fn find_providers(
+        &self,
+        cid: &str,
+        limit: usize,
+    ) -> Result<Vec<ProviderInfo>, StorageError> {
+        if limit == 0 || !self.datasets.lock().unwrap().contains_key(cid) {
+            return Ok(Vec::new());
+        }
+        Ok(vec![ProviderInfo {
+            peer_id: "mock-peer-id".to_string(),
+            addresses: Vec::new(),
+        }])
+    }
+
+    fn supports_announce(&self) -> bool {
+        true
+    }
+
+    async fn announce(&self, cid: &str) -> Result<(), StorageError> {
+        if self.datasets.lock().unwrap().contains_key(cid) {
+            Ok(())
+        } else {
+            Err(StorageError::InvalidCid(format!(
+                "{} is not stored locally",
+                cid
+            )))
+        }
+    }
+
+    async fn dataset_name(&self, cid: &str) -> Result<Option<String>, StorageError> {
+        if self.datasets.lock().unwrap().contains_key(cid) {
+            Ok(None)
+        } else {
+            Err(StorageError::Download(format!("{} not found", cid)))
+        }
+    }
+
+    async fn dataset_size(&self, cid: &str) -> Result<Option<u64>, StorageError> {
+        match self.datasets.lock().unwrap().get(cid) {
+            Some(size) => Ok(Some(*size as u64)),
+            None => Err(StorageError::Download(format!("{} not found", cid))),
+        }
+    }
+
+    async fn delete(&self, cid: &str) -> Result<(), StorageError> {
+        self.datasets.lock().unwrap().remove(cid);
+        Ok(())
+    }
+}
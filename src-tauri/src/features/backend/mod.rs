@@ -0,0 +1,10 @@
+pub mod backend;
+pub mod codex;
+#[cfg(any(test, feature = "mock-backend"))]
+pub mod mock;
+pub mod node;
+pub mod remote;
+
+pub use backend::*;
+pub use node::*;
+pub use remote::*;
@@ -0,0 +1,193 @@
+use codex_bindings::{CodexConfig, CodexNode, DebugInfo};
+use std::path::Path;
+
+use crate::features::backend::remote::{RemoteNode, RemoteNodeConfig};
+use crate::features::backend::{StorageBackend, TransferProgressCallback};
+use crate::features::shared::{ProviderInfo, StorageError};
+use crate::features::storage::StorageUsage;
+
+#[derive(Clone)]
+pub enum NodeBackendConfig {
+    Embedded(CodexConfig),
+    Remote(RemoteNodeConfig),
+}
+
+/// The node used by the app, picked from the settings at startup: either the
+/// embedded codex-bindings node or an external node reached over REST.
+#[derive(Clone)]
+pub enum NodeBackend {
+    Embedded(CodexNode),
+    Remote(RemoteNode),
+}
+
+impl StorageBackend for NodeBackend {
+    type Config = NodeBackendConfig;
+
+    fn create(config: NodeBackendConfig) -> Result<Self, StorageError> {
+        match config {
+            NodeBackendConfig::Embedded(config) => CodexNode::create(config).map(Self::Embedded),
+            NodeBackendConfig::Remote(config) => RemoteNode::create(config).map(Self::Remote),
+        }
+    }
+
+    fn is_started(&self) -> bool {
+        match self {
+            Self::Embedded(node) => StorageBackend::is_started(node),
+            Self::Remote(node) => node.is_started(),
+        }
+    }
+
+    async fn start(&mut self) -> Result<(), StorageError> {
+        match self {
+            Self::Embedded(node) => StorageBackend::start(node).await,
+            Self::Remote(node) => node.start().await,
+        }
+    }
+
+    async fn stop(&mut self) -> Result<(), StorageError> {
+        match self {
+            Self::Embedded(node) => StorageBackend::stop(node).await,
+            Self::Remote(node) => node.stop().await,
+        }
+    }
+
+    async fn peer_id(&self) -> Result<String, StorageError> {
+        match self {
+            Self::Embedded(node) => StorageBackend::peer_id(node).await,
+            Self::Remote(node) => node.peer_id().await,
+        }
+    }
+
+    async fn version(&self) -> Result<String, StorageError> {
+        match self {
+            Self::Embedded(node) => StorageBackend::version(node).await,
+            Self::Remote(node) => node.version().await,
+        }
+    }
+
+    async fn repo(&self) -> Result<String, StorageError> {
+        match self {
+            Self::Embedded(node) => StorageBackend::repo(node).await,
+            Self::Remote(node) => node.repo().await,
+        }
+    }
+
+    async fn connect(&self, peer_id: &str, addresses: &[String]) -> Result<(), StorageError> {
+        match self {
+            Self::Embedded(node) => node.connect(peer_id, addresses).await,
+            Self::Remote(node) => node.connect(peer_id, addresses).await,
+        }
+    }
+
+    async fn debug(&self) -> Result<DebugInfo, StorageError> {
+        match self {
+            Self::Embedded(node) => node.debug().await,
+            Self::Remote(node) => node.debug().await,
+        }
+    }
+
+    async fn upload(
+        &self,
+        file_path: &Path,
+        block_size: Option<usize>,
+        on_progress: TransferProgressCallback,
+    ) -> Result<String, StorageError> {
+        match self {
+            Self::Embedded(node) => node.upload(file_path, block_size, on_progress).await,
+            Self::Remote(node) => node.upload(file_path, block_size, on_progress).await,
+        }
+    }
+
+    async fn download(
+        &self,
+        cid: &str,
+        save_path: &Path,
+        on_progress: TransferProgressCallback,
+    ) -> Result<usize, StorageError> {
+        match self {
+            Self::Embedded(node) => node.download(cid, save_path, on_progress).await,
+            Self::Remote(node) => node.download(cid, save_path, on_progress).await,
+        }
+    }
+
+    async fn space(&self) -> Result<StorageUsage, StorageError> {
+        match self {
+            Self::Embedded(node) => node.space().await,
+            Self::Remote(node) => node.space().await,
+        }
+    }
+
+    async fn exists(&self, cid: &str) -> Result<bool, StorageError> {
+        match self {
+            Self::Embedded(node) => node.exists(cid).await,
+            Self::Remote(node) => node.exists(cid).await,
+        }
+    }
+
+    async fn manifests(&self) -> Result<Vec<String>, StorageError> {
+        match self {
+            Self::Embedded(node) => node.manifests().await,
+            Self::Remote(node) => node.manifests().await,
+        }
+    }
+
+    async fn fetch(&self, cid: &str) -> Result<(), StorageError> {
+        match self {
+            Self::Embedded(node) => node.fetch(cid).await,
+            Self::Remote(node) => node.fetch(cid).await,
+        }
+    }
+
+    fn supports_provider_lookup(&self) -> bool {
+        match self {
+            Self::Embedded(node) => node.supports_provider_lookup(),
+            Self::Remote(node) => node.supports_provider_lookup(),
+        }
+    }
+
+    async fn find_providers(
+        &self,
+        cid: &str,
+        limit: usize,
+    ) -> Result<Vec<ProviderInfo>, StorageError> {
+        match self {
+            Self::Embedded(node) => node.find_providers(cid, limit).await,
+            Self::Remote(node) => node.find_providers(cid, limit).await,
+        }
+    }
+
+    fn supports_announce(&self) -> bool {
+        match self {
+            Self::Embedded(node) => node.supports_announce(),
+            Self::Remote(node) => node.supports_announce(),
+        }
+    }
+
+    async fn announce(&self, cid: &str) -> Result<(), StorageError> {
+        match self {
+            Self::Embedded(node) => node.announce(cid).await,
+            Self::Remote(node) => node.announce(cid).await,
+        }
+    }
+
+    async fn dataset_name(&self, cid: &str) -> Result<Option<String>, StorageError> {
+        match self {
+            Self::Embedded(node) => node.dataset_name(cid).await,
+            Self::Remote(node) => node.dataset_name(cid).await,
+        }
+    }
+
+    async fn dataset_size(&self, cid: &str) -> Result<Option<u64>, StorageError> {
+        match self {
+            Self::Embedded(node) => node.dataset_size(cid).await,
+            Self::Remote(node) => node.dataset_size(cid).await,
+        }
+    }
+
+    async fn delete(&self, cid: &str) -> Result<(), StorageError> {
+        match self {
+            Self::Embedded(node) => node.delete(cid).await,
+            Self::Remote(node) => node.delete(cid).await,
+        }
+    }
+}
@@ -0,0 +1,548 @@
+use codex_bindings::DebugInfo;
+use futures_util::{StreamExt, TryStreamExt};
+use serde::Deserialize;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::io::AsyncWriteExt;
+use tokio_util::io::ReaderStream;
+
+use crate::features::backend::{StorageBackend, TransferProgressCallback};
+use crate::features::settings::{http_client, ProxySettings};
+use crate::features::shared::{ProviderInfo, StorageError};
+use crate::features::storage::StorageUsage;
+
+const API_PREFIX: &str = "/api/codex/v1";
+const RANGE_CHUNK_SIZE: usize = 8 * 1024 * 1024;
+const MIN_RANGE_CHUNK_SIZE: usize = 1024 * 1024;
+
+#[derive(Debug, Clone)]
+pub struct RemoteNodeConfig {
+    /// Base URL of the node's REST API, e.g. `http://localhost:8080`.
+    pub url: String,
+    /// Concurrent range requests per download, 1 to always stream sequentially.
+    ///
+    /// Only remote nodes have this: the embedded node's bindings offer a
+    /// single sequential stream.
+    pub download_parallelism: usize,
+    /// Cap on range data held in memory at once, `None` for no cap.
+    pub memory_limit_bytes: Option<usize>,
+    pub proxy: Option<ProxySettings>,
+}
+
+/// A Codex node running outside the app, driven through its REST API.
+#[derive(Debug, Clone)]
+pub struct RemoteNode {
+    base_url: String,
+    download_parallelism: usize,
+    range_chunk_size: usize,
+    client: reqwest::Client,
+    started: Arc<AtomicBool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RemoteVersion {
+    version: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RemoteDebugInfo {
+    id: String,
+    repo: String,
+    codex: RemoteVersion,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RemoteSpace {
+    total_blocks: u64,
+    quota_max_bytes: u64,
+    quota_used_bytes: u64,
+    quota_reserved_bytes: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct RemoteDataset {
+    cid: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RemoteDatasets {
+    content: Vec<RemoteDataset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RemoteManifest {
+    filename: Option<String>,
+    #[serde(rename = "datasetSize")]
+    dataset_size: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RemoteManifestResponse {
+    manifest: RemoteManifest,
+}
+
+fn request_error(err: reqwest::Error) -> StorageError {
+    StorageError::Configuration(format!("Remote node request failed: {}", err))
+}
+
+/// API path of a dataset route. CIDs are multibase strings of letters and
+/// digits only, anything else could reach another route of the node.
+fn data_path(cid: &str, route: &str) -> Result<String, StorageError> {
+    if cid.is_empty() || !cid.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(StorageError::InvalidCid(format!(
+            "{} is not a valid CID",
+            cid
+        )));
+    }
+    Ok(format!("/data/{}{}", cid, route))
+}
+
+/// `Content-Disposition` value naming an upload, with an ASCII fallback
+/// and the exact name percent-encoded as RFC 6266 and RFC 5987 describe.
+fn content_disposition(file_name: &str) -> String {
+    let fallback: String = file_name
+        .chars()
+        .map(|c| {
+            if c == ' ' || (c.is_ascii_graphic() && c != '"' && c != '\\') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let encoded: String = file_name
+        .bytes()
+        .map(|byte| {
+            if byte.is_ascii_alphanumeric() || b"!#$&+-.^_`|~".contains(&byte) {
+                (byte as char).to_string()
+            } else {
+                format!("%{:02X}", byte)
+            }
+        })
+        .collect();
+    format!(
+        "attachment; filename=\"{}\"; filename*=UTF-8''{}",
+        fallback, encoded
+    )
+}
+
+impl RemoteNode {
+    fn endpoint(&self, path: &str) -> String {
+        format!("{}{}{}", self.base_url, API_PREFIX, path)
+    }
+
+    async fn get_json<T: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+    ) -> Result<T, StorageError> {
+        self.client
+            .get(self.endpoint(path))
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(request_error)?
+            .json()
+            .await
+            .map_err(request_error)
+    }
+
+    async fn debug_info(&self) -> Result<RemoteDebugInfo, StorageError> {
+        self.get_json("/debug/info").await
+    }
+
+    /// Total size of the resource if the node serves byte ranges of it.
+    async fn ranged_size(&self, url: &str) -> Option<usize> {
+        let response = self
+            .client
+            .get(url)
+            .header(reqwest::header::RANGE, "bytes=0-0")
+            .send()
+            .await
+            .ok()?;
+        if response.status() != reqwest::StatusCode::PARTIAL_CONTENT {
+            return None;
+        }
+
+        // "bytes 0-0/<total>"
+        response
+            .headers()
+            .get(reqwest::header::CONTENT_RANGE)?
+            .to_str()
+            .ok()?
+            .rsplit_once('/')?
+            .1
+            .parse()
+            .ok()
+    }
+
+    /// Fetches `total` bytes as concurrent ranges, each written at its offset.
+    ///
+    /// A failed download leaves no file behind: the ranges that never
+    /// arrived would read as zeros.
+    async fn download_ranges(
+        &self,
+        url: &str,
+        total: usize,
+        save_path: &Path,
+        on_progress: TransferProgressCallback,
+    ) -> Result<usize, StorageError> {
+        let result = self.write_ranges(url, total, save_path, on_progress).await;
+        if result.is_err() {
+            let _ = tokio::fs::remove_file(save_path).await;
+        }
+        result
+    }
+
+    async fn write_ranges(
+        &self,
+        url: &str,
+        total: usize,
+        save_path: &Path,
+        on_progress: TransferProgressCallback,
+    ) -> Result<usize, StorageError> {
+        let file = tokio::fs::File::create(save_path)
+            .await
+            .map_err(|e| StorageError::Io(e.to_string()))?;
+        file.set_len(total as u64)
+            .await
+            .map_err(|e| StorageError::Io(e.to_string()))?;
+        let file = Arc::new(file.into_std().await);
+
+        let downloaded = Arc::new(AtomicUsize::new(0));
+        let on_progress = Arc::new(on_progress);
+        let ranges = (0..total)
+            .step_by(self.range_chunk_size)
+            .map(|start| (start, (start + self.range_chunk_size).min(total) - 1));
+
+        futures_util::stream::iter(ranges)
+            .map(|(start, end)| {
+                let file = Arc::clone(&file);
+                let downloaded = Arc::clone(&downloaded);
+                let on_progress = Arc::clone(&on_progress);
+                async move {
+                    let bytes = self
+                        .client
+                        .get(url)
+                        .header(reqwest::header::RANGE, format!("bytes={}-{}", start, end))
+                        .send()
+                        .await
+                        .and_then(|response| response.error_for_status())
+                        .map_err(|e| StorageError::Download(e.to_string()))?
+                        .bytes()
+                        .await
+                        .map_err(|e| StorageError::Download(e.to_string()))?;
+                    if bytes.len() != end - start + 1 {
+                        return Err(StorageError::Download(format!(
+                            "Range {}-{} returned {} bytes",
+                            start,
+                            end,
+                            bytes.len()
+                        )));
+                    }
+
+                    let written = bytes.len();
+                    tokio::task::spawn_blocking(move || write_at(&file, &bytes, start as u64))
+                        .await
+                        .map_err(|e| StorageError::Io(e.to_string()))?
+                        .map_err(|e| StorageError::Io(e.to_string()))?;
+
+                    let done = downloaded.fetch_add(written, Ordering::SeqCst) + written;
+                    on_progress(done, Some(total));
+                    Ok::<(), StorageError>(())
+                }
+            })
+            .buffer_unordered(self.download_parallelism)
+            // Stops at the first failed range, dropping the ones in flight
+            .try_collect::<Vec<()>>()
+            .await?;
+
+        Ok(total)
+    }
+}
+
+/// Writes all of `buf` at `offset` without moving a shared cursor, so
+/// concurrent ranges can share one file handle.
+fn write_at(file: &std::fs::File, buf: &[u8], offset: u64) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::FileExt;
+        file.write_all_at(buf, offset)
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::FileExt;
+        let mut written = 0;
+        while written < buf.len() {
+            match file.seek_write(&buf[written..], offset + written as u64)? {
+                0 => return Err(std::io::ErrorKind::WriteZero.into()),
+                n => written += n,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl StorageBackend for RemoteNode {
+    type Config = RemoteNodeConfig;
+
+    fn create(config: RemoteNodeConfig) -> Result<Self, StorageError> {
+        let base_url = config.url.trim_end_matches('/').to_string();
+        reqwest::Url::parse(&base_url)
+            .map_err(|e| StorageError::Configuration(format!("Invalid remote node URL: {}", e)))?;
+
+        // Every in-flight range is buffered whole, so shrink the ranges and
+        // then the parallelism until they fit in the memory limit
+        let mut download_parallelism = config.download_parallelism.max(1);
+        let mut range_chunk_size = RANGE_CHUNK_SIZE;
+        if let Some(limit) = config.memory_limit_bytes {
+            range_chunk_size =
+                (limit / download_parallelism).clamp(MIN_RANGE_CHUNK_SIZE, RANGE_CHUNK_SIZE);
+            download_parallelism = (limit / range_chunk_size).clamp(1, download_parallelism);
+        }
+
+        Ok(Self {
+            base_url,
+            download_parallelism,
+            range_chunk_size,
+            client: http_client(config.proxy.as_ref())?,
+            started: Arc::new(AtomicBool::new(false)),
+        })
+    }
+
+    fn is_started(&self) -> bool {
+        self.started.load(Ordering::SeqCst)
+    }
+
+    /// The remote node runs on its own, starting only checks that it is reachable.
+    async fn start(&mut self) -> Result<(), StorageError> {
+        self.debug_info()
+            .await
+            .map_err(|e| StorageError::NodeStart(e.to_string()))?;
+        self.started.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    async fn stop(&mut self) -> Result<(), StorageError> {
+        self.started.store(false, Ordering::SeqCst);
+        Ok(())
+    }
+
+    async fn peer_id(&self) -> Result<String, StorageError> {
+        Ok(self.debug_info().await?.id)
+    }
+
+    async fn version(&self) -> Result<String, StorageError> {
+        Ok(self.debug_info().await?.codex.version)
+    }
+
+    async fn repo(&self) -> Result<String, StorageError> {
+        Ok(self.debug_info().await?.repo)
+    }
+
+    async fn connect(&self, peer_id: &str, addresses: &[String]) -> Result<(), StorageError> {
+        let query: Vec<(&str, &str)> = addresses
+            .iter()
+            .map(|address| ("addrs", address.as_str()))
+            .collect();
+
+        self.client
+            .get(self.endpoint(&format!("/connect/{}", peer_id)))
+            .query(&query)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(request_error)?;
+        Ok(())
+    }
+
+    async fn debug(&self) -> Result<DebugInfo, StorageError> {
+        self.get_json("/debug/info").await
+    }
+
+    async fn upload(
+        &self,
+        file_path: &Path,
+        block_size: Option<usize>,
+        on_progress: TransferProgressCallback,
+    ) -> Result<String, StorageError> {
+        // The REST API always uses the node's configured block size
+        if let Some(block_size) = block_size {
+            println!(
+                "Ignoring block size {} for an upload through a remote node",
+                block_size
+            );
+        }
+
+        let file = tokio::fs::File::open(file_path)
+            .await
+            .map_err(|e| StorageError::Io(e.to_string()))?;
+        let total = file
+            .metadata()
+            .await
+            .map_err(|e| StorageError::Io(e.to_string()))?
+            .len() as usize;
+        let file_name = file_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let uploaded = Arc::new(AtomicUsize::new(0));
+        let stream = ReaderStream::new(file).inspect(move |chunk| {
+            if let Ok(chunk) = chunk {
+                let bytes = uploaded.fetch_add(chunk.len(), Ordering::SeqCst) + chunk.len();
+                on_progress(bytes, Some(total));
+            }
+        });
+
+        let response = self
+            .client
+            .post(self.endpoint("/data"))
+            .header(reqwest::header::CONTENT_TYPE, "application/octet-stream")
+            .header(
+                reqwest::header::CONTENT_DISPOSITION,
+                content_disposition(&file_name),
+            )
+            .body(reqwest::Body::wrap_stream(stream))
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| StorageError::Upload(e.to_string()))?;
+
+        response
+            .text()
+            .await
+            .map(|cid| cid.trim().to_string())
+            .map_err(|e| StorageError::Upload(e.to_string()))
+    }
+
+    async fn download(
+        &self,
+        cid: &str,
+        save_path: &Path,
+        on_progress: TransferProgressCallback,
+    ) -> Result<usize, StorageError> {
+        let url = self.endpoint(&data_path(cid, "/network/stream")?);
+
+        // Large downloads go faster as concurrent ranges on high-latency links
+        if self.download_parallelism > 1 {
+            if let Some(total) = self.ranged_size(&url).await {
+                if total > self.range_chunk_size {
+                    return self
+                        .download_ranges(&url, total, save_path, on_progress)
+                        .await;
+                }
+            }
+        }
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| StorageError::Download(e.to_string()))?;
+        let total = response.content_length().map(|length| length as usize);
+
+        let mut file = tokio::fs::File::create(save_path)
+            .await
+            .map_err(|e| StorageError::Io(e.to_string()))?;
+        let mut stream = response.bytes_stream();
+        let mut downloaded = 0;
+
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|e| StorageError::Download(e.to_string()))?;
+            file.write_all(&chunk)
+                .await
+                .map_err(|e| StorageError::Io(e.to_string()))?;
+            downloaded += chunk.len();
+            on_progress(downloaded, total);
+        }
+
+        file.flush()
+            .await
+            .map_err(|e| StorageError::Io(e.to_string()))?;
+        Ok(downloaded)
+    }
+
+    async fn space(&self) -> Result<StorageUsage, StorageError> {
+        let space: RemoteSpace = self.get_json("/space").await?;
+        Ok(StorageUsage {
+            used_bytes: space.quota_used_bytes + space.quota_reserved_bytes,
+            quota_bytes: space.quota_max_bytes,
+            total_blocks: space.total_blocks,
+        })
+    }
+
+    async fn exists(&self, cid: &str) -> Result<bool, StorageError> {
+        let exists: std::collections::HashMap<String, bool> =
+            self.get_json(&data_path(cid, "/exists")?).await?;
+        Ok(exists.get(cid).copied().unwrap_or(false))
+    }
+
+    async fn manifests(&self) -> Result<Vec<String>, StorageError> {
+        let datasets: RemoteDatasets = self.get_json("/data").await?;
+        Ok(datasets
+            .content
+            .into_iter()
+            .map(|dataset| dataset.cid)
+            .collect())
+    }
+
+    async fn fetch(&self, cid: &str) -> Result<(), StorageError> {
+        self.client
+            .post(self.endpoint(&data_path(cid, "/network")?))
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| StorageError::Download(e.to_string()))?;
+        Ok(())
+    }
+
+    fn supports_provider_lookup(&self) -> bool {
+        false
+    }
+
+    async fn find_providers(
+        &self,
+        _cid: &str,
+        _limit: usize,
+    ) -> Result<Vec<ProviderInfo>, StorageError> {
+        Err(StorageError::Unsupported(
+            "the REST API has no DHT provider lookup".to_string(),
+        ))
+    }
+
+    fn supports_announce(&self) -> bool {
+        false
+    }
+
+    async fn announce(&self, _cid: &str) -> Result<(), StorageError> {
+        Err(StorageError::Unsupported(
+            "the REST API has no explicit DHT announce".to_string(),
+        ))
+    }
+
+    async fn dataset_name(&self, cid: &str) -> Result<Option<String>, StorageError> {
+        let dataset: RemoteManifestResponse =
+            self.get_json(&data_path(cid, "/network/manifest")?).await?;
+        Ok(dataset.manifest.filename.filter(|name| !name.is_empty()))
+    }
+
+    async fn dataset_size(&self, cid: &str) -> Result<Option<u64>, StorageError> {
+        let dataset: RemoteManifestResponse =
+            self.get_json(&data_path(cid, "/network/manifest")?).await?;
+        Ok(dataset.manifest.dataset_size)
+    }
+
+    async fn delete(&self, cid: &str) -> Result<(), StorageError> {
+        self.client
+            .delete(self.endpoint(&data_path(cid, "")?))
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(request_error)?;
+        Ok(())
+    }
+}
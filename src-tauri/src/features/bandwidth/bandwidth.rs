@@ -0,0 +1,132 @@
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tauri::AppHandle;
+use tokio::sync::{Mutex, OnceCell};
+
+use crate::features::bandwidth::apply_bandwidth_cap;
+use crate::features::shared::{app_data_file, read_json_file, write_json_file, StorageError};
+
+pub const BANDWIDTH_FILE_NAME: &str = "bandwidth.json";
+
+/// Days of history kept, a little over a year.
+const HISTORY_DAYS: i64 = 400;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TransferDirection {
+    Upload,
+    Download,
+}
+
+/// Bytes transferred on one local calendar day.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct DailyBandwidth {
+    pub date: NaiveDate,
+    pub uploaded_bytes: u64,
+    pub downloaded_bytes: u64,
+}
+
+impl DailyBandwidth {
+    pub fn total_bytes(&self) -> u64 {
+        self.uploaded_bytes + self.downloaded_bytes
+    }
+}
+
+/// Daily transfer totals, mirrored to disk.
+///
+/// Only uploads and downloads made through the app are counted, the node
+/// doesn't report what it serves to other peers.
+pub struct BandwidthLedger {
+    days: Mutex<BTreeMap<NaiveDate, DailyBandwidth>>,
+    bandwidth_file: PathBuf,
+}
+
+impl BandwidthLedger {
+    pub fn load(bandwidth_file: PathBuf) -> Self {
+        let days = read_json_file(&bandwidth_file).unwrap_or_default();
+        Self {
+            days: Mutex::new(days),
+            bandwidth_file,
+        }
+    }
+
+    pub async fn record(
+        &self,
+        direction: TransferDirection,
+        bytes: u64,
+    ) -> Result<(), StorageError> {
+        let today = chrono::Local::now().date_naive();
+        let mut days = self.days.lock().await;
+        let day = days.entry(today).or_insert_with(|| DailyBandwidth {
+            date: today,
+            ..Default::default()
+        });
+        match direction {
+            TransferDirection::Upload => day.uploaded_bytes += bytes,
+            TransferDirection::Download => day.downloaded_bytes += bytes,
+        }
+
+        let oldest = today - chrono::Duration::days(HISTORY_DAYS);
+        days.retain(|date, _| *date > oldest);
+        write_json_file(&self.bandwidth_file, &*days)
+    }
+
+    /// Totals of the last `days` days including today, oldest first. Days
+    /// without transfers are listed with zero bytes.
+    pub async fn history(&self, days: u32) -> Vec<DailyBandwidth> {
+        let today = chrono::Local::now().date_naive();
+        let recorded = self.days.lock().await;
+        (0..days as i64)
+            .rev()
+            .map(|offset| today - chrono::Duration::days(offset))
+            .map(|date| {
+                recorded.get(&date).cloned().unwrap_or(DailyBandwidth {
+                    date,
+                    ..Default::default()
+                })
+            })
+            .collect()
+    }
+
+    /// Bytes transferred so far in the current calendar month.
+    pub async fn month_total(&self) -> u64 {
+        let today = chrono::Local::now().date_naive();
+        self.days
+            .lock()
+            .await
+            .values()
+            .filter(|day| day.date.year() == today.year() && day.date.month() == today.month())
+            .map(DailyBandwidth::total_bytes)
+            .sum()
+    }
+}
+
+// Global bandwidth ledger instance
+pub static BANDWIDTH_LEDGER: OnceCell<Arc<BandwidthLedger>> = OnceCell::const_new();
+
+pub async fn get_bandwidth_ledger_with_handle(
+    app_handle: &AppHandle,
+) -> Result<Arc<BandwidthLedger>, StorageError> {
+    BANDWIDTH_LEDGER
+        .get_or_try_init(|| async {
+            let bandwidth_file = app_data_file(app_handle, BANDWIDTH_FILE_NAME)?;
+            Ok(Arc::new(BandwidthLedger::load(bandwidth_file)))
+        })
+        .await
+        .map(Arc::clone)
+}
+
+/// Adds a finished transfer to today's totals and enforces the monthly cap.
+pub async fn record_bandwidth(app_handle: &AppHandle, direction: TransferDirection, bytes: u64) {
+    let result = match get_bandwidth_ledger_with_handle(app_handle).await {
+        Ok(ledger) => ledger.record(direction, bytes).await,
+        Err(e) => Err(e),
+    };
+    if let Err(e) = result {
+        eprintln!("Failed to record bandwidth: {}", e);
+    }
+    apply_bandwidth_cap(app_handle).await;
+}
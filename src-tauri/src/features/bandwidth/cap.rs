@@ -0,0 +1,91 @@
+use serde::Serialize;
+use specta::Type;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::AppHandle;
+
+use crate::features::bandwidth::get_bandwidth_ledger_with_handle;
+use crate::features::connection::get_storage_manager_with_handle;
+use crate::features::events::{emit_event, BANDWIDTH_STATUS_EVENT};
+use crate::features::settings::get_settings_store_with_handle;
+use crate::features::shared::StorageConnectionStatus;
+use crate::features::system::{active_metered_policy, hand_over_seeding_pause};
+
+// Whether the node was stopped by the cap, so only then it is restarted
+static SEEDING_PAUSED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct BandwidthStatus {
+    /// Bytes transferred so far in the current calendar month.
+    pub month_bytes: u64,
+    pub cap_bytes: Option<u64>,
+    pub seeding_paused: bool,
+}
+
+pub async fn bandwidth_status(app_handle: &AppHandle) -> BandwidthStatus {
+    let month_bytes = match get_bandwidth_ledger_with_handle(app_handle).await {
+        Ok(ledger) => ledger.month_total().await,
+        Err(_) => 0,
+    };
+    let cap_bytes = match get_settings_store_with_handle(app_handle).await {
+        Ok(store) => store
+            .get()
+            .await
+            .monthly_cap_gb
+            .map(|cap_gb| cap_gb * 1024 * 1024 * 1024),
+        Err(_) => None,
+    };
+    BandwidthStatus {
+        month_bytes,
+        cap_bytes,
+        seeding_paused: SEEDING_PAUSED.load(Ordering::SeqCst),
+    }
+}
+
+/// Stops the node once the monthly cap is reached and starts it again
+/// when a new month begins or the cap is raised.
+///
+/// Runs on every system check, so a node another policy restarted is
+/// stopped again while the cap is still reached.
+pub async fn apply_bandwidth_cap(app_handle: &AppHandle) {
+    let status = bandwidth_status(app_handle).await;
+    let over_cap = status
+        .cap_bytes
+        .is_some_and(|cap_bytes| status.month_bytes >= cap_bytes);
+
+    let Ok(manager) = get_storage_manager_with_handle(Some(app_handle.clone())).await else {
+        return;
+    };
+
+    if over_cap {
+        if manager.get_status().await == StorageConnectionStatus::Connected {
+            println!("Monthly bandwidth cap reached, pausing seeding");
+            match manager.stop_node().await {
+                Ok(()) => SEEDING_PAUSED.store(true, Ordering::SeqCst),
+                Err(e) => eprintln!("Failed to pause seeding: {}", e),
+            }
+            let _ = emit_event(
+                app_handle,
+                BANDWIDTH_STATUS_EVENT,
+                bandwidth_status(app_handle).await,
+            );
+        }
+    } else if SEEDING_PAUSED.swap(false, Ordering::SeqCst) {
+        if active_metered_policy(app_handle)
+            .await
+            .is_some_and(|policy| policy.pause_seeding)
+        {
+            // Still held back by the metered connection, which resumes it later
+            hand_over_seeding_pause();
+        } else {
+            println!("Under the monthly bandwidth cap, resuming seeding");
+            if let Err(e) = manager.start_node().await {
+                eprintln!("Failed to resume seeding: {}", e);
+            }
+        }
+        let _ = emit_event(
+            app_handle,
+            BANDWIDTH_STATUS_EVENT,
+            bandwidth_status(app_handle).await,
+        );
+    }
+}
@@ -0,0 +1,25 @@
+use crate::features::bandwidth::{
+    bandwidth_status, get_bandwidth_ledger_with_handle, BandwidthStatus, DailyBandwidth,
+};
+use crate::features::shared::{map_storage_error, CommandError};
+use tauri::AppHandle;
+
+/// Daily transfer totals of the last `days` days, oldest first.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_bandwidth_history(
+    days: u32,
+    app_handle: AppHandle,
+) -> Result<Vec<DailyBandwidth>, CommandError> {
+    let ledger = get_bandwidth_ledger_with_handle(&app_handle)
+        .await
+        .map_err(map_storage_error)?;
+    Ok(ledger.history(days.min(366)).await)
+}
+
+/// This month's usage against the monthly cap.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_bandwidth_status(app_handle: AppHandle) -> Result<BandwidthStatus, CommandError> {
+    Ok(bandwidth_status(&app_handle).await)
+}
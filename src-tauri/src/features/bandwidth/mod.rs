@@ -0,0 +1,7 @@
+pub mod bandwidth;
+pub mod cap;
+pub mod commands;
+
+pub use bandwidth::*;
+pub use cap::*;
+pub use commands::*;
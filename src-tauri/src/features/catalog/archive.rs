@@ -0,0 +1,98 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::Path;
+use tauri::AppHandle;
+
+use crate::features::catalog::{get_catalog_with_handle, CatalogEntry};
+use crate::features::shared::StorageError;
+use crate::features::shares::{get_share_registry_with_handle, Share};
+
+/// Format version written by this build; newer archives are refused.
+const CATALOG_ARCHIVE_VERSION: u32 = 1;
+
+/// The app's record of what it holds, without any blocks, for moving to
+/// another machine.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CatalogArchive {
+    pub version: u32,
+    /// Milliseconds since the Unix epoch.
+    pub exported_at: i64,
+    pub entries: Vec<CatalogEntry>,
+    #[serde(default)]
+    pub shares: Vec<Share>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CatalogArchiveImport {
+    pub entries_added: usize,
+    /// Entries already catalogued, which only had missing fields filled in.
+    pub entries_merged: usize,
+    pub shares_added: usize,
+    /// Shares whose name is already taken here.
+    pub shares_skipped: Vec<String>,
+}
+
+/// Writes the catalog, notes included, and the share records to `path`.
+pub async fn write_catalog_archive(
+    app_handle: &AppHandle,
+    path: &Path,
+) -> Result<CatalogArchive, StorageError> {
+    let catalog = get_catalog_with_handle(app_handle).await?;
+    let registry = get_share_registry_with_handle(app_handle).await?;
+    let archive = CatalogArchive {
+        version: CATALOG_ARCHIVE_VERSION,
+        exported_at: chrono::Utc::now().timestamp_millis(),
+        entries: catalog.list().await,
+        shares: registry.list().await,
+    };
+
+    let contents =
+        serde_json::to_vec_pretty(&archive).map_err(|e| StorageError::Io(e.to_string()))?;
+    std::fs::write(path, contents).map_err(|e| StorageError::Io(e.to_string()))?;
+    Ok(archive)
+}
+
+/// Merges an archive written by `write_catalog_archive` into this
+/// machine's catalog and share records.
+pub async fn read_catalog_archive(
+    app_handle: &AppHandle,
+    path: &Path,
+) -> Result<CatalogArchiveImport, StorageError> {
+    let contents = std::fs::read(path).map_err(|e| StorageError::Io(e.to_string()))?;
+    let archive: CatalogArchive = serde_json::from_slice(&contents)
+        .map_err(|e| StorageError::Configuration(format!("Invalid catalog archive: {}", e)))?;
+    if archive.version > CATALOG_ARCHIVE_VERSION {
+        return Err(StorageError::Unsupported(format!(
+            "catalog archive version {} is newer than this app supports",
+            archive.version
+        )));
+    }
+
+    let catalog = get_catalog_with_handle(app_handle).await?;
+    let (entries_added, entries_merged) = catalog.merge(archive.entries).await?;
+
+    let registry = get_share_registry_with_handle(app_handle).await?;
+    let existing: Vec<String> = registry
+        .list()
+        .await
+        .into_iter()
+        .map(|share| share.name)
+        .collect();
+    let mut shares_added = 0;
+    let mut shares_skipped = Vec::new();
+    for share in archive.shares {
+        if existing.contains(&share.name) {
+            shares_skipped.push(share.name);
+            continue;
+        }
+        registry.insert(share).await?;
+        shares_added += 1;
+    }
+
+    Ok(CatalogArchiveImport {
+        entries_added,
+        entries_merged,
+        shares_added,
+        shares_skipped,
+    })
+}
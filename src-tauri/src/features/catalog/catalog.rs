@@ -0,0 +1,248 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tauri::AppHandle;
+use tokio::sync::{Mutex, OnceCell};
+
+use crate::features::shared::{app_data_file, read_json_file, write_json_file, StorageError};
+
+pub const CATALOG_FILE_NAME: &str = "catalog.json";
+
+/// Detached signature over a dataset's CID made with the node's identity key.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+pub struct ManifestSignature {
+    pub peer_id: String,
+    /// `<public key>:<signature>`, both hex encoded, as shared with recipients.
+    pub signature: String,
+    pub signed_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+pub enum DatasetOrigin {
+    /// Published from this machine.
+    Uploaded,
+    /// Retrieved from the network.
+    Downloaded,
+}
+
+/// What the app knows about a dataset it uploaded or downloaded.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CatalogEntry {
+    pub cid: String,
+    pub name: Option<String>,
+    pub size: Option<u64>,
+    /// Milliseconds since the Unix epoch.
+    pub added_at: i64,
+    #[serde(default)]
+    pub signature: Option<ManifestSignature>,
+    /// Where the content was fetched from when uploaded from a URL.
+    #[serde(default)]
+    pub source_url: Option<String>,
+    #[serde(default)]
+    pub origin: Option<DatasetOrigin>,
+    /// Last upload, download or open, in milliseconds since the Unix epoch.
+    #[serde(default)]
+    pub last_accessed_at: Option<i64>,
+    /// Block size chosen for an upload, `None` for the node's default.
+    #[serde(default)]
+    pub block_size: Option<u64>,
+    /// When the local copy moves to the trash, in milliseconds since the
+    /// Unix epoch.
+    #[serde(default)]
+    pub expires_at: Option<i64>,
+    /// Where the content was last uploaded from or downloaded to.
+    #[serde(default)]
+    pub local_path: Option<String>,
+    /// Set while the dataset sits in the trash, in milliseconds since the Unix epoch.
+    #[serde(default)]
+    pub trashed_at: Option<i64>,
+    /// Set when a share of the dataset was revoked and its local copy
+    /// deleted, in milliseconds since the Unix epoch.
+    #[serde(default)]
+    pub revoked_at: Option<i64>,
+    /// Free text the user keeps about the content.
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+impl CatalogEntry {
+    pub fn new(cid: String) -> Self {
+        Self {
+            cid,
+            name: None,
+            size: None,
+            added_at: chrono::Utc::now().timestamp_millis(),
+            signature: None,
+            source_url: None,
+            origin: None,
+            last_accessed_at: None,
+            block_size: None,
+            expires_at: None,
+            local_path: None,
+            trashed_at: None,
+            revoked_at: None,
+            note: None,
+        }
+    }
+
+    /// Whether the CID, name or note contains `query`, ignoring case.
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.to_lowercase();
+        [Some(&self.cid), self.name.as_ref(), self.note.as_ref()]
+            .into_iter()
+            .flatten()
+            .any(|field| field.to_lowercase().contains(&query))
+    }
+}
+
+/// Datasets known to the app, keyed by CID and mirrored to disk.
+pub struct Catalog {
+    entries: Mutex<BTreeMap<String, CatalogEntry>>,
+    catalog_file: PathBuf,
+}
+
+impl Catalog {
+    pub fn load(catalog_file: PathBuf) -> Self {
+        let entries = read_json_file(&catalog_file).unwrap_or_default();
+        Self {
+            entries: Mutex::new(entries),
+            catalog_file,
+        }
+    }
+
+    pub async fn get(&self, cid: &str) -> Option<CatalogEntry> {
+        self.entries.lock().await.get(cid).cloned()
+    }
+
+    pub async fn list(&self) -> Vec<CatalogEntry> {
+        self.entries.lock().await.values().cloned().collect()
+    }
+
+    /// Updates the entry for `cid`, creating it first if needed.
+    pub async fn upsert(
+        &self,
+        cid: &str,
+        update: impl FnOnce(&mut CatalogEntry),
+    ) -> Result<CatalogEntry, StorageError> {
+        let mut entries = self.entries.lock().await;
+        let entry = entries
+            .entry(cid.to_string())
+            .or_insert_with(|| CatalogEntry::new(cid.to_string()));
+        update(entry);
+        let entry = entry.clone();
+
+        write_json_file(&self.catalog_file, &*entries)?;
+        Ok(entry)
+    }
+
+    /// Adds entries for unknown CIDs and fills in what known ones lack,
+    /// returning how many were added and how many merged into existing ones.
+    pub async fn merge(&self, imported: Vec<CatalogEntry>) -> Result<(usize, usize), StorageError> {
+        let mut entries = self.entries.lock().await;
+        let (mut added, mut merged) = (0, 0);
+        for import in imported {
+            let Some(entry) = entries.get_mut(&import.cid) else {
+                entries.insert(import.cid.clone(), import);
+                added += 1;
+                continue;
+            };
+            entry.name = entry.name.take().or(import.name);
+            entry.size = entry.size.or(import.size);
+            entry.added_at = entry.added_at.min(import.added_at);
+            entry.signature = entry.signature.take().or(import.signature);
+            entry.source_url = entry.source_url.take().or(import.source_url);
+            entry.origin = entry.origin.take().or(import.origin);
+            entry.block_size = entry.block_size.or(import.block_size);
+            entry.note = entry.note.take().or(import.note);
+            merged += 1;
+        }
+
+        write_json_file(&self.catalog_file, &*entries)?;
+        Ok((added, merged))
+    }
+
+    /// Records a finished upload or download of `cid`. Content transferred
+    /// again is wanted again, so it also comes back out of the trash.
+    pub async fn record_transfer(
+        &self,
+        cid: &str,
+        name: Option<String>,
+        size: u64,
+        origin: DatasetOrigin,
+        local_path: Option<String>,
+    ) -> Result<CatalogEntry, StorageError> {
+        self.upsert(cid, |entry| {
+            entry.name = entry.name.take().or(name);
+            entry.size = Some(size);
+            // Content we published stays ours even if we download it again
+            if entry.origin != Some(DatasetOrigin::Uploaded) {
+                entry.origin = Some(origin);
+            }
+            entry.last_accessed_at = Some(chrono::Utc::now().timestamp_millis());
+            entry.local_path = local_path.or(entry.local_path.take());
+            entry.trashed_at = None;
+        })
+        .await
+    }
+
+    /// CIDs of the trashed datasets, only those trashed before
+    /// `trashed_before` if given.
+    pub async fn trashed_cids(&self, trashed_before: Option<i64>) -> Vec<String> {
+        self.entries
+            .lock()
+            .await
+            .values()
+            .filter(|entry| {
+                entry.trashed_at.is_some_and(|trashed_at| {
+                    trashed_before.map_or(true, |before| trashed_at <= before)
+                })
+            })
+            .map(|entry| entry.cid.clone())
+            .collect()
+    }
+
+    pub async fn remove(&self, cid: &str) -> Result<Option<CatalogEntry>, StorageError> {
+        let mut entries = self.entries.lock().await;
+        let removed = entries.remove(cid);
+        if removed.is_some() {
+            write_json_file(&self.catalog_file, &*entries)?;
+        }
+        Ok(removed)
+    }
+}
+
+// Global catalog instance
+pub static CATALOG: OnceCell<Arc<Catalog>> = OnceCell::const_new();
+
+pub async fn get_catalog_with_handle(app_handle: &AppHandle) -> Result<Arc<Catalog>, StorageError> {
+    CATALOG
+        .get_or_try_init(|| async {
+            let catalog_file = app_data_file(app_handle, CATALOG_FILE_NAME)?;
+            Ok(Arc::new(Catalog::load(catalog_file)))
+        })
+        .await
+        .map(Arc::clone)
+}
+
+/// Records a finished transfer, keeping whatever the catalog already knew.
+pub async fn record_in_catalog(
+    app_handle: &AppHandle,
+    cid: &str,
+    name: Option<String>,
+    size: u64,
+    origin: DatasetOrigin,
+    local_path: Option<String>,
+) {
+    let result = match get_catalog_with_handle(app_handle).await {
+        Ok(catalog) => catalog
+            .record_transfer(cid, name, size, origin, local_path)
+            .await
+            .map(|_| ()),
+        Err(e) => Err(e),
+    };
+    if let Err(e) = result {
+        eprintln!("Failed to update catalog for {}: {}", cid, e);
+    }
+}
@@ -0,0 +1,217 @@
+use crate::features::catalog::{
+    fix_catalog_mismatch, get_catalog_with_handle, import_cid_list_file, purge_trash,
+    read_catalog_archive, reconcile_catalog, restore_trashed, validate_expiry,
+    write_catalog_archive, CatalogArchiveImport, CatalogEntry, CatalogReconciliation,
+    CidListImport, ReconcileAction,
+};
+use crate::features::confirmation::{consume_confirmation, ProtectedAction};
+use crate::features::shared::{map_storage_error, validate_user_path, CommandError, StorageError};
+use tauri::AppHandle;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_catalog(app_handle: AppHandle) -> Result<Vec<CatalogEntry>, CommandError> {
+    let catalog = get_catalog_with_handle(&app_handle)
+        .await
+        .map_err(map_storage_error)?;
+    Ok(catalog
+        .list()
+        .await
+        .into_iter()
+        .filter(|entry| entry.trashed_at.is_none())
+        .collect())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_catalog_entry(
+    cid: String,
+    app_handle: AppHandle,
+) -> Result<Option<CatalogEntry>, CommandError> {
+    let catalog = get_catalog_with_handle(&app_handle)
+        .await
+        .map_err(map_storage_error)?;
+    Ok(catalog.get(&cid).await)
+}
+
+/// Sets or clears when the local copy of a catalogued dataset moves to the
+/// trash.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_dataset_expiry(
+    cid: String,
+    expires_at: Option<i64>,
+    app_handle: AppHandle,
+) -> Result<CatalogEntry, CommandError> {
+    validate_expiry(expires_at).map_err(map_storage_error)?;
+    let catalog = get_catalog_with_handle(&app_handle)
+        .await
+        .map_err(map_storage_error)?;
+    if catalog.get(&cid).await.is_none() {
+        return Err(map_storage_error(StorageError::InvalidCid(format!(
+            "{} is not in the catalog",
+            cid
+        ))));
+    }
+    catalog
+        .upsert(&cid, |entry| entry.expires_at = expires_at)
+        .await
+        .map_err(map_storage_error)
+}
+
+/// Sets or clears the free-text note kept about a catalogued dataset.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_note(
+    cid: String,
+    text: Option<String>,
+    app_handle: AppHandle,
+) -> Result<CatalogEntry, CommandError> {
+    let catalog = get_catalog_with_handle(&app_handle)
+        .await
+        .map_err(map_storage_error)?;
+    if catalog.get(&cid).await.is_none() {
+        return Err(map_storage_error(StorageError::InvalidCid(format!(
+            "{} is not in the catalog",
+            cid
+        ))));
+    }
+    let note = text
+        .map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty());
+    catalog
+        .upsert(&cid, |entry| entry.note = note)
+        .await
+        .map_err(map_storage_error)
+}
+
+/// Catalogued datasets whose CID, name or note contains `query`.
+#[tauri::command]
+#[specta::specta]
+pub async fn search_catalog(
+    query: String,
+    app_handle: AppHandle,
+) -> Result<Vec<CatalogEntry>, CommandError> {
+    let catalog = get_catalog_with_handle(&app_handle)
+        .await
+        .map_err(map_storage_error)?;
+    Ok(catalog
+        .list()
+        .await
+        .into_iter()
+        .filter(|entry| entry.trashed_at.is_none() && entry.matches(query.trim()))
+        .collect())
+}
+
+/// Catalogs a published list of CIDs, optionally queueing downloads to mirror them.
+#[tauri::command]
+#[specta::specta]
+pub async fn import_cid_list(
+    path: String,
+    mirror: Option<bool>,
+    app_handle: AppHandle,
+) -> Result<CidListImport, CommandError> {
+    let path = validate_user_path(&app_handle, &path).map_err(map_storage_error)?;
+    import_cid_list_file(&path, mirror.unwrap_or(false), &app_handle)
+        .await
+        .map_err(map_storage_error)
+}
+
+/// Writes the catalog and share records, not the blocks, to a file for
+/// moving to another machine. Returns how many entries were written.
+#[tauri::command]
+#[specta::specta]
+pub async fn export_catalog(path: String, app_handle: AppHandle) -> Result<usize, CommandError> {
+    let path = validate_user_path(&app_handle, &path).map_err(map_storage_error)?;
+    write_catalog_archive(&app_handle, &path)
+        .await
+        .map(|archive| archive.entries.len())
+        .map_err(map_storage_error)
+}
+
+/// Merges a file written by `export_catalog` into this machine's catalog.
+#[tauri::command]
+#[specta::specta]
+pub async fn import_catalog(
+    path: String,
+    app_handle: AppHandle,
+) -> Result<CatalogArchiveImport, CommandError> {
+    let path = validate_user_path(&app_handle, &path).map_err(map_storage_error)?;
+    read_catalog_archive(&app_handle, &path)
+        .await
+        .map_err(map_storage_error)
+}
+
+/// Lists where the catalog and the repo disagree, as done at launch.
+#[tauri::command]
+#[specta::specta]
+pub async fn check_catalog_consistency(
+    app_handle: AppHandle,
+) -> Result<CatalogReconciliation, CommandError> {
+    reconcile_catalog(&app_handle)
+        .await
+        .map_err(map_storage_error)
+}
+
+/// Re-pins, adopts or purges a dataset flagged by the consistency check.
+/// Purging needs a confirmation token.
+#[tauri::command]
+#[specta::specta]
+pub async fn resolve_catalog_mismatch(
+    cid: String,
+    action: ReconcileAction,
+    confirmation_token: Option<String>,
+    app_handle: AppHandle,
+) -> Result<(), CommandError> {
+    if action == ReconcileAction::Purge {
+        consume_confirmation(
+            ProtectedAction::PurgeCatalogEntry,
+            confirmation_token.as_deref().unwrap_or_default(),
+        )
+        .await
+        .map_err(map_storage_error)?;
+    }
+    fix_catalog_mismatch(&app_handle, &cid, action)
+        .await
+        .map_err(map_storage_error)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_trash(app_handle: AppHandle) -> Result<Vec<CatalogEntry>, CommandError> {
+    let catalog = get_catalog_with_handle(&app_handle)
+        .await
+        .map_err(map_storage_error)?;
+    Ok(catalog
+        .list()
+        .await
+        .into_iter()
+        .filter(|entry| entry.trashed_at.is_some())
+        .collect())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn restore_from_trash(
+    cid: String,
+    app_handle: AppHandle,
+) -> Result<CatalogEntry, CommandError> {
+    restore_trashed(&app_handle, &cid)
+        .await
+        .map_err(map_storage_error)
+}
+
+/// Deletes everything in the trash now, returning the removed CIDs.
+#[tauri::command]
+#[specta::specta]
+pub async fn empty_trash(
+    confirmation_token: String,
+    app_handle: AppHandle,
+) -> Result<Vec<String>, CommandError> {
+    consume_confirmation(ProtectedAction::EmptyTrash, &confirmation_token)
+        .await
+        .map_err(map_storage_error)?;
+    purge_trash(&app_handle, None)
+        .await
+        .map_err(map_storage_error)
+}
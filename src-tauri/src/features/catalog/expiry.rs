@@ -0,0 +1,75 @@
+use std::time::Duration;
+use tauri::AppHandle;
+
+use crate::features::catalog::{get_catalog_with_handle, move_to_trash, purge_expired_trash};
+use crate::features::shared::StorageError;
+
+const EXPIRY_CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const NODE_WAIT_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Refuses an expiry that has already passed. The schedule would act on it
+/// within the hour with nobody confirming, use the trash for that instead.
+pub fn validate_expiry(expires_at: Option<i64>) -> Result<(), StorageError> {
+    if expires_at.is_some_and(|expires_at| expires_at <= chrono::Utc::now().timestamp_millis()) {
+        return Err(StorageError::Configuration(
+            "Expiry must be in the future".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Moves every dataset whose catalog expiry has passed to the trash,
+/// returning the CIDs trashed. They stay restorable until the trash
+/// retention runs out; the expiry is cleared so a restore sticks.
+pub async fn trash_expired_datasets(app_handle: &AppHandle) -> Result<Vec<String>, StorageError> {
+    let catalog = get_catalog_with_handle(app_handle).await?;
+    let now = chrono::Utc::now().timestamp_millis();
+    let expired: Vec<String> = catalog
+        .list()
+        .await
+        .into_iter()
+        .filter(|entry| {
+            entry.trashed_at.is_none()
+                && entry.expires_at.is_some_and(|expires_at| expires_at <= now)
+        })
+        .map(|entry| entry.cid)
+        .collect();
+
+    for cid in &expired {
+        move_to_trash(app_handle, cid).await?;
+        catalog.upsert(cid, |entry| entry.expires_at = None).await?;
+    }
+    Ok(expired)
+}
+
+/// Periodically trashes expired datasets and purges trash past its
+/// retention period for as long as the app is running.
+pub async fn run_expiry_schedule(app_handle: AppHandle) {
+    loop {
+        let result = match trash_expired_datasets(&app_handle).await {
+            Ok(trashed) => purge_expired_trash(&app_handle)
+                .await
+                .map(|purged| (trashed, purged)),
+            Err(e) => Err(e),
+        };
+        let delay = match result {
+            Ok((trashed, purged)) => {
+                if !trashed.is_empty() {
+                    println!("Moved {} expired dataset(s) to the trash", trashed.len());
+                }
+                if !purged.is_empty() {
+                    println!("Purged {} dataset(s) from the trash", purged.len());
+                }
+                EXPIRY_CHECK_INTERVAL
+            }
+            // Try again soon once the node is up
+            Err(StorageError::NodeNotStarted) => NODE_WAIT_INTERVAL,
+            Err(e) => {
+                eprintln!("Failed to remove expired datasets: {}", e);
+                EXPIRY_CHECK_INTERVAL
+            }
+        };
+
+        tokio::time::sleep(delay).await;
+    }
+}
@@ -0,0 +1,132 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashSet;
+use std::path::Path;
+use tauri::AppHandle;
+
+use crate::features::catalog::get_catalog_with_handle;
+use crate::features::download::{sanitize_file_name, unique_path};
+use crate::features::queue::{enqueue_download_unless_present, EnqueueDownloadResult};
+use crate::features::settings::get_settings_store_with_handle;
+use crate::features::shared::StorageError;
+use crate::features::system::extract_cid;
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ListedCid {
+    Cid(String),
+    Named { cid: String, name: Option<String> },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CidListImport {
+    /// CIDs added to or already in the catalog.
+    pub imported: Vec<String>,
+    /// Entries that didn't parse as a CID.
+    pub skipped: Vec<String>,
+    /// Operation ids of the downloads queued to mirror the list.
+    pub queued: Vec<String>,
+}
+
+/// Reads a JSON array of CIDs or `{ "cid", "name" }` objects, or else one
+/// CID per line optionally followed by a name. Blank lines and `#`
+/// comments are ignored.
+fn parse_cid_list(contents: &str) -> Vec<(String, Option<String>)> {
+    if let Ok(listed) = serde_json::from_str::<Vec<ListedCid>>(contents) {
+        return listed
+            .into_iter()
+            .map(|listed| match listed {
+                ListedCid::Cid(cid) => (cid, None),
+                ListedCid::Named { cid, name } => (cid, name),
+            })
+            .collect();
+    }
+
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(
+            |line| match line.split_once(|c: char| c.is_whitespace() || c == ',') {
+                Some((cid, name)) => {
+                    let name = name.trim_matches(|c: char| c.is_whitespace() || c == ',');
+                    (
+                        cid.to_string(),
+                        Some(name.to_string()).filter(|name| !name.is_empty()),
+                    )
+                }
+                None => (line.to_string(), None),
+            },
+        )
+        .collect()
+}
+
+/// Adds the CIDs listed in a file to the catalog and, with `mirror`,
+/// queues a download of each into the downloads directory.
+pub async fn import_cid_list_file(
+    path: &Path,
+    mirror: bool,
+    app_handle: &AppHandle,
+) -> Result<CidListImport, StorageError> {
+    let contents = std::fs::read_to_string(path).map_err(|e| StorageError::Io(e.to_string()))?;
+    let catalog = get_catalog_with_handle(app_handle).await?;
+
+    let downloads_dir = if mirror {
+        let settings = get_settings_store_with_handle(app_handle)
+            .await?
+            .get()
+            .await;
+        let downloads_dir = settings.resolve_downloads_dir()?;
+        std::fs::create_dir_all(&downloads_dir).map_err(|e| StorageError::Io(e.to_string()))?;
+        Some(downloads_dir)
+    } else {
+        None
+    };
+
+    let mut import = CidListImport {
+        imported: Vec::new(),
+        skipped: Vec::new(),
+        queued: Vec::new(),
+    };
+    // Queued downloads don't exist on disk yet, so track the names handed out
+    let mut used_names = HashSet::new();
+
+    for (listed, name) in parse_cid_list(&contents) {
+        let Some(cid) = extract_cid(&listed) else {
+            import.skipped.push(listed);
+            continue;
+        };
+
+        let entry = catalog
+            .upsert(&cid, |entry| {
+                if entry.name.is_none() {
+                    entry.name = name;
+                }
+            })
+            .await?;
+
+        if let Some(downloads_dir) = &downloads_dir {
+            let mut file_name = sanitize_file_name(entry.name.as_deref().unwrap_or_default(), &cid);
+            if !used_names.insert(file_name.clone()) {
+                file_name = cid.clone();
+                used_names.insert(file_name.clone());
+            }
+            let save_path = unique_path(downloads_dir, &file_name);
+            // Datasets we already hold don't need mirroring again
+            let result = enqueue_download_unless_present(
+                app_handle,
+                cid.clone(),
+                save_path.to_string_lossy().to_string(),
+                false,
+            )
+            .await?;
+            if let EnqueueDownloadResult::Queued { operation_id } = result {
+                import.queued.push(operation_id);
+            }
+        }
+
+        import.imported.push(cid);
+    }
+
+    Ok(import)
+}
@@ -0,0 +1,18 @@
+pub mod archive;
+pub mod catalog;
+pub mod commands;
+pub mod expiry;
+pub mod import;
+pub mod reconcile;
+pub mod trash;
+
+#[cfg(test)]
+mod tests;
+
+pub use archive::*;
+pub use catalog::*;
+pub use commands::*;
+pub use expiry::*;
+pub use import::*;
+pub use reconcile::*;
+pub use trash::*;
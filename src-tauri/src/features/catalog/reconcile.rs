@@ -0,0 +1,134 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::time::Duration;
+use tauri::AppHandle;
+
+use crate::features::backend::StorageBackend;
+use crate::features::catalog::get_catalog_with_handle;
+use crate::features::connection::get_storage_manager_with_handle;
+use crate::features::events::{emit_event, CATALOG_RECONCILED_EVENT};
+use crate::features::peers::connect_to_providers;
+use crate::features::shared::StorageError;
+use crate::features::storage::{block_exists, ensure_quota_for_dataset};
+
+const NODE_WAIT_INTERVAL: Duration = Duration::from_secs(10);
+/// Gives up on the startup check if the node isn't up by then.
+const NODE_WAIT_ATTEMPTS: u32 = 30;
+
+/// Where the catalog and the repo disagree.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Default)]
+pub struct CatalogReconciliation {
+    /// Uploaded or downloaded datasets whose data is gone from the repo.
+    pub missing_locally: Vec<String>,
+    /// Datasets in the repo the catalog doesn't know about.
+    pub unknown_to_catalog: Vec<String>,
+}
+
+impl CatalogReconciliation {
+    pub fn is_consistent(&self) -> bool {
+        self.missing_locally.is_empty() && self.unknown_to_catalog.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReconcileAction {
+    /// Fetch a missing dataset from the network again.
+    Repin,
+    /// Add a dataset the catalog doesn't know to it.
+    Adopt,
+    /// Drop the catalog entry of a missing dataset.
+    Purge,
+}
+
+/// Compares the catalog with the repo's manifests.
+pub async fn reconcile_catalog(
+    app_handle: &AppHandle,
+) -> Result<CatalogReconciliation, StorageError> {
+    let manager = get_storage_manager_with_handle(Some(app_handle.clone())).await?;
+    let node = manager.get_node().await?;
+    if !node.is_started() {
+        return Err(StorageError::NodeNotStarted);
+    }
+
+    let manifests = node.manifests().await?;
+    let entries = get_catalog_with_handle(app_handle).await?.list().await;
+
+    let mut report = CatalogReconciliation::default();
+    for entry in &entries {
+        // Entries imported from lists or share files were never stored, and
+        // trashed or revoked ones are meant to be gone
+        if entry.origin.is_none() || entry.trashed_at.is_some() || entry.revoked_at.is_some() {
+            continue;
+        }
+        let stored = manifests.contains(&entry.cid)
+            && block_exists(&node, &entry.cid).await.unwrap_or(false);
+        if !stored {
+            report.missing_locally.push(entry.cid.clone());
+        }
+    }
+    report.unknown_to_catalog = manifests
+        .into_iter()
+        .filter(|cid| !entries.iter().any(|entry| &entry.cid == cid))
+        .collect();
+    Ok(report)
+}
+
+/// Applies one of the fixes offered in a reconciliation report.
+pub async fn fix_catalog_mismatch(
+    app_handle: &AppHandle,
+    cid: &str,
+    action: ReconcileAction,
+) -> Result<(), StorageError> {
+    let catalog = get_catalog_with_handle(app_handle).await?;
+    match action {
+        ReconcileAction::Repin => {
+            let manager = get_storage_manager_with_handle(Some(app_handle.clone())).await?;
+            manager.ensure_writable()?;
+            let node = manager.get_node().await?;
+            if !node.is_started() {
+                return Err(StorageError::NodeNotStarted);
+            }
+            connect_to_providers(&manager, cid).await;
+            ensure_quota_for_dataset(&node, cid).await?;
+            node.fetch(cid).await
+        }
+        ReconcileAction::Adopt => {
+            let manager = get_storage_manager_with_handle(Some(app_handle.clone())).await?;
+            let node = manager.get_node().await?;
+            let name = node.dataset_name(cid).await.unwrap_or(None);
+            catalog
+                .upsert(cid, |entry| entry.name = entry.name.take().or(name))
+                .await
+                .map(|_| ())
+        }
+        ReconcileAction::Purge => catalog.remove(cid).await.map(|_| ()),
+    }
+}
+
+/// Reconciles the catalog once the node is up after launch and reports
+/// any mismatch to the frontend.
+pub async fn run_startup_reconciliation(app_handle: AppHandle) {
+    for _ in 0..NODE_WAIT_ATTEMPTS {
+        match reconcile_catalog(&app_handle).await {
+            Ok(report) => {
+                if !report.is_consistent() {
+                    println!(
+                        "Catalog mismatch: {} missing locally, {} unknown to the catalog",
+                        report.missing_locally.len(),
+                        report.unknown_to_catalog.len()
+                    );
+                }
+                let _ = emit_event(&app_handle, CATALOG_RECONCILED_EVENT, report);
+                return;
+            }
+            Err(StorageError::NodeNotStarted) | Err(StorageError::NodeNotInitialized) => {
+                tokio::time::sleep(NODE_WAIT_INTERVAL).await
+            }
+            Err(e) => {
+                eprintln!("Failed to reconcile catalog: {}", e);
+                return;
+            }
+        }
+    }
+}
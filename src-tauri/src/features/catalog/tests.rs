@@ -0,0 +1,30 @@
+use crate::features::catalog::{Catalog, DatasetOrigin};
+
+fn temp_catalog() -> Catalog {
+    let dir = std::env::temp_dir().join(format!("storeman-test-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).unwrap();
+    Catalog::load(dir.join("catalog.json"))
+}
+
+#[tokio::test]
+async fn transferring_trashed_content_again_restores_it() {
+    let catalog = temp_catalog();
+    catalog
+        .record_transfer("cid", None, 10, DatasetOrigin::Uploaded, None)
+        .await
+        .unwrap();
+    catalog
+        .upsert("cid", |entry| entry.trashed_at = Some(0))
+        .await
+        .unwrap();
+    assert_eq!(catalog.trashed_cids(None).await, vec!["cid".to_string()]);
+
+    let entry = catalog
+        .record_transfer("cid", None, 10, DatasetOrigin::Uploaded, None)
+        .await
+        .unwrap();
+
+    assert_eq!(entry.trashed_at, None);
+    // Purging the trash must leave the new copy alone
+    assert!(catalog.trashed_cids(None).await.is_empty());
+}
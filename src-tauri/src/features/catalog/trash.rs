@@ -0,0 +1,105 @@
+use tauri::AppHandle;
+
+use crate::features::audit::record_audit;
+use crate::features::backend::StorageBackend;
+use crate::features::catalog::{get_catalog_with_handle, CatalogEntry};
+use crate::features::connection::get_storage_manager_with_handle;
+use crate::features::settings::get_settings_store_with_handle;
+use crate::features::shared::{StorageConnectionStatus, StorageError};
+
+const MILLIS_PER_DAY: i64 = 24 * 60 * 60 * 1000;
+
+/// Deletes datasets from the node's repo and the catalog, returning the
+/// CIDs removed. Each deletion lands in the audit log as `command`.
+pub async fn delete_local_datasets(
+    app_handle: &AppHandle,
+    cids: Vec<String>,
+    command: &str,
+) -> Result<Vec<String>, StorageError> {
+    if cids.is_empty() {
+        return Ok(cids);
+    }
+
+    let manager = get_storage_manager_with_handle(Some(app_handle.clone())).await?;
+    if manager.get_status().await != StorageConnectionStatus::Connected {
+        return Err(StorageError::NodeNotStarted);
+    }
+    let node = manager.get_node().await?;
+    let catalog = get_catalog_with_handle(app_handle).await?;
+
+    let mut removed = Vec::new();
+    for cid in cids {
+        let result = node.delete(&cid).await;
+        record_audit(app_handle, command, &[&cid], &result).await;
+        match result {
+            Ok(()) => {
+                catalog.remove(&cid).await?;
+                removed.push(cid);
+            }
+            Err(e) => eprintln!("Failed to delete dataset {}: {}", cid, e),
+        }
+    }
+    Ok(removed)
+}
+
+/// Marks a dataset as deleted while keeping its blocks for the retention
+/// period, so an accidental deletion can still be undone.
+pub async fn move_to_trash(
+    app_handle: &AppHandle,
+    cid: &str,
+) -> Result<CatalogEntry, StorageError> {
+    let catalog = get_catalog_with_handle(app_handle).await?;
+    let entry = catalog
+        .upsert(cid, |entry| {
+            entry.trashed_at = Some(chrono::Utc::now().timestamp_millis())
+        })
+        .await?;
+    record_audit(
+        app_handle,
+        "trash_dataset",
+        &[cid],
+        &Ok::<(), StorageError>(()),
+    )
+    .await;
+    Ok(entry)
+}
+
+pub async fn restore_trashed(
+    app_handle: &AppHandle,
+    cid: &str,
+) -> Result<CatalogEntry, StorageError> {
+    let catalog = get_catalog_with_handle(app_handle).await?;
+    if !catalog
+        .get(cid)
+        .await
+        .is_some_and(|entry| entry.trashed_at.is_some())
+    {
+        return Err(StorageError::InvalidCid(format!(
+            "{} is not in the trash",
+            cid
+        )));
+    }
+    catalog.upsert(cid, |entry| entry.trashed_at = None).await
+}
+
+/// Deletes trashed datasets for good, only those trashed before
+/// `trashed_before` if given.
+pub async fn purge_trash(
+    app_handle: &AppHandle,
+    trashed_before: Option<i64>,
+) -> Result<Vec<String>, StorageError> {
+    let catalog = get_catalog_with_handle(app_handle).await?;
+    let trashed = catalog.trashed_cids(trashed_before).await;
+    delete_local_datasets(app_handle, trashed, "delete_dataset").await
+}
+
+/// Deletes trashed datasets whose retention period has run out.
+pub async fn purge_expired_trash(app_handle: &AppHandle) -> Result<Vec<String>, StorageError> {
+    let retention_days = get_settings_store_with_handle(app_handle)
+        .await?
+        .get()
+        .await
+        .trash_retention_days;
+    let cutoff = chrono::Utc::now().timestamp_millis() - retention_days as i64 * MILLIS_PER_DAY;
+    purge_trash(app_handle, Some(cutoff)).await
+}
@@ -0,0 +1,203 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tauri::AppHandle;
+
+use crate::features::catalog::{get_catalog_with_handle, move_to_trash, DatasetOrigin};
+use crate::features::recent::{get_recent_items_with_handle, RecentAction};
+use crate::features::settings::get_settings_store_with_handle;
+use crate::features::shared::StorageError;
+
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CleanupCandidate {
+    pub path: String,
+    pub size: u64,
+    pub age_days: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CleanupReport {
+    pub downloads_dir: String,
+    pub candidates: Vec<CleanupCandidate>,
+    pub total_bytes: u64,
+    /// Downloaded datasets moved to the trash, or that would be in a dry run.
+    pub trashed_datasets: Vec<String>,
+    pub dry_run: bool,
+    pub errors: Vec<String>,
+}
+
+/// Files the app downloaded, as recorded in the catalog and recent items.
+/// Nothing else in the downloads directory is ever touched.
+async fn recorded_downloads(app_handle: &AppHandle) -> Result<HashSet<PathBuf>, StorageError> {
+    let catalog = get_catalog_with_handle(app_handle).await?.list().await;
+    let recent = get_recent_items_with_handle(app_handle)
+        .await?
+        .list(usize::MAX)
+        .await;
+
+    let catalog_paths = catalog
+        .into_iter()
+        .filter(|entry| entry.origin == Some(DatasetOrigin::Downloaded))
+        .filter_map(|entry| entry.local_path);
+    let recent_paths = recent
+        .into_iter()
+        .filter(|item| item.action == RecentAction::Downloaded)
+        .filter_map(|item| item.path);
+    Ok(catalog_paths
+        .chain(recent_paths)
+        .filter_map(|path| std::fs::canonicalize(path).ok())
+        .collect())
+}
+
+/// The recorded downloads inside `downloads_dir` last modified more than
+/// `max_age` ago.
+fn collect_expired_files(
+    downloads_dir: &Path,
+    downloads: HashSet<PathBuf>,
+    max_age: Duration,
+    now: SystemTime,
+) -> Vec<CleanupCandidate> {
+    let mut candidates: Vec<CleanupCandidate> = downloads
+        .into_iter()
+        .filter(|path| path.starts_with(downloads_dir))
+        .filter_map(|path| {
+            let metadata = std::fs::metadata(&path)
+                .ok()
+                .filter(|metadata| metadata.is_file())?;
+            let age = metadata
+                .modified()
+                .ok()
+                .and_then(|modified| now.duration_since(modified).ok())
+                .unwrap_or_default();
+            (age > max_age).then(|| CleanupCandidate {
+                path: path.to_string_lossy().to_string(),
+                size: metadata.len(),
+                age_days: age.as_secs() / SECONDS_PER_DAY,
+            })
+        })
+        .collect();
+    candidates.sort_by(|a, b| a.path.cmp(&b.path));
+    candidates
+}
+
+/// Downloaded datasets not transferred or opened for longer than `max_age`
+/// and not in the trash yet.
+async fn expired_datasets(
+    app_handle: &AppHandle,
+    max_age: Duration,
+) -> Result<Vec<String>, StorageError> {
+    let cutoff = chrono::Utc::now().timestamp_millis()
+        - i64::try_from(max_age.as_millis()).unwrap_or(i64::MAX);
+    Ok(get_catalog_with_handle(app_handle)
+        .await?
+        .list()
+        .await
+        .into_iter()
+        .filter(|entry| {
+            entry.origin == Some(DatasetOrigin::Downloaded)
+                && entry.trashed_at.is_none()
+                && entry.last_accessed_at.unwrap_or(entry.added_at) < cutoff
+        })
+        .map(|entry| entry.cid)
+        .collect())
+}
+
+/// Applies the configured cleanup policy to the files the app downloaded
+/// into the downloads directory, and to their datasets if it says so.
+/// Datasets go through the trash, so they stay restorable for its
+/// retention period.
+///
+/// With `dry_run` set nothing is deleted and the report lists what would be removed.
+pub async fn cleanup_downloads(
+    app_handle: &AppHandle,
+    dry_run: bool,
+) -> Result<CleanupReport, StorageError> {
+    let settings = get_settings_store_with_handle(app_handle)
+        .await?
+        .get()
+        .await;
+    let policy = settings
+        .cleanup_policy
+        .clone()
+        .ok_or_else(|| StorageError::Configuration("Auto-cleanup is not enabled".to_string()))?;
+    let downloads_dir = settings.resolve_downloads_dir()?;
+    let max_age = Duration::from_secs(policy.max_age_days.saturating_mul(SECONDS_PER_DAY));
+
+    let mut candidates = Vec::new();
+    if let Ok(downloads_dir) = std::fs::canonicalize(&downloads_dir) {
+        candidates = collect_expired_files(
+            &downloads_dir,
+            recorded_downloads(app_handle).await?,
+            max_age,
+            SystemTime::now(),
+        );
+    }
+
+    let mut trashed_datasets = Vec::new();
+    if policy.drop_blocks {
+        trashed_datasets = expired_datasets(app_handle, max_age).await?;
+    }
+
+    let mut errors = Vec::new();
+    if !dry_run {
+        candidates.retain(|candidate| match std::fs::remove_file(&candidate.path) {
+            Ok(()) => true,
+            Err(e) => {
+                errors.push(format!("{}: {}", candidate.path, e));
+                false
+            }
+        });
+
+        let mut trashed = Vec::new();
+        for cid in trashed_datasets {
+            match move_to_trash(app_handle, &cid).await {
+                Ok(_) => trashed.push(cid),
+                Err(e) => errors.push(format!("{}: {}", cid, e)),
+            }
+        }
+        trashed_datasets = trashed;
+    }
+
+    Ok(CleanupReport {
+        downloads_dir: downloads_dir.to_string_lossy().to_string(),
+        total_bytes: candidates.iter().map(|candidate| candidate.size).sum(),
+        candidates,
+        trashed_datasets,
+        dry_run,
+        errors,
+    })
+}
+
+/// Periodically applies the cleanup policy while it is enabled.
+pub async fn run_cleanup_schedule(app_handle: AppHandle) {
+    loop {
+        let enabled = match get_settings_store_with_handle(&app_handle).await {
+            Ok(store) => store.get().await.cleanup_policy.is_some(),
+            Err(_) => false,
+        };
+
+        if enabled {
+            match cleanup_downloads(&app_handle, false).await {
+                Ok(report)
+                    if !report.candidates.is_empty() || !report.trashed_datasets.is_empty() =>
+                {
+                    println!(
+                        "Cleaned up {} old download(s), {} bytes freed, {} dataset(s) trashed",
+                        report.candidates.len(),
+                        report.total_bytes,
+                        report.trashed_datasets.len()
+                    )
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!("Failed to clean up downloads: {}", e),
+            }
+        }
+
+        tokio::time::sleep(CLEANUP_INTERVAL).await;
+    }
+}
@@ -0,0 +1,26 @@
+use crate::features::cleanup::{cleanup_downloads, CleanupReport};
+use crate::features::confirmation::{consume_confirmation, ProtectedAction};
+use crate::features::shared::{map_storage_error, CommandError};
+use tauri::AppHandle;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn preview_cleanup(app_handle: AppHandle) -> Result<CleanupReport, CommandError> {
+    cleanup_downloads(&app_handle, true)
+        .await
+        .map_err(map_storage_error)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn run_cleanup(
+    confirmation_token: String,
+    app_handle: AppHandle,
+) -> Result<CleanupReport, CommandError> {
+    consume_confirmation(ProtectedAction::RunCleanup, &confirmation_token)
+        .await
+        .map_err(map_storage_error)?;
+    cleanup_downloads(&app_handle, false)
+        .await
+        .map_err(map_storage_error)
+}
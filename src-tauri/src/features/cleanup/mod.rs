@@ -0,0 +1,5 @@
+pub mod cleanup;
+pub mod commands;
+
+pub use cleanup::*;
+pub use commands::*;
@@ -0,0 +1,158 @@
+use codex_bindings::CodexNode;
+use std::path::PathBuf;
+
+use crate::features::connection::{create_codex_config, recover_data_dir, StorageManager};
+use crate::features::download::download_file_with_manager;
+use crate::features::settings::{SettingsStore, SETTINGS_FILE_NAME};
+use crate::features::shared::StorageError;
+use crate::features::upload::upload_file_with_manager;
+
+pub const HEADLESS_FLAG: &str = "--headless";
+
+// Must match the identifier in tauri.conf.json so the headless node shares
+// its data directory with the desktop app
+const APP_IDENTIFIER: &str = "dev.nipsys.storeman";
+
+const USAGE: &str = "Usage:
+  storeman --headless status
+  storeman --headless upload <file>
+  storeman --headless download <cid> <save_path>
+  storeman --headless serve";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum HeadlessCommand {
+    Status,
+    Upload { file_path: String },
+    Download { cid: String, save_path: String },
+    Serve,
+}
+
+impl HeadlessCommand {
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        match args.as_slice() {
+            ["status"] => Ok(HeadlessCommand::Status),
+            ["upload", file_path] => Ok(HeadlessCommand::Upload {
+                file_path: file_path.to_string(),
+            }),
+            ["download", cid, save_path] => Ok(HeadlessCommand::Download {
+                cid: cid.to_string(),
+                save_path: save_path.to_string(),
+            }),
+            ["serve"] => Ok(HeadlessCommand::Serve),
+            _ => Err(USAGE.to_string()),
+        }
+    }
+}
+
+pub fn headless_requested() -> bool {
+    std::env::args().any(|arg| arg == HEADLESS_FLAG)
+}
+
+/// Same location Tauri resolves as `app_data_dir`, without needing an app handle.
+fn headless_app_data_dir() -> Result<PathBuf, StorageError> {
+    dirs::data_dir()
+        .map(|dir| dir.join(APP_IDENTIFIER))
+        .ok_or_else(|| {
+            StorageError::Configuration("Unable to determine app data directory".to_string())
+        })
+}
+
+/// Runs a single CLI subcommand against the desktop app's node data and
+/// returns the process exit code.
+pub fn run_headless() -> i32 {
+    let args: Vec<String> = std::env::args()
+        .skip(1)
+        .filter(|arg| arg != HEADLESS_FLAG)
+        .collect();
+
+    let command = match HeadlessCommand::parse(&args) {
+        Ok(command) => command,
+        Err(usage) => {
+            eprintln!("{}", usage);
+            return 2;
+        }
+    };
+
+    tauri::async_runtime::block_on(async move {
+        match execute(command).await {
+            Ok(()) => 0,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                1
+            }
+        }
+    })
+}
+
+async fn execute(command: HeadlessCommand) -> Result<(), StorageError> {
+    let app_data_dir = headless_app_data_dir()?;
+    let data_dir = app_data_dir.join("node_data");
+    // Fails while the desktop app or another CLI runs a node on the same data
+    let report = recover_data_dir(&data_dir)?;
+    if report.needs_attention() {
+        println!("Recovered node data directory: {:?}", report);
+    }
+
+    // The same settings the desktop app starts its node with
+    let settings = SettingsStore::load(app_data_dir.join(SETTINGS_FILE_NAME))
+        .get()
+        .await;
+    let config = create_codex_config(&data_dir, &settings)?;
+    let manager = StorageManager::<CodexNode>::new(config, data_dir).await?;
+    manager.set_read_only(settings.read_only);
+    manager.set_download_only(settings.download_only);
+    manager.set_stall_timeout(settings.stall_timeout_secs);
+    manager.start_node().await?;
+
+    let result = execute_with_manager(&manager, command).await;
+
+    manager.stop_node().await?;
+    result
+}
+
+async fn execute_with_manager(
+    manager: &StorageManager<CodexNode>,
+    command: HeadlessCommand,
+) -> Result<(), StorageError> {
+    match command {
+        HeadlessCommand::Status => {
+            let info = manager.get_node_info().await?;
+            println!("Status: {:?}", manager.get_status().await);
+            println!("Peer ID: {}", info.peer_id.unwrap_or_default());
+            println!("Version: {}", info.version.unwrap_or_default());
+            println!("Repo: {}", info.repo_path.unwrap_or_default());
+        }
+        HeadlessCommand::Upload { file_path } => {
+            let result = upload_file_with_manager(
+                manager,
+                uuid::Uuid::new_v4().to_string(),
+                file_path.into(),
+            )
+            .await?;
+            println!("{}", result.cid);
+        }
+        HeadlessCommand::Download { cid, save_path } => {
+            let result = download_file_with_manager(
+                manager,
+                uuid::Uuid::new_v4().to_string(),
+                cid,
+                save_path.into(),
+            )
+            .await?;
+            println!(
+                "Downloaded {} bytes to {}",
+                result.size,
+                result.filepath.unwrap_or_default()
+            );
+        }
+        HeadlessCommand::Serve => {
+            println!("Serving stored content, press Ctrl+C to stop");
+            tokio::signal::ctrl_c()
+                .await
+                .map_err(|e| StorageError::Io(e.to_string()))?;
+        }
+    }
+
+    Ok(())
+}
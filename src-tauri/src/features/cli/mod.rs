@@ -0,0 +1,3 @@
+pub mod cli;
+
+pub use cli::*;
@@ -0,0 +1,16 @@
+use crate::features::confirmation::{request_confirmation, ProtectedAction};
+use crate::features::shared::{map_storage_error, CommandError};
+use tauri::AppHandle;
+
+/// Asks the user to confirm a protected action, the returned token has to
+/// be passed to the command carrying it out.
+#[tauri::command]
+#[specta::specta]
+pub async fn confirm_action(
+    action: ProtectedAction,
+    app_handle: AppHandle,
+) -> Result<String, CommandError> {
+    request_confirmation(&app_handle, action)
+        .await
+        .map_err(map_storage_error)
+}
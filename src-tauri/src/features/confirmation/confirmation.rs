@@ -0,0 +1,128 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+use tokio::sync::Mutex;
+
+use crate::features::shared::{translate, MessageKey, StorageError};
+
+/// How long a confirmation stays valid, so an old token can't be replayed
+/// long after the user agreed.
+const CONFIRMATION_TTL: Duration = Duration::from_secs(2 * 60);
+
+/// Commands that permanently delete data or expose the node key, which
+/// only run with a token the user granted through a native dialog the
+/// webview can't click.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProtectedAction {
+    EmptyTrash,
+    RunCleanup,
+    ExportIdentity,
+    ImportIdentity,
+    RotateIdentity,
+    RevokeShare,
+    PurgeCatalogEntry,
+}
+
+impl ProtectedAction {
+    fn prompt(&self) -> MessageKey {
+        match self {
+            ProtectedAction::EmptyTrash => MessageKey::ConfirmEmptyTrash,
+            ProtectedAction::RunCleanup => MessageKey::ConfirmRunCleanup,
+            ProtectedAction::ExportIdentity => MessageKey::ConfirmExportIdentity,
+            ProtectedAction::ImportIdentity => MessageKey::ConfirmImportIdentity,
+            ProtectedAction::RotateIdentity => MessageKey::ConfirmRotateIdentity,
+            ProtectedAction::RevokeShare => MessageKey::ConfirmRevokeShare,
+            ProtectedAction::PurgeCatalogEntry => MessageKey::ConfirmPurgeCatalogEntry,
+        }
+    }
+}
+
+/// What a token lets through.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Confirmed {
+    Action(ProtectedAction),
+    /// An upload above the size limit, of this source at this size.
+    LargeUpload {
+        source: String,
+        file_size: u64,
+    },
+}
+
+struct PendingConfirmation {
+    confirmed: Confirmed,
+    granted_at: Instant,
+}
+
+/// Every outstanding confirmation token, whatever it was issued for.
+static PENDING_CONFIRMATIONS: Mutex<Option<HashMap<String, PendingConfirmation>>> =
+    Mutex::const_new(None);
+
+/// Returns a single-use token for `confirmed`.
+pub async fn issue_confirmation(confirmed: Confirmed) -> String {
+    let token = uuid::Uuid::new_v4().to_string();
+    let mut pending = PENDING_CONFIRMATIONS.lock().await;
+    let pending = pending.get_or_insert_with(HashMap::new);
+    pending.retain(|_, confirmation| confirmation.granted_at.elapsed() < CONFIRMATION_TTL);
+    pending.insert(
+        token.clone(),
+        PendingConfirmation {
+            confirmed,
+            granted_at: Instant::now(),
+        },
+    );
+    token
+}
+
+/// Spends `token`, failing unless it was issued for exactly `confirmed`
+/// and hasn't expired.
+pub async fn consume_token(confirmed: &Confirmed, token: &str) -> Result<(), StorageError> {
+    let confirmation = PENDING_CONFIRMATIONS
+        .lock()
+        .await
+        .as_mut()
+        .and_then(|pending| pending.remove(token));
+    match confirmation {
+        Some(confirmation)
+            if &confirmation.confirmed == confirmed
+                && confirmation.granted_at.elapsed() < CONFIRMATION_TTL =>
+        {
+            Ok(())
+        }
+        _ => Err(StorageError::NotConfirmed),
+    }
+}
+
+/// Asks the user to confirm `action` in a native dialog and returns a
+/// single-use token for it once they do.
+pub async fn request_confirmation(
+    app_handle: &AppHandle,
+    action: ProtectedAction,
+) -> Result<String, StorageError> {
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    app_handle
+        .dialog()
+        .message(translate(action.prompt(), &[]))
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancel)
+        .show(move |confirmed| {
+            let _ = sender.send(confirmed);
+        });
+    if !receiver.await.unwrap_or(false) {
+        return Err(StorageError::NotConfirmed);
+    }
+
+    Ok(issue_confirmation(Confirmed::Action(action)).await)
+}
+
+/// Spends `token`, failing unless the user confirmed this very action
+/// within the last two minutes.
+pub async fn consume_confirmation(
+    action: ProtectedAction,
+    token: &str,
+) -> Result<(), StorageError> {
+    consume_token(&Confirmed::Action(action), token).await
+}
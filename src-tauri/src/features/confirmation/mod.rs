@@ -0,0 +1,5 @@
+pub mod commands;
+pub mod confirmation;
+
+pub use commands::*;
+pub use confirmation::*;
@@ -1,33 +1,90 @@
-use crate::features::connection::get_storage_manager_with_handle;
-use crate::features::shared::{map_storage_error, NodeInfo, StorageConnectionStatus};
+use crate::features::backend::NodeBackendConfig;
+use crate::features::connection::{
+    create_codex_config, get_last_recovery_report, get_storage_manager_with_handle, node_data_dir,
+    RecoveryReport, DEFAULT_MAX_PEERS,
+};
+use crate::features::peers::check_ip_family;
+use crate::features::settings::get_settings_store_with_handle;
+use crate::features::shared::{
+    map_storage_error, CachedValue, CommandError, NodeInfo, OperationLogLine, OperationResult,
+    StorageConnectionStatus, StorageError,
+};
+use std::time::Duration;
 use tauri::AppHandle;
 
+// The frontend polls these, a slightly stale answer beats queueing behind
+// transfers for the node's locks on every tick
+static NODE_STATUS_CACHE: CachedValue<StorageConnectionStatus> =
+    CachedValue::new(Duration::from_millis(500));
+static NODE_INFO_CACHE: CachedValue<NodeInfo> = CachedValue::new(Duration::from_secs(2));
+
+/// Forgets the cached status answers, called by the manager whenever the
+/// node's status changes.
+pub async fn invalidate_status_caches() {
+    NODE_STATUS_CACHE.invalidate().await;
+    NODE_INFO_CACHE.invalidate().await;
+}
+
 #[tauri::command]
-pub async fn get_node_status(app_handle: AppHandle) -> Result<StorageConnectionStatus, String> {
-    let manager = get_storage_manager_with_handle(Some(app_handle))
+#[specta::specta]
+pub async fn get_node_status(
+    app_handle: AppHandle,
+) -> Result<StorageConnectionStatus, CommandError> {
+    NODE_STATUS_CACHE
+        .get_or_refresh(|| async {
+            let manager = get_storage_manager_with_handle(Some(app_handle)).await?;
+            Ok(manager.get_status().await)
+        })
         .await
-        .map_err(map_storage_error)?;
-    Ok(manager.get_status().await)
+        .map_err(map_storage_error)
 }
 
 #[tauri::command]
-pub async fn get_node_info(app_handle: AppHandle) -> Result<NodeInfo, String> {
-    let manager = get_storage_manager_with_handle(Some(app_handle))
+#[specta::specta]
+pub async fn get_node_info(app_handle: AppHandle) -> Result<NodeInfo, CommandError> {
+    NODE_INFO_CACHE
+        .get_or_refresh(|| async {
+            let manager = get_storage_manager_with_handle(Some(app_handle.clone())).await?;
+            let mut info = manager.get_node_info().await?;
+
+            let settings = get_settings_store_with_handle(&app_handle)
+                .await?
+                .get()
+                .await;
+            if settings.remote_node.is_none() {
+                info.max_peers = Some(settings.max_peers.unwrap_or(DEFAULT_MAX_PEERS));
+            }
+            Ok(info)
+        })
         .await
-        .map_err(map_storage_error)?;
-    manager.get_node_info().await.map_err(map_storage_error)
+        .map_err(map_storage_error)
 }
 
 #[tauri::command]
-pub async fn start_node(app_handle: AppHandle) -> Result<(), String> {
-    let manager = get_storage_manager_with_handle(Some(app_handle))
+#[specta::specta]
+pub async fn start_node(app_handle: AppHandle) -> Result<(), CommandError> {
+    let manager = get_storage_manager_with_handle(Some(app_handle.clone()))
         .await
         .map_err(map_storage_error)?;
-    manager.start_node().await.map_err(map_storage_error)
+    manager.start_node().await.map_err(map_storage_error)?;
+
+    // Binding happens inside the node, only now can the families be checked
+    let settings = get_settings_store_with_handle(&app_handle)
+        .await
+        .map_err(map_storage_error)?
+        .get()
+        .await;
+    let node = manager.get_node().await.map_err(map_storage_error)?;
+    if let Err(e) = check_ip_family(&node, &settings).await {
+        let _ = manager.stop_node().await;
+        return Err(map_storage_error(e));
+    }
+    Ok(())
 }
 
 #[tauri::command]
-pub async fn stop_node(app_handle: AppHandle) -> Result<(), String> {
+#[specta::specta]
+pub async fn stop_node(app_handle: AppHandle) -> Result<(), CommandError> {
     let manager = get_storage_manager_with_handle(Some(app_handle))
         .await
         .map_err(map_storage_error)?;
@@ -35,11 +92,12 @@ pub async fn stop_node(app_handle: AppHandle) -> Result<(), String> {
 }
 
 #[tauri::command]
+#[specta::specta]
 pub async fn connect_to_peer(
     peer_id: String,
     addresses: Vec<String>,
     app_handle: AppHandle,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     let manager = get_storage_manager_with_handle(Some(app_handle))
         .await
         .map_err(map_storage_error)?;
@@ -48,3 +106,108 @@ pub async fn connect_to_peer(
         .await
         .map_err(map_storage_error)
 }
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_recovery_report() -> Result<Option<RecoveryReport>, CommandError> {
+    Ok(get_last_recovery_report().await)
+}
+
+/// Looks up a recently finished transfer, `None` if it is unknown or still running.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_operation_result(
+    operation_id: String,
+    app_handle: AppHandle,
+) -> Result<Option<OperationResult>, CommandError> {
+    let manager = get_storage_manager_with_handle(Some(app_handle))
+        .await
+        .map_err(map_storage_error)?;
+    Ok(manager.get_operation_result(&operation_id).await)
+}
+
+/// Log lines of a running or recently finished transfer.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_operation_log(
+    operation_id: String,
+    app_handle: AppHandle,
+) -> Result<Option<Vec<OperationLogLine>>, CommandError> {
+    let manager = get_storage_manager_with_handle(Some(app_handle))
+        .await
+        .map_err(map_storage_error)?;
+    Ok(manager.get_operation_log(&operation_id).await)
+}
+
+/// Cancels every running transfer, returns how many were cancelled.
+#[tauri::command]
+#[specta::specta]
+pub async fn cancel_all_operations(app_handle: AppHandle) -> Result<usize, CommandError> {
+    let manager = get_storage_manager_with_handle(Some(app_handle))
+        .await
+        .map_err(map_storage_error)?;
+    Ok(manager.cancel_operations_by_user(None).await)
+}
+
+/// Cancels one running transfer, returns whether it was running.
+///
+/// A cancelled queued transfer leaves the queue for good.
+#[tauri::command]
+#[specta::specta]
+pub async fn cancel_operation(
+    operation_id: String,
+    app_handle: AppHandle,
+) -> Result<bool, CommandError> {
+    let manager = get_storage_manager_with_handle(Some(app_handle))
+        .await
+        .map_err(map_storage_error)?;
+    Ok(manager.cancel_operations_by_user(Some(&operation_id)).await > 0)
+}
+
+/// Changes the embedded node's peer limit and restarts it in place.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_max_peers(max_peers: u32, app_handle: AppHandle) -> Result<(), CommandError> {
+    let store = get_settings_store_with_handle(&app_handle)
+        .await
+        .map_err(map_storage_error)?;
+    let mut settings = store.get().await;
+    if settings.remote_node.is_some() {
+        return Err(map_storage_error(StorageError::Unsupported(
+            "the peer limit of a remote node is configured on that node".to_string(),
+        )));
+    }
+
+    settings.max_peers = Some(max_peers);
+    let settings = store.update(settings).await.map_err(map_storage_error)?;
+
+    let data_dir = node_data_dir(&app_handle).map_err(map_storage_error)?;
+    let config = create_codex_config(&data_dir, &settings).map_err(map_storage_error)?;
+    let manager = get_storage_manager_with_handle(Some(app_handle))
+        .await
+        .map_err(map_storage_error)?;
+    manager
+        .reconfigure(NodeBackendConfig::Embedded(config))
+        .await
+        .map_err(map_storage_error)
+}
+
+/// Base URL of the node's REST API, for driving it with curl or the codex CLI.
+///
+/// Only a remote node has one: codex-bindings runs the embedded node
+/// in-process without its REST server and offers no API port to configure.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_node_api_endpoint(app_handle: AppHandle) -> Result<String, CommandError> {
+    let settings = get_settings_store_with_handle(&app_handle)
+        .await
+        .map_err(map_storage_error)?
+        .get()
+        .await;
+    match settings.remote_node {
+        Some(remote_node) => Ok(remote_node.url),
+        None => Err(map_storage_error(StorageError::Unsupported(
+            "the embedded node has no REST API, codex-bindings does not start one".to_string(),
+        ))),
+    }
+}
@@ -1,20 +1,75 @@
 use codex_bindings::node::config::RepoKind;
 use codex_bindings::{CodexConfig, LogLevel};
-use tauri::{AppHandle, Manager};
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
 
-/// Creates a CodexConfig using the app handle for proper application data storage
-pub fn create_codex_config(app_handle: &AppHandle) -> CodexConfig {
-    // Use app_data_dir for proper application data storage
-    let data_dir = app_handle
-        .path()
-        .app_data_dir()
-        .expect("Failed to get app data directory")
-        .join("node_data");
+use crate::features::settings::AppSettings;
+use crate::features::shared::{app_storage_dir, StorageError};
 
+/// Directory holding the node's repo inside the app data directory
+pub fn node_data_dir(app_handle: &AppHandle) -> Result<PathBuf, StorageError> {
+    Ok(app_storage_dir(app_handle)?.join("node_data"))
+}
+
+pub const DEFAULT_MAX_PEERS: u32 = 50;
+/// UDP port the node listens on for peer discovery.
+pub const DISCOVERY_PORT: u16 = 8089;
+
+/// Creates the embedded node's CodexConfig from the app settings, with its
+/// repo in `data_dir`
+pub fn create_codex_config(
+    data_dir: &Path,
+    settings: &AppSettings,
+) -> Result<CodexConfig, StorageError> {
+    // codex-bindings has no pre-shared key transport yet. Starting on the
+    // public network would silently break the isolation the user asked for,
+    // so refuse instead.
+    if settings.private_network.is_some() {
+        return Err(StorageError::Unsupported(
+            "private networks need swarm key support in codex-bindings, \
+             disable the private network setting to start the node"
+                .to_string(),
+        ));
+    }
+
+    // Same for discovery: the node always joins the DHT, there is no switch
+    // in codex or its bindings to keep it to static peers
+    if !settings.dht_discovery {
+        return Err(StorageError::Unsupported(
+            "DHT discovery can't be turned off in codex-bindings, \
+             enable DHT discovery to start the node"
+                .to_string(),
+        ));
+    }
+
+    // Nor does it expose a circuit relay client to reserve slots with
+    if !settings.relay_peers.is_empty() {
+        return Err(StorageError::Unsupported(
+            "relays need circuit relay support in codex-bindings, \
+             remove the relay peers to start the node"
+                .to_string(),
+        ));
+    }
+
+    // The embedded node works out its announced addresses on its own
+    if settings.external_address.is_some() {
+        return Err(StorageError::Unsupported(
+            "codex-bindings can't override the announced address, \
+             remove the external address to start the node"
+                .to_string(),
+        ));
+    }
+
+    Ok(codex_config_for_data_dir(data_dir)
+        .max_peers(settings.max_peers.unwrap_or(DEFAULT_MAX_PEERS)))
+}
+
+/// Creates a CodexConfig storing the node's repo in `data_dir`
+pub fn codex_config_for_data_dir(data_dir: &Path) -> CodexConfig {
     println!("Storage data directory: {}", data_dir.display());
 
     // Ensure the directory exists using std::fs
-    if let Err(e) = std::fs::create_dir_all(&data_dir) {
+    if let Err(e) = std::fs::create_dir_all(data_dir) {
         panic!(
             "Failed to create data directory {}: {}",
             data_dir.display(),
@@ -29,9 +84,9 @@ pub fn create_codex_config(app_handle: &AppHandle) -> CodexConfig {
 
     CodexConfig::new()
         .log_level(LogLevel::Debug)
-        .data_dir(&data_dir)
+        .data_dir(data_dir)
         .storage_quota(1024 * 1024 * 1024) // 1 GB
-        .max_peers(50)
-        .discovery_port(8089)
+        .max_peers(DEFAULT_MAX_PEERS)
+        .discovery_port(DISCOVERY_PORT)
         .repo_kind(RepoKind::LevelDb)
 }
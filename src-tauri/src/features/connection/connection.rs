@@ -1,13 +1,45 @@
-use codex_bindings::{connect, debug, CodexNode};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
-use tokio::sync::{Mutex, OnceCell, RwLock};
-
-use crate::features::shared::{NodeInfo, StorageConnectionStatus, StorageError};
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, MutexGuard, OnceCell, RwLock};
+use tokio_util::sync::CancellationToken;
+
+use crate::features::backend::{NodeBackend, NodeBackendConfig, RemoteNodeConfig, StorageBackend};
+use crate::features::connection::{
+    clear_running_marker, invalidate_status_caches, mark_running, recover_data_dir,
+};
+use crate::features::events::{emit_event, RECOVERY_REPORT_EVENT};
+use crate::features::shared::{
+    LogLevel, MessageKey, NodeInfo, OperationLogLine, OperationResult, OperationStage,
+    ProgressMessage, StorageConnectionStatus, StorageError,
+};
+
+const PROGRESS_EVENT_CAPACITY: usize = 256;
+const MAX_OPERATION_RESULTS: usize = 100;
+const MAX_OPERATION_LOG_LINES: usize = 200;
+/// A stalled transfer fails once it sat idle this many times the stall timeout.
+const STALL_FAILURE_FACTOR: u32 = 3;
+const STALL_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Default)]
+struct OperationLog {
+    last_stage: String,
+    lines: Vec<OperationLogLine>,
+    last_bytes: usize,
+    /// When the byte count last moved.
+    last_progress_at: Option<Instant>,
+}
 
-pub struct StorageManager {
-    node: Arc<Mutex<Option<CodexNode>>>,
-    config: codex_bindings::CodexConfig,
+/// Owns the storage node and tracks its lifecycle.
+///
+/// Generic over the node implementation so tests can swap the app's node
+/// for a mock backend.
+pub struct StorageManager<B: StorageBackend = NodeBackend> {
+    node: Arc<Mutex<Option<B>>>,
+    config: Arc<RwLock<B::Config>>,
+    data_dir: PathBuf,
     status: Arc<RwLock<StorageConnectionStatus>>,
     progress_senders: Arc<
         Mutex<
@@ -17,15 +49,47 @@ pub struct StorageManager {
             >,
         >,
     >,
+    progress_events: tokio::sync::broadcast::Sender<crate::features::shared::ProgressMessage>,
+    operation_results: Arc<Mutex<VecDeque<OperationResult>>>,
+    /// Log lines of running and remembered operations.
+    operation_logs: Arc<Mutex<HashMap<String, OperationLog>>>,
+    running_operations: Arc<Mutex<HashMap<String, CancellationToken>>>,
+    /// Operations the user cancelled, as opposed to ones a node stop or
+    /// pause interrupted.
+    cancelled_by_user: Arc<Mutex<HashSet<String>>>,
+    dialed_peers: Arc<Mutex<HashSet<String>>>,
+    last_activity: Arc<Mutex<Instant>>,
+    stopped_for_inactivity: Arc<AtomicBool>,
+    /// Held for the whole of a start, stop or restart so they can't
+    /// interleave and leave the status out of step with the node.
+    lifecycle: Arc<Mutex<()>>,
+    read_only: Arc<AtomicBool>,
+    download_only: Arc<AtomicBool>,
+    /// Seconds without transfer progress before a transfer counts as
+    /// stalled, 0 to never.
+    stall_timeout_secs: Arc<AtomicU64>,
 }
 
-impl StorageManager {
-    pub async fn new(config: codex_bindings::CodexConfig) -> Result<Self, StorageError> {
+impl<B: StorageBackend> StorageManager<B> {
+    pub async fn new(config: B::Config, data_dir: PathBuf) -> Result<Self, StorageError> {
         let manager = Self {
             node: Arc::new(Mutex::new(None)),
-            config,
+            config: Arc::new(RwLock::new(config)),
+            data_dir,
             status: Arc::new(RwLock::new(StorageConnectionStatus::Disconnected)),
             progress_senders: Arc::new(Mutex::new(HashMap::new())),
+            progress_events: tokio::sync::broadcast::channel(PROGRESS_EVENT_CAPACITY).0,
+            operation_results: Arc::new(Mutex::new(VecDeque::new())),
+            operation_logs: Arc::new(Mutex::new(HashMap::new())),
+            running_operations: Arc::new(Mutex::new(HashMap::new())),
+            cancelled_by_user: Arc::new(Mutex::new(HashSet::new())),
+            dialed_peers: Arc::new(Mutex::new(HashSet::new())),
+            last_activity: Arc::new(Mutex::new(Instant::now())),
+            stopped_for_inactivity: Arc::new(AtomicBool::new(false)),
+            lifecycle: Arc::new(Mutex::new(())),
+            read_only: Arc::new(AtomicBool::new(false)),
+            download_only: Arc::new(AtomicBool::new(false)),
+            stall_timeout_secs: Arc::new(AtomicU64::new(0)),
         };
 
         manager.initialize_node().await?;
@@ -34,25 +98,20 @@ impl StorageManager {
     }
 
     pub async fn initialize_node(&self) -> Result<(), StorageError> {
-        {
-            let mut status = self.status.write().await;
-            *status = StorageConnectionStatus::Connecting;
-        }
+        self.set_status(StorageConnectionStatus::Connecting).await;
 
-        {
-            let node_guard = self.node.lock().await;
-            if node_guard.is_some() {
-                // Node already initialized, just update status
-                let mut status = self.status.write().await;
-                *status = StorageConnectionStatus::Initialized;
-                return Ok(());
-            }
+        if self.node.lock().await.is_some() {
+            // Node already initialized, just update status
+            self.set_status(StorageConnectionStatus::Initialized).await;
+            return Ok(());
         }
 
-        let node = match CodexNode::new(self.config.clone()) {
+        let config = self.config.read().await.clone();
+        let node = match B::create(config) {
             Ok(node) => node,
             Err(e) => {
-                return Err(StorageError::NodeCreation(e.to_string()));
+                self.set_status(StorageConnectionStatus::Error).await;
+                return Err(e);
             }
         };
 
@@ -61,19 +120,25 @@ impl StorageManager {
             *node_guard = Some(node);
         }
 
-        {
-            let mut status = self.status.write().await;
-            *status = StorageConnectionStatus::Initialized;
-        }
+        self.set_status(StorageConnectionStatus::Initialized).await;
 
         Ok(())
     }
 
+    /// Claims the node's lifecycle, failing with `Busy` while another
+    /// start or stop is running instead of interleaving with it.
+    fn begin_transition(&self) -> Result<MutexGuard<'_, ()>, StorageError> {
+        self.lifecycle.try_lock().map_err(|_| StorageError::Busy)
+    }
+
     pub async fn start_node(&self) -> Result<(), StorageError> {
-        {
-            let mut status = self.status.write().await;
-            *status = StorageConnectionStatus::Connecting;
-        }
+        let _transition = self.begin_transition()?;
+        self.start_node_locked().await
+    }
+
+    async fn start_node_locked(&self) -> Result<(), StorageError> {
+        self.stopped_for_inactivity.store(false, Ordering::SeqCst);
+        self.set_status(StorageConnectionStatus::Connecting).await;
 
         let mut node = {
             let mut node_guard = self.node.lock().await;
@@ -91,12 +156,12 @@ impl StorageManager {
             }
         };
 
-        match node.start() {
+        match node.start().await {
             Ok(_) => {}
             Err(e) => {
-                let mut node_guard = self.node.lock().await;
-                *node_guard = Some(node);
-                return Err(StorageError::NodeStart(e.to_string()));
+                *self.node.lock().await = Some(node);
+                self.set_status(StorageConnectionStatus::Error).await;
+                return Err(e);
             }
         }
 
@@ -105,20 +170,37 @@ impl StorageManager {
             *node_guard = Some(node);
         }
 
-        {
-            let mut status = self.status.write().await;
-            *status = StorageConnectionStatus::Connected;
+        // Cleared again on a clean stop, so a leftover marker means the
+        // previous run didn't shut down properly
+        if let Err(e) = mark_running(&self.data_dir) {
+            eprintln!("Failed to write node running marker: {}", e);
         }
 
+        self.set_status(StorageConnectionStatus::Connected).await;
+
         Ok(())
     }
 
     pub async fn stop_node(&self) -> Result<(), StorageError> {
-        {
-            let mut status = self.status.write().await;
-            *status = StorageConnectionStatus::Disconnected;
+        let _transition = self.begin_transition()?;
+        self.stop_node_locked().await
+    }
+
+    async fn stop_node_locked(&self) -> Result<(), StorageError> {
+        // A deliberate stop shouldn't be undone by the next transfer
+        self.stopped_for_inactivity.store(false, Ordering::SeqCst);
+
+        // Transfers can't finish against a stopped node
+        let cancelled = self.cancel_all_operations().await;
+        if cancelled > 0 {
+            println!(
+                "Cancelled {} running operation(s) before stopping",
+                cancelled
+            );
         }
 
+        self.set_status(StorageConnectionStatus::Disconnected).await;
+
         {
             let node_option = {
                 let mut node_guard = self.node.lock().await;
@@ -126,8 +208,10 @@ impl StorageManager {
             };
 
             if let Some(mut node) = node_option {
-                if let Err(e) = node.stop() {
+                if let Err(e) = node.stop().await {
                     eprintln!("Failed to stop node: {}", e);
+                } else {
+                    clear_running_marker(&self.data_dir);
                 }
                 // Put the stopped node back
                 let mut node_guard = self.node.lock().await;
@@ -135,18 +219,241 @@ impl StorageManager {
             }
         }
 
+        self.set_status(StorageConnectionStatus::Initialized).await;
+
+        Ok(())
+    }
+
+    /// Stops the node after a stretch without transfers, to be started
+    /// again by the next one through `wake_from_inactivity`.
+    pub async fn stop_for_inactivity(&self) -> Result<(), StorageError> {
+        let _transition = self.begin_transition()?;
+        self.stop_node_locked().await?;
+        self.stopped_for_inactivity.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Starts the node again if it was only stopped for inactivity.
+    pub async fn wake_from_inactivity(&self) -> Result<(), StorageError> {
+        *self.last_activity.lock().await = Instant::now();
+        // Transfers wait for a transition in progress rather than fail
+        let _transition = self.lifecycle.lock().await;
+        if self.stopped_for_inactivity.swap(false, Ordering::SeqCst) {
+            println!("Starting node stopped for inactivity");
+            self.start_node_locked().await?;
+        }
+        Ok(())
+    }
+
+    /// How long the node has gone without transfers, `None` while one is running.
+    pub async fn idle_duration(&self) -> Option<Duration> {
+        if !self.running_operations.lock().await.is_empty() {
+            return None;
+        }
+        Some(self.last_activity.lock().await.elapsed())
+    }
+
+    /// Recreates the node with a new config, starting it again if it was running.
+    pub async fn reconfigure(&self, config: B::Config) -> Result<(), StorageError> {
+        // The config is only read when the node is created
+        *self.config.write().await = config;
+        self.recreate_node(|| Ok(())).await
+    }
+
+    /// Drops the node and creates it again, running `prepare` in between
+    /// while nothing holds on to the data directory.
+    pub async fn recreate_node(
+        &self,
+        prepare: impl FnOnce() -> Result<(), StorageError>,
+    ) -> Result<(), StorageError> {
+        let _transition = self.begin_transition()?;
+        let was_running = self.get_status().await == StorageConnectionStatus::Connected;
+        if was_running {
+            self.stop_node_locked().await?;
+        }
+
         {
-            let mut status = self.status.write().await;
-            *status = StorageConnectionStatus::Initialized;
+            let mut node_guard = self.node.lock().await;
+            *node_guard = None;
+        }
+
+        prepare()?;
+
+        self.initialize_node().await?;
+        if was_running {
+            self.start_node_locked().await?;
+        }
+        Ok(())
+    }
+
+    /// In read-only mode the node keeps serving what it stores but refuses
+    /// anything that would add to its repo.
+    pub fn set_read_only(&self, read_only: bool) {
+        self.read_only.store(read_only, Ordering::SeqCst);
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.read_only.load(Ordering::SeqCst)
+    }
+
+    /// Fails with `ReadOnlyMode` before work that stores new content.
+    pub fn ensure_writable(&self) -> Result<(), StorageError> {
+        if self.is_read_only() {
+            return Err(StorageError::ReadOnlyMode);
         }
+        Ok(())
+    }
+
+    /// In download-only mode nothing is uploaded or announced, and
+    /// downloaded datasets are dropped from the repo once saved, so the node
+    /// has nothing of its own to serve to other peers.
+    pub fn set_download_only(&self, download_only: bool) {
+        self.download_only.store(download_only, Ordering::SeqCst);
+    }
+
+    pub fn is_download_only(&self) -> bool {
+        self.download_only.load(Ordering::SeqCst)
+    }
 
+    /// Fails with `DownloadOnlyMode` before work that offers content to peers.
+    pub fn ensure_hosting(&self) -> Result<(), StorageError> {
+        if self.is_download_only() {
+            return Err(StorageError::DownloadOnlyMode);
+        }
         Ok(())
     }
 
+    pub fn set_stall_timeout(&self, stall_timeout_secs: Option<u32>) {
+        self.stall_timeout_secs
+            .store(stall_timeout_secs.unwrap_or(0) as u64, Ordering::SeqCst);
+    }
+
+    /// Resolves with `Stalled` once the operation's byte count stopped
+    /// moving for `STALL_FAILURE_FACTOR` times the stall timeout, after
+    /// reporting a `Stalled` stage at the timeout itself. Never resolves
+    /// while stall detection is off.
+    pub async fn watch_for_stall(&self, operation_id: &str) -> StorageError {
+        let mut reported = false;
+        loop {
+            tokio::time::sleep(STALL_CHECK_INTERVAL).await;
+            let timeout = Duration::from_secs(self.stall_timeout_secs.load(Ordering::SeqCst));
+            if timeout.is_zero() {
+                continue;
+            }
+
+            // Only moving bytes count, hashing and other local steps take
+            // as long as they take
+            let idle = {
+                let logs = self.operation_logs.lock().await;
+                logs.get(operation_id)
+                    .filter(|log| {
+                        matches!(
+                            log.last_stage.as_str(),
+                            "Uploading" | "Downloading" | "Stalled"
+                        )
+                    })
+                    .and_then(|log| log.last_progress_at)
+                    .map(|last_progress_at| last_progress_at.elapsed())
+                    .unwrap_or_default()
+            };
+            if idle >= timeout * STALL_FAILURE_FACTOR {
+                return StorageError::Stalled(idle.as_secs());
+            }
+            if idle >= timeout && !reported {
+                let stalled = ProgressMessage::new(operation_id.to_string())
+                    .with_stage(OperationStage::Stalled)
+                    .with_message(
+                        MessageKey::TransferStalled,
+                        vec![idle.as_secs().to_string()],
+                    );
+                self.send_progress(operation_id, stalled).await;
+            }
+            // Moving again after a stall can stall again later
+            reported = idle >= timeout;
+        }
+    }
+
+    pub fn data_dir(&self) -> &Path {
+        &self.data_dir
+    }
+
     pub async fn get_status(&self) -> StorageConnectionStatus {
         self.status.read().await.clone()
     }
 
+    /// Every status change goes through here, so the cached answers the
+    /// frontend polls never outlive the status they were read from.
+    async fn set_status(&self, status: StorageConnectionStatus) {
+        *self.status.write().await = status;
+        invalidate_status_caches().await;
+    }
+
+    /// Flags a running node as degraded until `restore_connectivity` has
+    /// checked it again.
+    pub async fn mark_degraded(&self) {
+        let degraded = {
+            let mut status = self.status.write().await;
+            let degraded = *status == StorageConnectionStatus::Connected;
+            if degraded {
+                *status = StorageConnectionStatus::Degraded;
+            }
+            degraded
+        };
+        if degraded {
+            invalidate_status_caches().await;
+        }
+    }
+
+    /// Checks that a degraded node still answers and restarts it if it doesn't.
+    pub async fn restore_connectivity(&self) -> Result<(), StorageError> {
+        if self.get_status().await != StorageConnectionStatus::Degraded {
+            return Ok(());
+        }
+
+        let node = self.get_node().await?;
+        if node.is_started() && node.space().await.is_ok() {
+            self.set_status(StorageConnectionStatus::Connected).await;
+            return Ok(());
+        }
+
+        println!("Node did not respond after a system change, restarting it");
+        let _transition = self.begin_transition()?;
+        self.stop_node_locked().await?;
+        self.start_node_locked().await
+    }
+
+    /// Asks a connected node for its peer ID, which fails or hangs once the
+    /// native node has stopped working while the status still says Connected.
+    pub async fn check_health(&self, timeout: Duration) -> Result<(), StorageError> {
+        let node = self.get_node().await?;
+        match tokio::time::timeout(timeout, node.peer_id()).await {
+            Ok(result) => result.map(|_| ()),
+            Err(_) => Err(StorageError::NodeUnresponsive(format!(
+                "no answer within {} seconds",
+                timeout.as_secs()
+            ))),
+        }
+    }
+
+    /// Flags a connected node that failed its health check.
+    pub async fn mark_unhealthy(&self) -> bool {
+        let unhealthy = {
+            let mut status = self.status.write().await;
+            let unhealthy = matches!(
+                *status,
+                StorageConnectionStatus::Connected | StorageConnectionStatus::Degraded
+            );
+            if unhealthy {
+                *status = StorageConnectionStatus::Error;
+            }
+            unhealthy
+        };
+        if unhealthy {
+            invalidate_status_caches().await;
+        }
+        unhealthy
+    }
+
     pub async fn connect_to_peer(
         &self,
         peer_id: String,
@@ -165,13 +472,17 @@ impl StorageManager {
             return Err(StorageError::NodeNotStarted);
         }
 
-        connect(&node, &peer_id, &addresses)
-            .await
-            .map_err(|e| StorageError::Configuration(e.to_string()))?;
+        node.connect(&peer_id, &addresses).await?;
+        self.dialed_peers.lock().await.insert(peer_id);
 
         Ok(())
     }
 
+    /// Whether the peer was connected to through `connect_to_peer`.
+    pub async fn was_dialed(&self, peer_id: &str) -> bool {
+        self.dialed_peers.lock().await.contains(peer_id)
+    }
+
     pub async fn get_node_info(&self) -> Result<NodeInfo, StorageError> {
         let node = {
             let node_guard = self.node.lock().await;
@@ -181,30 +492,33 @@ impl StorageManager {
                 .clone()
         };
 
-        let peer_id = node.peer_id().ok();
-        let version = node.version().ok();
-        let repo_path = node.repo().ok();
+        let peer_id = node.peer_id().await.ok();
+        let version = node.version().await.ok();
+        let repo_path = node.repo().await.ok();
         let mut debug_info = Option::None;
 
         if node.is_started() {
-            match debug(&node).await {
-                Ok(info) => debug_info = Some(info),
-                Err(e) => {
-                    return Err(StorageError::Configuration(e.to_string()));
-                }
-            }
+            debug_info = Some(node.debug().await?);
         }
+        let connectivity = debug_info
+            .as_ref()
+            .map(crate::features::peers::node_connectivity);
 
         Ok(NodeInfo {
             peer_id,
             version,
             repo_path,
             debug_info,
+            max_peers: None,
+            connectivity,
+            hosting: !self.is_download_only(),
+            provider_lookup: node.supports_provider_lookup(),
+            announce: node.supports_announce(),
         })
     }
 
     // Helper methods for upload/download features
-    pub async fn get_node(&self) -> Result<CodexNode, StorageError> {
+    pub async fn get_node(&self) -> Result<B, StorageError> {
         let node_guard = self.node.lock().await;
         node_guard
             .as_ref()
@@ -217,10 +531,41 @@ impl StorageManager {
         operation_id: &str,
         progress: crate::features::shared::ProgressMessage,
     ) {
+        // Stage changes make the operation's log, byte updates would drown it
+        let stage = stage_label(&progress.stage);
+        let stage_changed = {
+            let mut logs = self.operation_logs.lock().await;
+            let log = logs.entry(operation_id.to_string()).or_default();
+            let changed = log.last_stage != stage;
+            log.last_stage = stage.clone();
+            // Stall reports carry no byte count of their own, and estimates
+            // would hide a node that stopped moving data
+            let moved =
+                log.last_progress_at.is_none() || log.last_bytes != progress.bytes_processed;
+            if moved && !progress.estimated && !matches!(progress.stage, OperationStage::Stalled) {
+                log.last_bytes = progress.bytes_processed;
+                log.last_progress_at = Some(Instant::now());
+            }
+            changed
+        };
+        if stage_changed {
+            let level = match progress.stage {
+                OperationStage::Failed(_) => LogLevel::Error,
+                _ => LogLevel::Info,
+            };
+            let message = match &progress.message {
+                Some(message) => format!("{}: {}", stage, message),
+                None => stage,
+            };
+            self.push_log_line(operation_id, level, message).await;
+        }
+
         let senders = self.progress_senders.lock().await;
         if let Some(sender) = senders.get(operation_id) {
-            let _ = sender.send(progress);
+            let _ = sender.send(progress.clone());
         }
+        // Nobody listening is fine, progress is best effort
+        let _ = self.progress_events.send(progress);
     }
 
     pub async fn register_progress_sender(
@@ -235,23 +580,162 @@ impl StorageManager {
         rx
     }
 
+    /// Receives progress messages for every operation.
+    pub fn subscribe_progress(
+        &self,
+    ) -> tokio::sync::broadcast::Receiver<crate::features::shared::ProgressMessage> {
+        self.progress_events.subscribe()
+    }
+
     pub async fn unregister_progress_sender(&self, operation_id: &str) {
         let mut senders = self.progress_senders.lock().await;
         senders.remove(operation_id);
     }
+
+    /// Tracks a running transfer, the token fires when it gets cancelled.
+    pub async fn register_operation(&self, operation_id: String) -> CancellationToken {
+        let token = CancellationToken::new();
+        *self.last_activity.lock().await = Instant::now();
+        self.cancelled_by_user.lock().await.remove(&operation_id);
+        self.running_operations
+            .lock()
+            .await
+            .insert(operation_id, token.clone());
+        token
+    }
+
+    pub async fn finish_operation(&self, operation_id: &str) {
+        self.running_operations.lock().await.remove(operation_id);
+        *self.last_activity.lock().await = Instant::now();
+    }
+
+    pub async fn running_operation_count(&self) -> usize {
+        self.running_operations.lock().await.len()
+    }
+
+    /// Cancels every running transfer and returns how many there were.
+    pub async fn cancel_all_operations(&self) -> usize {
+        let operations = std::mem::take(&mut *self.running_operations.lock().await);
+        for token in operations.values() {
+            token.cancel();
+        }
+        operations.len()
+    }
+
+    /// Cancels running transfers on the user's behalf, all of them or just
+    /// `operation_id`, and returns how many were cancelled.
+    ///
+    /// Unlike a node stop, this tells the transfer queue to drop the jobs
+    /// instead of running them again.
+    pub async fn cancel_operations_by_user(&self, operation_id: Option<&str>) -> usize {
+        let operations: Vec<(String, CancellationToken)> = {
+            let mut running = self.running_operations.lock().await;
+            match operation_id {
+                Some(operation_id) => running.remove_entry(operation_id).into_iter().collect(),
+                None => running.drain().collect(),
+            }
+        };
+
+        let mut cancelled_by_user = self.cancelled_by_user.lock().await;
+        for (operation_id, token) in &operations {
+            cancelled_by_user.insert(operation_id.clone());
+            token.cancel();
+        }
+        operations.len()
+    }
+
+    /// Whether the user cancelled `operation_id`, forgetting it either way.
+    pub async fn take_user_cancel(&self, operation_id: &str) -> bool {
+        self.cancelled_by_user.lock().await.remove(operation_id)
+    }
+
+    async fn push_log_line(&self, operation_id: &str, level: LogLevel, message: String) {
+        let mut logs = self.operation_logs.lock().await;
+        let log = logs.entry(operation_id.to_string()).or_default();
+        if log.lines.len() < MAX_OPERATION_LOG_LINES {
+            log.lines.push(OperationLogLine {
+                timestamp: chrono::Utc::now().timestamp_millis(),
+                level,
+                message,
+            });
+        }
+    }
+
+    /// Logs a line for an operation, kept with its result once it finishes.
+    pub async fn log_operation(&self, operation_id: &str, level: LogLevel, message: String) {
+        match level {
+            LogLevel::Info => println!("[{}] {}", operation_id, message),
+            LogLevel::Error => eprintln!("[{}] {}", operation_id, message),
+        }
+        self.push_log_line(operation_id, level, message).await;
+    }
+
+    /// The log of a running or recently finished operation.
+    pub async fn get_operation_log(&self, operation_id: &str) -> Option<Vec<OperationLogLine>> {
+        self.operation_logs
+            .lock()
+            .await
+            .get(operation_id)
+            .map(|log| log.lines.clone())
+    }
+
+    /// Remembers a finished operation, dropping the oldest beyond `MAX_OPERATION_RESULTS`.
+    pub async fn record_operation_result(&self, mut result: OperationResult) {
+        // The live log stays around for lines logged after the result
+        let mut logs = self.operation_logs.lock().await;
+        if let Some(log) = logs.get(&result.operation_id) {
+            result.log = log.lines.clone();
+        }
+        let mut results = self.operation_results.lock().await;
+        results.retain(|existing| existing.operation_id != result.operation_id);
+        results.push_back(result);
+        while results.len() > MAX_OPERATION_RESULTS {
+            if let Some(dropped) = results.pop_front() {
+                logs.remove(&dropped.operation_id);
+            }
+        }
+    }
+
+    pub async fn get_operation_result(&self, operation_id: &str) -> Option<OperationResult> {
+        self.operation_results
+            .lock()
+            .await
+            .iter()
+            .find(|result| result.operation_id == operation_id)
+            .cloned()
+    }
 }
 
-impl Clone for StorageManager {
+impl<B: StorageBackend> Clone for StorageManager<B> {
     fn clone(&self) -> Self {
         Self {
             node: Arc::clone(&self.node),
-            config: self.config.clone(),
+            config: Arc::clone(&self.config),
+            data_dir: self.data_dir.clone(),
             status: Arc::clone(&self.status),
             progress_senders: Arc::clone(&self.progress_senders),
+            progress_events: self.progress_events.clone(),
+            operation_results: Arc::clone(&self.operation_results),
+            operation_logs: Arc::clone(&self.operation_logs),
+            running_operations: Arc::clone(&self.running_operations),
+            cancelled_by_user: Arc::clone(&self.cancelled_by_user),
+            dialed_peers: Arc::clone(&self.dialed_peers),
+            last_activity: Arc::clone(&self.last_activity),
+            stopped_for_inactivity: Arc::clone(&self.stopped_for_inactivity),
+            lifecycle: Arc::clone(&self.lifecycle),
+            read_only: Arc::clone(&self.read_only),
+            download_only: Arc::clone(&self.download_only),
+            stall_timeout_secs: Arc::clone(&self.stall_timeout_secs),
         }
     }
 }
 
+/// Stage name without its details, e.g. `Failed` for `Failed("reason")`.
+fn stage_label(stage: &OperationStage) -> String {
+    let label = format!("{:?}", stage);
+    label.split('(').next().unwrap_or_default().to_string()
+}
+
 // Global manager instance
 pub static STORAGE_MANAGER: OnceCell<Arc<StorageManager>> = OnceCell::const_new();
 
@@ -261,14 +745,62 @@ pub async fn get_storage_manager_with_handle(
     if let Some(manager) = STORAGE_MANAGER.get() {
         Ok(Arc::clone(manager))
     } else {
-        let config = if let Some(handle) = app_handle {
-            crate::features::connection::create_codex_config(&handle)
+        let (config, data_dir, read_only, download_only, stall_timeout_secs) = if let Some(handle) =
+            app_handle
+        {
+            let data_dir = crate::features::connection::node_data_dir(&handle)?;
+            let settings = crate::features::settings::get_settings_store_with_handle(&handle)
+                .await?
+                .get()
+                .await;
+
+            let config = match settings.remote_node {
+                Some(remote_node) => {
+                    println!("Using remote node at {}", remote_node.url);
+                    NodeBackendConfig::Remote(RemoteNodeConfig {
+                        url: remote_node.url,
+                        download_parallelism: settings.download_parallelism as usize,
+                        memory_limit_bytes: settings
+                            .transfer_memory_limit_mb
+                            .map(|limit| limit as usize * 1024 * 1024),
+                        proxy: settings.proxy.clone(),
+                    })
+                }
+                None => {
+                    // Clean up after an unclean shutdown before the native node
+                    // trips over leftover locks
+                    let report = recover_data_dir(&data_dir)?;
+                    if report.needs_attention() {
+                        println!("Recovered node data directory: {:?}", report);
+                        let _ = emit_event(&handle, RECOVERY_REPORT_EVENT, report.clone());
+                    }
+                    crate::features::connection::set_last_recovery_report(report).await;
+
+                    if settings.proxy.is_some() {
+                        println!("The embedded node connects to peers directly, the proxy only applies to the app's HTTP requests");
+                    }
+                    NodeBackendConfig::Embedded(crate::features::connection::create_codex_config(
+                        &data_dir, &settings,
+                    )?)
+                }
+            };
+
+            (
+                config,
+                data_dir,
+                settings.read_only,
+                settings.download_only,
+                settings.stall_timeout_secs,
+            )
         } else {
             return Err(StorageError::Configuration(
                 "App handle is required to create storage manager".to_string(),
             ));
         };
-        let manager = Arc::new(StorageManager::new(config).await?);
+        let manager = Arc::new(StorageManager::new(config, data_dir).await?);
+        manager.set_read_only(read_only);
+        manager.set_download_only(download_only);
+        manager.set_stall_timeout(stall_timeout_secs);
         STORAGE_MANAGER.set(manager.clone()).map_err(|_| {
             StorageError::Configuration("Failed to initialize Storage manager".to_string())
         })?;
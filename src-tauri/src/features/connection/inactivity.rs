@@ -0,0 +1,52 @@
+use std::time::Duration;
+use tauri::AppHandle;
+
+use crate::features::connection::STORAGE_MANAGER;
+use crate::features::settings::get_settings_store_with_handle;
+use crate::features::shared::StorageConnectionStatus;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Stops the embedded node after `auto_disconnect_minutes` without
+/// transfers, so laptops don't keep a P2P node running on battery.
+///
+/// The next upload or download starts it again. A remote node isn't ours
+/// to stop, so the policy only applies to the embedded one.
+pub async fn run_inactivity_policy(app_handle: AppHandle) {
+    loop {
+        tokio::time::sleep(CHECK_INTERVAL).await;
+
+        let settings = match get_settings_store_with_handle(&app_handle).await {
+            Ok(store) => store.get().await,
+            Err(_) => continue,
+        };
+        let Some(minutes) = settings.auto_disconnect_minutes else {
+            continue;
+        };
+        if settings.remote_node.is_some() {
+            continue;
+        }
+        let Some(manager) = STORAGE_MANAGER.get() else {
+            continue;
+        };
+
+        let running = matches!(
+            manager.get_status().await,
+            StorageConnectionStatus::Connected | StorageConnectionStatus::Degraded
+        );
+        let timeout = Duration::from_secs(minutes as u64 * 60);
+        let idle = manager
+            .idle_duration()
+            .await
+            .is_some_and(|idle| idle >= timeout);
+        if running && idle {
+            println!(
+                "Stopping node after {} minute(s) without transfers",
+                minutes
+            );
+            if let Err(e) = manager.stop_for_inactivity().await {
+                eprintln!("Failed to stop idle node: {}", e);
+            }
+        }
+    }
+}
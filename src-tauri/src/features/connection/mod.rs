@@ -1,7 +1,16 @@
 pub mod commands;
 pub mod config;
 pub mod connection;
+pub mod inactivity;
+pub mod recovery;
+pub mod watchdog;
+
+#[cfg(test)]
+mod tests;
 
 pub use commands::*;
 pub use config::*;
 pub use connection::*;
+pub use inactivity::*;
+pub use recovery::*;
+pub use watchdog::*;
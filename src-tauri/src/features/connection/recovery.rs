@@ -0,0 +1,133 @@
+use fs2::FileExt;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use tokio::sync::Mutex;
+
+use crate::features::shared::StorageError;
+
+const RUNNING_MARKER_FILE_NAME: &str = ".node_running";
+const LEVELDB_LOCK_FILE_NAME: &str = "LOCK";
+const DATA_DIR_LOCK_FILE_NAME: &str = ".storeman.lock";
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Default)]
+pub struct RecoveryReport {
+    pub unclean_shutdown: bool,
+    pub removed_locks: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+impl RecoveryReport {
+    pub fn needs_attention(&self) -> bool {
+        self.unclean_shutdown || !self.removed_locks.is_empty() || !self.errors.is_empty()
+    }
+}
+
+static LAST_RECOVERY_REPORT: Mutex<Option<RecoveryReport>> = Mutex::const_new(None);
+
+pub async fn set_last_recovery_report(report: RecoveryReport) {
+    *LAST_RECOVERY_REPORT.lock().await = Some(report);
+}
+
+pub async fn get_last_recovery_report() -> Option<RecoveryReport> {
+    LAST_RECOVERY_REPORT.lock().await.clone()
+}
+
+/// Data directories this process holds the lock of, kept open until exit
+/// since closing the file releases the lock.
+static DATA_DIR_LOCKS: std::sync::Mutex<Vec<(PathBuf, File)>> = std::sync::Mutex::new(Vec::new());
+
+/// Takes an exclusive lock on `data_dir` for the rest of the process, so no
+/// other instance of the app or the headless CLI runs a node on it at the
+/// same time. Taking it again from this process is a no-op.
+///
+/// The OS releases the lock when the process dies, so a crash never leaves
+/// it behind.
+pub fn lock_data_dir(data_dir: &Path) -> Result<(), StorageError> {
+    let mut locks = DATA_DIR_LOCKS.lock().unwrap();
+    if locks.iter().any(|(locked_dir, _)| locked_dir == data_dir) {
+        return Ok(());
+    }
+
+    let io_error = |e: std::io::Error| StorageError::Io(e.to_string());
+    std::fs::create_dir_all(data_dir).map_err(io_error)?;
+    let lock_file = File::create(data_dir.join(DATA_DIR_LOCK_FILE_NAME)).map_err(io_error)?;
+    lock_file.try_lock_exclusive().map_err(|_| {
+        StorageError::RepoLocked(format!(
+            "{} is in use by another process",
+            data_dir.display()
+        ))
+    })?;
+    locks.push((data_dir.to_path_buf(), lock_file));
+    Ok(())
+}
+
+fn running_marker(data_dir: &Path) -> PathBuf {
+    data_dir.join(RUNNING_MARKER_FILE_NAME)
+}
+
+/// Records that the node is running out of `data_dir`.
+pub fn mark_running(data_dir: &Path) -> std::io::Result<()> {
+    std::fs::write(running_marker(data_dir), std::process::id().to_string())
+}
+
+pub fn clear_running_marker(data_dir: &Path) {
+    let marker = running_marker(data_dir);
+    if marker.exists() {
+        if let Err(e) = std::fs::remove_file(&marker) {
+            eprintln!("Failed to remove {}: {}", marker.display(), e);
+        }
+    }
+}
+
+fn find_lock_files(dir: &Path, locks: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            find_lock_files(&path, locks);
+        } else if entry.file_name() == LEVELDB_LOCK_FILE_NAME {
+            locks.push(path);
+        }
+    }
+}
+
+/// Locks the node data directory and cleans it up after an unclean
+/// shutdown.
+///
+/// Must run before the node is created: the LevelDB lock files are only
+/// stale while no node holds them. Holding the data directory lock makes
+/// sure no other process runs one, and fails otherwise.
+pub fn recover_data_dir(data_dir: &Path) -> Result<RecoveryReport, StorageError> {
+    lock_data_dir(data_dir)?;
+
+    let mut report = RecoveryReport {
+        unclean_shutdown: running_marker(data_dir).exists(),
+        ..Default::default()
+    };
+
+    if !report.unclean_shutdown {
+        return Ok(report);
+    }
+
+    let mut locks = Vec::new();
+    find_lock_files(data_dir, &mut locks);
+
+    for lock in locks {
+        match std::fs::remove_file(&lock) {
+            Ok(()) => report
+                .removed_locks
+                .push(lock.to_string_lossy().to_string()),
+            Err(e) => report
+                .errors
+                .push(format!("Failed to remove {}: {}", lock.display(), e)),
+        }
+    }
+
+    clear_running_marker(data_dir);
+    Ok(report)
+}
@@ -0,0 +1,448 @@
+use std::path::{Path, PathBuf};
+
+use crate::features::backend::mock::{MockBackend, MockConfig};
+use crate::features::backend::StorageBackend;
+use crate::features::connection::StorageManager;
+use crate::features::download::{
+    download_file_with_manager, download_file_with_verification, verify_against_node,
+    DownloadVerification,
+};
+use crate::features::shared::{
+    OperationOutcome, OperationStage, StorageConnectionStatus, StorageError,
+};
+use crate::features::storage::ensure_quota_for_dataset;
+use crate::features::upload::upload_file_with_manager;
+
+fn temp_dir() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("storeman-test-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn temp_file(dir: &Path, size: usize) -> PathBuf {
+    let path = dir.join("upload.bin");
+    std::fs::write(&path, vec![7u8; size]).unwrap();
+    path
+}
+
+async fn manager(config: MockConfig) -> StorageManager<MockBackend> {
+    StorageManager::new(config, temp_dir()).await.unwrap()
+}
+
+async fn started_manager(config: MockConfig) -> StorageManager<MockBackend> {
+    let manager = manager(config).await;
+    manager.start_node().await.unwrap();
+    manager
+}
+
+#[tokio::test]
+async fn new_manager_is_initialized() {
+    let manager = manager(MockConfig::default()).await;
+    assert_eq!(
+        manager.get_status().await,
+        StorageConnectionStatus::Initialized
+    );
+}
+
+#[tokio::test]
+async fn node_creation_failure_is_reported() {
+    let config = MockConfig {
+        fail_create: true,
+        ..Default::default()
+    };
+    let result = StorageManager::<MockBackend>::new(config, temp_dir()).await;
+    assert!(matches!(result, Err(StorageError::NodeCreation(_))));
+}
+
+#[tokio::test]
+async fn start_and_stop_transition_status() {
+    let manager = manager(MockConfig::default()).await;
+
+    manager.start_node().await.unwrap();
+    assert_eq!(
+        manager.get_status().await,
+        StorageConnectionStatus::Connected
+    );
+    assert!(manager.get_node().await.unwrap().is_started());
+
+    manager.stop_node().await.unwrap();
+    assert_eq!(
+        manager.get_status().await,
+        StorageConnectionStatus::Initialized
+    );
+    assert!(!manager.get_node().await.unwrap().is_started());
+}
+
+#[tokio::test]
+async fn start_failure_sets_error_status() {
+    let manager = manager(MockConfig {
+        fail_start: true,
+        ..Default::default()
+    })
+    .await;
+
+    let result = manager.start_node().await;
+    assert!(matches!(result, Err(StorageError::NodeStart(_))));
+    assert_eq!(manager.get_status().await, StorageConnectionStatus::Error);
+}
+
+#[tokio::test]
+async fn connect_requires_started_node() {
+    let manager = manager(MockConfig::default()).await;
+    let result = manager.connect_to_peer("peer".to_string(), vec![]).await;
+    assert!(matches!(result, Err(StorageError::NodeNotStarted)));
+
+    manager.start_node().await.unwrap();
+    manager
+        .connect_to_peer("peer".to_string(), vec![])
+        .await
+        .unwrap();
+    assert_eq!(
+        manager.get_node().await.unwrap().connected_peers(),
+        vec!["peer".to_string()]
+    );
+}
+
+#[tokio::test]
+async fn upload_reports_progress_and_completes() {
+    let manager = started_manager(MockConfig::default()).await;
+    let mut progress = manager.subscribe_progress();
+    let dir = temp_dir();
+
+    let result = upload_file_with_manager(&manager, "op".to_string(), temp_file(&dir, 1000))
+        .await
+        .unwrap();
+    assert_eq!(result.size, 1000);
+
+    let mut stages = Vec::new();
+    let mut verified = None;
+    while let Ok(message) = progress.try_recv() {
+        assert_eq!(message.operation_id, "op");
+        verified = message.verified.or(verified);
+        stages.push(message.stage);
+    }
+    assert_eq!(verified, Some(result.verified));
+    let uploading = stages
+        .iter()
+        .filter(|stage| matches!(stage, OperationStage::Uploading))
+        .count();
+    // The size announcement plus one message per mock progress step
+    assert_eq!(uploading, 1 + MockConfig::default().progress_steps);
+    assert!(matches!(stages.last(), Some(OperationStage::Completed)));
+}
+
+#[tokio::test]
+async fn upload_requires_started_node() {
+    let manager = manager(MockConfig::default()).await;
+    let dir = temp_dir();
+    let result = upload_file_with_manager(&manager, "op".to_string(), temp_file(&dir, 10)).await;
+    assert!(matches!(result, Err(StorageError::NodeNotStarted)));
+}
+
+#[tokio::test]
+async fn upload_failure_is_reported() {
+    let manager = started_manager(MockConfig {
+        fail_transfers: true,
+        ..Default::default()
+    })
+    .await;
+    let dir = temp_dir();
+    let result = upload_file_with_manager(&manager, "op".to_string(), temp_file(&dir, 10)).await;
+    assert!(matches!(result, Err(StorageError::Upload(_))));
+}
+
+#[tokio::test]
+async fn upload_over_quota_is_refused() {
+    let manager = started_manager(MockConfig {
+        quota_bytes: 100,
+        ..Default::default()
+    })
+    .await;
+    let dir = temp_dir();
+    let result = upload_file_with_manager(&manager, "op".to_string(), temp_file(&dir, 500)).await;
+    assert!(matches!(result, Err(StorageError::QuotaExceeded { .. })));
+}
+
+#[tokio::test]
+async fn fetch_over_quota_is_refused() {
+    let manager = started_manager(MockConfig {
+        quota_bytes: 100,
+        ..Default::default()
+    })
+    .await;
+    let dir = temp_dir();
+    let upload = upload_file_with_manager(&manager, "op".to_string(), temp_file(&dir, 64))
+        .await
+        .unwrap();
+
+    // A second copy of the dataset doesn't fit in what is left
+    let node = manager.get_node().await.unwrap();
+    let result = ensure_quota_for_dataset(&node, &upload.cid).await;
+    assert!(matches!(result, Err(StorageError::QuotaExceeded { .. })));
+}
+
+#[tokio::test]
+async fn uploaded_content_can_be_downloaded() {
+    let manager = started_manager(MockConfig::default()).await;
+    let dir = temp_dir();
+
+    let upload = upload_file_with_manager(&manager, "up".to_string(), temp_file(&dir, 2048))
+        .await
+        .unwrap();
+    let upload_verified = upload.verified;
+
+    let save_path = dir.join("download.bin");
+    let download =
+        download_file_with_manager(&manager, "down".to_string(), upload.cid, save_path.clone())
+            .await
+            .unwrap();
+
+    assert!(upload_verified);
+    assert_eq!(download.size, 2048);
+    assert!(download.verified);
+    assert_eq!(std::fs::metadata(save_path).unwrap().len(), 2048);
+}
+
+#[tokio::test]
+async fn hash_verification_catches_modified_files() {
+    let manager = started_manager(MockConfig::default()).await;
+    let dir = temp_dir();
+    let upload = upload_file_with_manager(&manager, "up".to_string(), temp_file(&dir, 2048))
+        .await
+        .unwrap();
+
+    let save_path = dir.join("download.bin");
+    let download = download_file_with_verification(
+        &manager,
+        "down".to_string(),
+        upload.cid.clone(),
+        save_path.clone(),
+        DownloadVerification::FullHash,
+    )
+    .await
+    .unwrap();
+    assert!(download.verified);
+
+    std::fs::write(&save_path, vec![1u8; 2048]).unwrap();
+    let node = manager.get_node().await.unwrap();
+    assert!(!verify_against_node(&node, &upload.cid, &save_path)
+        .await
+        .unwrap());
+}
+
+#[tokio::test]
+async fn download_of_unknown_cid_fails() {
+    let manager = started_manager(MockConfig::default()).await;
+    let dir = temp_dir();
+    let result = download_file_with_manager(
+        &manager,
+        "down".to_string(),
+        "missing".to_string(),
+        dir.join("download.bin"),
+    )
+    .await;
+    assert!(matches!(result, Err(StorageError::Download(_))));
+}
+
+#[tokio::test]
+async fn slow_transfers_still_complete() {
+    let manager = started_manager(MockConfig {
+        step_delay: std::time::Duration::from_millis(5),
+        ..Default::default()
+    })
+    .await;
+    let dir = temp_dir();
+    let result = upload_file_with_manager(&manager, "op".to_string(), temp_file(&dir, 64))
+        .await
+        .unwrap();
+    assert_eq!(result.size, 64);
+}
+
+#[tokio::test]
+async fn degraded_node_is_restored() {
+    let manager = started_manager(MockConfig::default()).await;
+
+    manager.mark_degraded().await;
+    assert_eq!(
+        manager.get_status().await,
+        StorageConnectionStatus::Degraded
+    );
+
+    manager.restore_connectivity().await.unwrap();
+    assert_eq!(
+        manager.get_status().await,
+        StorageConnectionStatus::Connected
+    );
+}
+
+#[tokio::test]
+async fn stopped_node_is_not_marked_degraded() {
+    let manager = manager(MockConfig::default()).await;
+    manager.mark_degraded().await;
+    assert_eq!(
+        manager.get_status().await,
+        StorageConnectionStatus::Initialized
+    );
+}
+
+#[tokio::test]
+async fn finished_operations_can_be_looked_up() {
+    let manager = started_manager(MockConfig::default()).await;
+    let dir = temp_dir();
+
+    let upload = upload_file_with_manager(&manager, "up".to_string(), temp_file(&dir, 16))
+        .await
+        .unwrap();
+    let _ = download_file_with_manager(
+        &manager,
+        "down".to_string(),
+        "missing".to_string(),
+        dir.join("download.bin"),
+    )
+    .await;
+
+    let result = manager.get_operation_result("up").await.unwrap();
+    assert!(
+        matches!(result.outcome, OperationOutcome::Upload(ref response) if response.cid == upload.cid)
+    );
+    let result = manager.get_operation_result("down").await.unwrap();
+    assert!(matches!(result.outcome, OperationOutcome::Failed(_)));
+    assert!(manager.get_operation_result("unknown").await.is_none());
+}
+
+#[tokio::test]
+async fn cancel_all_stops_running_transfers() {
+    let manager = started_manager(MockConfig {
+        step_delay: std::time::Duration::from_millis(50),
+        ..Default::default()
+    })
+    .await;
+    let dir = temp_dir();
+
+    let upload_manager = manager.clone();
+    let upload = tokio::spawn(async move {
+        upload_file_with_manager(&upload_manager, "op".to_string(), temp_file(&dir, 64)).await
+    });
+    tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+
+    assert_eq!(manager.cancel_all_operations().await, 1);
+    let result = upload.await.unwrap();
+    assert!(matches!(result, Err(StorageError::Cancelled)));
+}
+
+#[tokio::test]
+async fn only_user_cancels_are_remembered() {
+    let manager = started_manager(MockConfig::default()).await;
+
+    manager.register_operation("stopped".to_string()).await;
+    manager.cancel_all_operations().await;
+    assert!(!manager.take_user_cancel("stopped").await);
+
+    manager.register_operation("kept".to_string()).await;
+    manager.register_operation("dropped".to_string()).await;
+    assert_eq!(manager.cancel_operations_by_user(Some("dropped")).await, 1);
+    assert!(manager.take_user_cancel("dropped").await);
+    assert!(!manager.take_user_cancel("dropped").await);
+    assert_eq!(manager.running_operation_count().await, 1);
+}
+
+#[tokio::test]
+async fn reconfigure_restarts_a_running_node() {
+    let manager = started_manager(MockConfig::default()).await;
+
+    manager
+        .reconfigure(MockConfig {
+            progress_steps: 1,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(
+        manager.get_status().await,
+        StorageConnectionStatus::Connected
+    );
+    assert!(manager.get_node().await.unwrap().is_started());
+}
+
+#[tokio::test]
+async fn rotating_identity_removes_key_and_restarts() {
+    let manager = started_manager(MockConfig::default()).await;
+    let key_file = crate::features::identity::identity_key_file(manager.data_dir());
+    std::fs::write(&key_file, b"old-key").unwrap();
+
+    crate::features::identity::reset_identity(&manager)
+        .await
+        .unwrap();
+
+    assert!(!key_file.exists());
+    assert_eq!(
+        manager.get_status().await,
+        StorageConnectionStatus::Connected
+    );
+}
+
+#[test]
+fn node_failures_are_classified() {
+    use crate::features::shared::classify_node_failure;
+
+    let classify =
+        |message: &str| classify_node_failure(message.to_string(), StorageError::NodeStart);
+    assert!(matches!(
+        classify("bind: Address already in use"),
+        StorageError::PortInUse(_)
+    ));
+    assert!(matches!(
+        classify("IO error: lock data/LOCK: Resource temporarily unavailable"),
+        StorageError::RepoLocked(_)
+    ));
+    assert!(matches!(
+        classify("something else"),
+        StorageError::NodeStart(_)
+    ));
+}
+
+#[tokio::test]
+async fn failed_health_check_flags_node_as_error() {
+    let manager = started_manager(MockConfig::default()).await;
+    manager
+        .check_health(std::time::Duration::from_secs(1))
+        .await
+        .unwrap();
+
+    assert!(manager.mark_unhealthy().await);
+    assert_eq!(manager.get_status().await, StorageConnectionStatus::Error);
+    // Only a running node can turn unhealthy
+    assert!(!manager.mark_unhealthy().await);
+}
+
+#[tokio::test]
+async fn read_only_mode_only_serves_stored_datasets() {
+    let manager = started_manager(MockConfig::default()).await;
+    let dir = temp_dir();
+    let upload = upload_file_with_manager(&manager, "up".to_string(), temp_file(&dir, 64))
+        .await
+        .unwrap();
+
+    manager.set_read_only(true);
+    let result = upload_file_with_manager(&manager, "up2".to_string(), temp_file(&dir, 64)).await;
+    assert!(matches!(result, Err(StorageError::ReadOnlyMode)));
+    let result = download_file_with_manager(
+        &manager,
+        "missing".to_string(),
+        "missing".to_string(),
+        dir.join("missing.bin"),
+    )
+    .await;
+    assert!(matches!(result, Err(StorageError::ReadOnlyMode)));
+
+    // Stored content is still served
+    download_file_with_manager(
+        &manager,
+        "down".to_string(),
+        upload.cid,
+        dir.join("down.bin"),
+    )
+    .await
+    .unwrap();
+}
@@ -0,0 +1,90 @@
+use std::time::Duration;
+use tauri::AppHandle;
+
+use crate::features::connection::{get_storage_manager_with_handle, StorageManager};
+use crate::features::events::{emit_event, NodeHealth, NODE_HEALTH_EVENT};
+use crate::features::shared::StorageConnectionStatus;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+const CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+/// Failed checks in a row before the node counts as unhealthy, so a single
+/// slow answer under load doesn't restart it.
+const FAILURES_BEFORE_RESTART: u32 = 2;
+const MIN_RESTART_DELAY: Duration = Duration::from_secs(5);
+const MAX_RESTART_DELAY: Duration = Duration::from_secs(5 * 60);
+
+/// Restarts the node with backoff until it is connected again, or until
+/// something else, like the user stopping it, changes the status.
+async fn reconnect(app_handle: &AppHandle, manager: &StorageManager) {
+    let mut delay = MIN_RESTART_DELAY;
+    while manager.get_status().await == StorageConnectionStatus::Error {
+        println!("Restarting unresponsive node");
+        if let Err(e) = manager.stop_node().await {
+            eprintln!("Failed to stop unresponsive node: {}", e);
+        }
+        match manager.start_node().await {
+            Ok(()) => {
+                let _ = emit_event(
+                    app_handle,
+                    NODE_HEALTH_EVENT,
+                    NodeHealth {
+                        healthy: true,
+                        error: None,
+                    },
+                );
+                return;
+            }
+            Err(e) => eprintln!("Failed to restart node: {}", e),
+        }
+        tokio::time::sleep(delay).await;
+        delay = (delay * 2).min(MAX_RESTART_DELAY);
+    }
+}
+
+/// Checks that a node reported as connected still answers, and restarts it
+/// when it doesn't, so the status can't silently diverge from its health.
+pub async fn run_connection_watchdog(app_handle: AppHandle) {
+    let manager = match get_storage_manager_with_handle(Some(app_handle.clone())).await {
+        Ok(manager) => manager,
+        Err(e) => {
+            eprintln!("Connection watchdog unavailable: {}", e);
+            return;
+        }
+    };
+
+    let mut failures = 0;
+    loop {
+        tokio::time::sleep(CHECK_INTERVAL).await;
+        // Degraded nodes are checked by the system watcher's recovery
+        if manager.get_status().await != StorageConnectionStatus::Connected {
+            failures = 0;
+            continue;
+        }
+
+        let error = match manager.check_health(CHECK_TIMEOUT).await {
+            Ok(()) => {
+                failures = 0;
+                continue;
+            }
+            Err(e) => e,
+        };
+        failures += 1;
+        eprintln!("Node health check failed ({}): {}", failures, error);
+        if failures < FAILURES_BEFORE_RESTART {
+            continue;
+        }
+
+        failures = 0;
+        if manager.mark_unhealthy().await {
+            let _ = emit_event(
+                &app_handle,
+                NODE_HEALTH_EVENT,
+                NodeHealth {
+                    healthy: false,
+                    error: Some(error.to_string()),
+                },
+            );
+            reconnect(&app_handle, &manager).await;
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use crate::features::contacts::{
+    decrypt_file_with_own_key, get_contact_book_with_handle, own_public_key,
+    share_file_with_contact, Contact,
+};
+use crate::features::shared::{map_storage_error, validate_user_path, CommandError, StorageError};
+use crate::features::shares::ShareFile;
+use tauri::AppHandle;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_contacts(app_handle: AppHandle) -> Result<Vec<Contact>, CommandError> {
+    let book = get_contact_book_with_handle(&app_handle)
+        .await
+        .map_err(map_storage_error)?;
+    Ok(book.list().await)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn add_contact(contact: Contact, app_handle: AppHandle) -> Result<Contact, CommandError> {
+    let book = get_contact_book_with_handle(&app_handle)
+        .await
+        .map_err(map_storage_error)?;
+    book.save(contact, false).await.map_err(map_storage_error)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn update_contact(
+    contact: Contact,
+    app_handle: AppHandle,
+) -> Result<Contact, CommandError> {
+    let book = get_contact_book_with_handle(&app_handle)
+        .await
+        .map_err(map_storage_error)?;
+    book.save(contact, true).await.map_err(map_storage_error)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn remove_contact(name: String, app_handle: AppHandle) -> Result<Contact, CommandError> {
+    let book = get_contact_book_with_handle(&app_handle)
+        .await
+        .map_err(map_storage_error)?;
+    book.remove(&name).await.map_err(map_storage_error)
+}
+
+/// Our public key, for contacts to encrypt what they share with us.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_own_public_key(app_handle: AppHandle) -> Result<String, CommandError> {
+    own_public_key(&app_handle).map_err(map_storage_error)
+}
+
+/// Uploads a file only the named contact can decrypt and writes the
+/// `.dexshare` file to send them.
+#[tauri::command]
+#[specta::specta]
+pub async fn share_with_contact(
+    name: String,
+    file_path: String,
+    share_path: String,
+    app_handle: AppHandle,
+) -> Result<ShareFile, CommandError> {
+    let file_path = validate_user_path(&app_handle, &file_path).map_err(map_storage_error)?;
+    let share_path = validate_user_path(&app_handle, &share_path).map_err(map_storage_error)?;
+    share_file_with_contact(&app_handle, &name, file_path, share_path)
+        .await
+        .map_err(map_storage_error)
+}
+
+/// Decrypts a downloaded file a contact encrypted for us.
+#[tauri::command]
+#[specta::specta]
+pub async fn decrypt_shared_file(
+    path: String,
+    output_path: String,
+    app_handle: AppHandle,
+) -> Result<(), CommandError> {
+    let path = validate_user_path(&app_handle, &path).map_err(map_storage_error)?;
+    let output_path = validate_user_path(&app_handle, &output_path).map_err(map_storage_error)?;
+    tokio::task::spawn_blocking(move || decrypt_file_with_own_key(&app_handle, &path, &output_path))
+        .await
+        .map_err(|e| StorageError::Io(e.to_string()))
+        .and_then(|result| result)
+        .map_err(map_storage_error)
+}
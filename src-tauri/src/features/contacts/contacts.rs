@@ -0,0 +1,173 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tauri::AppHandle;
+use tokio::sync::{Mutex, OnceCell};
+
+use crate::features::contacts::{encrypt_file_for, parse_recipient, ENCRYPTION_ALGORITHM};
+use crate::features::shared::{
+    app_data_file, read_json_file, staging_file, write_json_file, StagedArtifacts, StorageError,
+};
+use crate::features::shares::{write_share_file, EncryptionHint, ShareFile, ShareProvider};
+use crate::features::upload::upload_file_with_progress;
+
+pub const CONTACTS_FILE_NAME: &str = "contacts.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct Contact {
+    pub name: String,
+    pub peer_id: String,
+    /// Encryption key content shared with the contact is encrypted for,
+    /// an `age1...` X25519 recipient.
+    pub public_key: String,
+    #[serde(default)]
+    pub addresses: Vec<String>,
+    /// Peers the contact prefers to fetch through, listed in share files
+    /// made for them.
+    #[serde(default)]
+    pub preferred_providers: Vec<ShareProvider>,
+}
+
+impl Contact {
+    pub fn validate(&self) -> Result<(), StorageError> {
+        if self.name.trim().is_empty() {
+            return Err(StorageError::Configuration(
+                "Contact name cannot be empty".to_string(),
+            ));
+        }
+        if self.peer_id.trim().is_empty() {
+            return Err(StorageError::Configuration(
+                "Contact peer ID cannot be empty".to_string(),
+            ));
+        }
+        parse_recipient(&self.public_key).map(|_| ())
+    }
+}
+
+/// Contacts keyed by name, mirrored to disk.
+pub struct ContactBook {
+    contacts: Mutex<BTreeMap<String, Contact>>,
+    contacts_file: PathBuf,
+}
+
+impl ContactBook {
+    pub fn load(contacts_file: PathBuf) -> Self {
+        let contacts = read_json_file(&contacts_file).unwrap_or_default();
+        Self {
+            contacts: Mutex::new(contacts),
+            contacts_file,
+        }
+    }
+
+    pub async fn get(&self, name: &str) -> Option<Contact> {
+        self.contacts.lock().await.get(name).cloned()
+    }
+
+    pub async fn list(&self) -> Vec<Contact> {
+        self.contacts.lock().await.values().cloned().collect()
+    }
+
+    /// Adds a contact, or replaces the one with the same name if `replace`.
+    pub async fn save(&self, contact: Contact, replace: bool) -> Result<Contact, StorageError> {
+        contact.validate()?;
+        let mut contacts = self.contacts.lock().await;
+        let exists = contacts.contains_key(&contact.name);
+        if exists && !replace {
+            return Err(StorageError::Configuration(format!(
+                "A contact named {} already exists",
+                contact.name
+            )));
+        }
+        if !exists && replace {
+            return Err(StorageError::Configuration(format!(
+                "No contact named {}",
+                contact.name
+            )));
+        }
+        contacts.insert(contact.name.clone(), contact.clone());
+        write_json_file(&self.contacts_file, &*contacts)?;
+        Ok(contact)
+    }
+
+    pub async fn remove(&self, name: &str) -> Result<Contact, StorageError> {
+        let mut contacts = self.contacts.lock().await;
+        let removed = contacts
+            .remove(name)
+            .ok_or_else(|| StorageError::Configuration(format!("No contact named {}", name)))?;
+        write_json_file(&self.contacts_file, &*contacts)?;
+        Ok(removed)
+    }
+}
+
+// Global contact book instance
+pub static CONTACT_BOOK: OnceCell<Arc<ContactBook>> = OnceCell::const_new();
+
+pub async fn get_contact_book_with_handle(
+    app_handle: &AppHandle,
+) -> Result<Arc<ContactBook>, StorageError> {
+    CONTACT_BOOK
+        .get_or_try_init(|| async {
+            let contacts_file = app_data_file(app_handle, CONTACTS_FILE_NAME)?;
+            Ok(Arc::new(ContactBook::load(contacts_file)))
+        })
+        .await
+        .map(Arc::clone)
+}
+
+/// Encrypts a file for a contact, uploads it and writes a share file listing
+/// the contact's preferred providers next to this node.
+pub async fn share_file_with_contact(
+    app_handle: &AppHandle,
+    name: &str,
+    file_path: PathBuf,
+    share_path: PathBuf,
+) -> Result<ShareFile, StorageError> {
+    let book = get_contact_book_with_handle(app_handle).await?;
+    let contact = book
+        .get(name)
+        .await
+        .ok_or_else(|| StorageError::Configuration(format!("No contact named {}", name)))?;
+
+    let file_name = file_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or_else(|| StorageError::Io(format!("{} is not a file", file_path.display())))?;
+    let staging_dir = staging_file(app_handle).await?;
+    std::fs::create_dir_all(&staging_dir).map_err(|e| StorageError::Io(e.to_string()))?;
+    let mut artifacts = StagedArtifacts::default();
+    artifacts.track(staging_dir.clone());
+
+    let encrypted_path = staging_dir.join(format!("{}.age", file_name));
+    let public_key = contact.public_key.clone();
+    let encrypted_path_clone = encrypted_path.clone();
+    tokio::task::spawn_blocking(move || {
+        encrypt_file_for(&public_key, &file_path, &encrypted_path_clone)
+    })
+    .await
+    .map_err(|e| StorageError::Io(e.to_string()))??;
+
+    let upload = upload_file_with_progress(
+        uuid::Uuid::new_v4().to_string(),
+        encrypted_path,
+        None,
+        None,
+        app_handle.clone(),
+    )
+    .await?;
+    artifacts.clean_up();
+
+    let encryption = EncryptionHint {
+        algorithm: ENCRYPTION_ALGORITHM.to_string(),
+        recipient_key: Some(contact.public_key),
+    };
+    write_share_file(
+        app_handle,
+        &upload.cid,
+        &share_path,
+        Some(encryption),
+        contact.preferred_providers,
+    )
+    .await
+}
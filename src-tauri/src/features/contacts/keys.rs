@@ -0,0 +1,122 @@
+use age::secrecy::ExposeSecret;
+use std::path::Path;
+use std::str::FromStr;
+use tauri::AppHandle;
+
+use crate::features::shared::StorageError;
+
+/// Algorithm named in the encryption hint of share files.
+pub const ENCRYPTION_ALGORITHM: &str = "age-x25519";
+
+#[cfg(not(mobile))]
+const KEYRING_SERVICE: &str = "dev.nipsys.storeman";
+#[cfg(not(mobile))]
+const KEYRING_USER: &str = "encryption-identity";
+#[cfg(mobile)]
+const IDENTITY_FILE_NAME: &str = "encryption_identity";
+
+fn key_error(reason: impl std::fmt::Display) -> StorageError {
+    StorageError::Configuration(format!("Encryption key: {}", reason))
+}
+
+#[cfg(not(mobile))]
+fn load_secret(_app_handle: &AppHandle) -> Result<Option<String>, StorageError> {
+    let entry = keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER).map_err(key_error)?;
+    match entry.get_password() {
+        Ok(secret) => Ok(Some(secret)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(key_error(e)),
+    }
+}
+
+#[cfg(not(mobile))]
+fn store_secret(_app_handle: &AppHandle, secret: &str) -> Result<(), StorageError> {
+    keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)
+        .and_then(|entry| entry.set_password(secret))
+        .map_err(key_error)
+}
+
+// Mobile has no keyring to reach, the app's private data directory is the
+// closest equivalent
+#[cfg(mobile)]
+fn load_secret(app_handle: &AppHandle) -> Result<Option<String>, StorageError> {
+    let path = crate::features::shared::app_data_file(app_handle, IDENTITY_FILE_NAME)?;
+    match std::fs::read_to_string(path) {
+        Ok(secret) => Ok(Some(secret.trim().to_string())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(StorageError::Io(e.to_string())),
+    }
+}
+
+#[cfg(mobile)]
+fn store_secret(app_handle: &AppHandle, secret: &str) -> Result<(), StorageError> {
+    let path = crate::features::shared::app_data_file(app_handle, IDENTITY_FILE_NAME)?;
+    std::fs::write(path, secret).map_err(|e| StorageError::Io(e.to_string()))
+}
+
+/// Our own encryption keypair, created and stored in the OS keyring on
+/// first use.
+pub fn own_identity(app_handle: &AppHandle) -> Result<age::x25519::Identity, StorageError> {
+    if let Some(secret) = load_secret(app_handle)? {
+        return age::x25519::Identity::from_str(&secret).map_err(key_error);
+    }
+
+    let identity = age::x25519::Identity::generate();
+    store_secret(app_handle, identity.to_string().expose_secret())?;
+    Ok(identity)
+}
+
+/// The key contacts encrypt for when sharing with us.
+pub fn own_public_key(app_handle: &AppHandle) -> Result<String, StorageError> {
+    Ok(own_identity(app_handle)?.to_public().to_string())
+}
+
+pub fn parse_recipient(public_key: &str) -> Result<age::x25519::Recipient, StorageError> {
+    age::x25519::Recipient::from_str(public_key.trim()).map_err(key_error)
+}
+
+/// Encrypts `source` into `destination` so only the holder of the key
+/// behind `public_key` can read it.
+pub fn encrypt_file_for(
+    public_key: &str,
+    source: &Path,
+    destination: &Path,
+) -> Result<(), StorageError> {
+    let recipient = parse_recipient(public_key)?;
+    let encryptor = age::Encryptor::with_recipients(vec![Box::new(recipient)])
+        .ok_or_else(|| key_error("no recipient"))?;
+
+    let io_error = |e: std::io::Error| StorageError::Io(e.to_string());
+    let mut input = std::fs::File::open(source).map_err(io_error)?;
+    let output = std::fs::File::create(destination).map_err(io_error)?;
+    let mut writer = encryptor.wrap_output(output).map_err(io_error)?;
+    std::io::copy(&mut input, &mut writer).map_err(io_error)?;
+    writer.finish().map_err(io_error)?;
+    Ok(())
+}
+
+/// Decrypts a file shared with us into `destination`.
+pub fn decrypt_file_with_own_key(
+    app_handle: &AppHandle,
+    source: &Path,
+    destination: &Path,
+) -> Result<(), StorageError> {
+    let identity = own_identity(app_handle)?;
+
+    let io_error = |e: std::io::Error| StorageError::Io(e.to_string());
+    let input = std::fs::File::open(source).map_err(io_error)?;
+    let decryptor = match age::Decryptor::new(std::io::BufReader::new(input)).map_err(key_error)? {
+        age::Decryptor::Recipients(decryptor) => decryptor,
+        age::Decryptor::Passphrase(_) => {
+            return Err(StorageError::Unsupported(
+                "passphrase encrypted files".to_string(),
+            ))
+        }
+    };
+    let mut reader = decryptor
+        .decrypt(std::iter::once(&identity as &dyn age::Identity))
+        .map_err(key_error)?;
+    let mut output = std::fs::File::create(destination).map_err(io_error)?;
+    std::io::copy(&mut reader, &mut output).map_err(io_error)?;
+    Ok(())
+}
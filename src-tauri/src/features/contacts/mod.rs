@@ -0,0 +1,7 @@
+pub mod commands;
+pub mod contacts;
+pub mod keys;
+
+pub use commands::*;
+pub use contacts::*;
+pub use keys::*;
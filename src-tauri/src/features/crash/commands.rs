@@ -0,0 +1,9 @@
+use crate::features::crash::{last_crash_report, CrashReport};
+use crate::features::shared::CommandError;
+
+/// What ended the previous run, `None` if it exited cleanly.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_last_crash_report() -> Result<Option<CrashReport>, CommandError> {
+    Ok(last_crash_report())
+}
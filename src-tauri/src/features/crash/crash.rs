@@ -0,0 +1,112 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::backtrace::Backtrace;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+use crate::features::shared::{app_data_file, read_json_file, write_json_file, StorageError};
+
+const CRASH_FILE_NAME: &str = "crash_report.json";
+const RUNNING_MARKER_FILE_NAME: &str = ".app_running";
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct PanicReport {
+    /// Milliseconds since the Unix epoch.
+    pub timestamp: i64,
+    pub message: String,
+    pub location: Option<String>,
+    pub thread: Option<String>,
+    pub backtrace: String,
+    pub app_version: String,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct CrashReport {
+    /// The previous run didn't get to exit cleanly.
+    pub unclean_shutdown: bool,
+    /// The panic that ended it, if it was one.
+    pub panic: Option<PanicReport>,
+}
+
+static LAST_CRASH_REPORT: Mutex<Option<CrashReport>> = Mutex::new(None);
+static RUNNING_MARKER: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+pub fn last_crash_report() -> Option<CrashReport> {
+    LAST_CRASH_REPORT.lock().unwrap().clone()
+}
+
+fn panic_message(info: &std::panic::PanicHookInfo) -> String {
+    if let Some(message) = info.payload().downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = info.payload().downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Unknown panic".to_string()
+    }
+}
+
+/// Writes panics to the crash file before the default hook runs, so the
+/// next launch can show what happened.
+fn install_panic_hook(crash_file: PathBuf, app_version: String) {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let report = PanicReport {
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            message: panic_message(info),
+            location: info.location().map(|location| location.to_string()),
+            thread: std::thread::current().name().map(str::to_string),
+            backtrace: Backtrace::force_capture().to_string(),
+            app_version: app_version.clone(),
+        };
+        // The first panic is the interesting one, later ones are fallout
+        if !crash_file.exists() {
+            if let Err(e) = write_json_file(&crash_file, &report) {
+                eprintln!("Failed to write crash report: {}", e);
+            }
+        }
+        default_hook(info);
+    }));
+}
+
+fn take_crash_file(crash_file: &Path) -> Option<PanicReport> {
+    let report = read_json_file(crash_file);
+    if crash_file.exists() {
+        if let Err(e) = std::fs::remove_file(crash_file) {
+            eprintln!("Failed to remove {}: {}", crash_file.display(), e);
+        }
+    }
+    report
+}
+
+/// Picks up what the previous run left behind, then installs the panic
+/// hook and marks this run as running until `clear_app_running_marker`.
+pub fn start_crash_reporting(app_handle: &AppHandle) -> Result<(), StorageError> {
+    let crash_file = app_data_file(app_handle, CRASH_FILE_NAME)?;
+    let marker = app_data_file(app_handle, RUNNING_MARKER_FILE_NAME)?;
+
+    let unclean_shutdown = marker.exists();
+    let panic = take_crash_file(&crash_file);
+    if unclean_shutdown || panic.is_some() {
+        println!("The previous run closed unexpectedly");
+        *LAST_CRASH_REPORT.lock().unwrap() = Some(CrashReport {
+            unclean_shutdown,
+            panic,
+        });
+    }
+
+    install_panic_hook(crash_file, app_handle.package_info().version.to_string());
+    std::fs::write(&marker, std::process::id().to_string())
+        .map_err(|e| StorageError::Io(e.to_string()))?;
+    *RUNNING_MARKER.lock().unwrap() = Some(marker);
+    Ok(())
+}
+
+/// Records a clean exit.
+pub fn clear_app_running_marker() {
+    if let Some(marker) = RUNNING_MARKER.lock().unwrap().take() {
+        if let Err(e) = std::fs::remove_file(&marker) {
+            eprintln!("Failed to remove {}: {}", marker.display(), e);
+        }
+    }
+}
@@ -0,0 +1,5 @@
+pub mod commands;
+pub mod crash;
+
+pub use commands::*;
+pub use crash::*;
@@ -1,14 +1,59 @@
-use crate::features::download::download_file_with_progress;
-use crate::features::shared::map_storage_error;
+use crate::features::audit::record_audit;
+use crate::features::download::{
+    download_file_with_progress, download_to_default_dir, verify_sidecar_file, SidecarVerification,
+};
+use crate::features::shared::{map_storage_error, validate_user_path, CommandError, StorageError};
 use tauri::AppHandle;
+use uuid::Uuid;
 
 #[tauri::command]
+#[specta::specta]
 pub async fn download_file_from_storage(
     cid: String,
     save_path: String,
     app_handle: AppHandle,
-) -> Result<crate::features::shared::DownloadResultResponse, String> {
-    download_file_with_progress(cid, save_path.into(), app_handle)
+) -> Result<crate::features::shared::DownloadResultResponse, CommandError> {
+    let resolved_path = validate_user_path(&app_handle, &save_path).map_err(map_storage_error)?;
+    let result = download_file_with_progress(
+        Uuid::new_v4().to_string(),
+        cid.clone(),
+        resolved_path,
+        app_handle.clone(),
+    )
+    .await;
+    record_audit(
+        &app_handle,
+        "download_file_from_storage",
+        &[&cid, &save_path],
+        &result,
+    )
+    .await;
+    result.map_err(map_storage_error)
+}
+
+/// Downloads into the managed downloads directory, named from the manifest.
+#[tauri::command]
+#[specta::specta]
+pub async fn download_to_default(
+    cid: String,
+    app_handle: AppHandle,
+) -> Result<crate::features::shared::DownloadResultResponse, CommandError> {
+    let result =
+        download_to_default_dir(Uuid::new_v4().to_string(), cid.clone(), app_handle.clone()).await;
+    record_audit(&app_handle, "download_to_default", &[&cid], &result).await;
+    result.map_err(map_storage_error)
+}
+
+/// Checks a file against its `.sha256`/`.blake3` sidecar, given either path.
+#[tauri::command]
+#[specta::specta]
+pub async fn verify_sidecar(
+    path: String,
+    app_handle: AppHandle,
+) -> Result<SidecarVerification, CommandError> {
+    let path = validate_user_path(&app_handle, &path).map_err(map_storage_error)?;
+    tokio::task::spawn_blocking(move || verify_sidecar_file(&path))
         .await
+        .map_err(|e| map_storage_error(StorageError::Io(e.to_string())))?
         .map_err(map_storage_error)
 }
@@ -0,0 +1,106 @@
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+use tauri_plugin_fs::FsExt;
+
+use crate::features::backend::StorageBackend;
+use crate::features::connection::get_storage_manager_with_handle;
+use crate::features::download::download_file_with_progress;
+use crate::features::settings::{get_settings_store_with_handle, AppSettings};
+use crate::features::shared::{is_broad_directory, DownloadResultResponse, StorageError};
+
+const RESERVED_CHARACTERS: &[char] = &['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+const RESERVED_WINDOWS_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Turns a file name taken from a manifest into one that is safe to create
+/// on any platform, falling back to `fallback` if nothing usable is left.
+pub fn sanitize_file_name(name: &str, fallback: &str) -> String {
+    let sanitized: String = name
+        .chars()
+        .map(|c| {
+            if c.is_control() || RESERVED_CHARACTERS.contains(&c) {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect();
+    // Windows drops trailing dots and spaces, and ".." must never survive
+    let sanitized = sanitized.trim_matches(|c: char| c == '.' || c.is_whitespace());
+
+    if sanitized.is_empty() {
+        return fallback.to_string();
+    }
+
+    let stem = sanitized.split('.').next().unwrap_or_default();
+    if RESERVED_WINDOWS_NAMES
+        .iter()
+        .any(|reserved| reserved.eq_ignore_ascii_case(stem))
+    {
+        return format!("_{}", sanitized);
+    }
+
+    sanitized.to_string()
+}
+
+/// Picks `name` inside `dir`, adding " (n)" before the extension if taken.
+pub fn unique_path(dir: &Path, name: &str) -> PathBuf {
+    let candidate = dir.join(name);
+    if !candidate.exists() {
+        return candidate;
+    }
+
+    let (stem, extension) = match name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => (stem, format!(".{}", extension)),
+        _ => (name, String::new()),
+    };
+    (1..)
+        .map(|n| dir.join(format!("{} ({}){}", stem, n, extension)))
+        .find(|path| !path.exists())
+        .unwrap_or(candidate)
+}
+
+/// Lets the frontend read files in the managed downloads directory.
+///
+/// Never widens the scope to a filesystem root or the home directory, in
+/// case the settings file was edited by hand.
+pub fn allow_downloads_dir(app_handle: &AppHandle, settings: &AppSettings) {
+    match settings.resolve_downloads_dir() {
+        Ok(dir) if is_broad_directory(&dir) => {
+            eprintln!("Refusing to allow downloads directory {}", dir.display())
+        }
+        Ok(dir) => {
+            if let Err(e) = app_handle.fs_scope().allow_directory(&dir, true) {
+                eprintln!("Failed to allow downloads directory: {}", e);
+            }
+        }
+        Err(e) => eprintln!("Failed to resolve downloads directory: {}", e),
+    }
+}
+
+/// Downloads a dataset into the managed downloads directory, named after
+/// the file name in its manifest.
+pub async fn download_to_default_dir(
+    operation_id: String,
+    cid: String,
+    app_handle: AppHandle,
+) -> Result<DownloadResultResponse, StorageError> {
+    let settings = get_settings_store_with_handle(&app_handle)
+        .await?
+        .get()
+        .await;
+    let downloads_dir = settings.resolve_downloads_dir()?;
+    std::fs::create_dir_all(&downloads_dir).map_err(|e| StorageError::Io(e.to_string()))?;
+
+    let manager = get_storage_manager_with_handle(Some(app_handle.clone())).await?;
+    let node = manager.get_node().await?;
+    if !node.is_started() {
+        return Err(StorageError::NodeNotStarted);
+    }
+    let name = node.dataset_name(&cid).await?.unwrap_or_default();
+    let save_path = unique_path(&downloads_dir, &sanitize_file_name(&name, &cid));
+
+    download_file_with_progress(operation_id, cid, save_path, app_handle).await
+}
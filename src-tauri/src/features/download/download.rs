@@ -1,21 +1,179 @@
-use codex_bindings::{download_stream, DownloadStreamOptions};
 use std::path::PathBuf;
-use uuid::Uuid;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
 
-use crate::features::connection::get_storage_manager_with_handle;
+use crate::features::backend::StorageBackend;
+use crate::features::bandwidth::{record_bandwidth, TransferDirection};
+use crate::features::catalog::{record_in_catalog, DatasetOrigin};
+use crate::features::connection::{get_storage_manager_with_handle, StorageManager};
+use crate::features::download::{verify_against_node, write_sidecar, DownloadVerification};
+use crate::features::peers::connect_to_providers;
+use crate::features::recent::{record_recent_item, RecentAction};
+use crate::features::settings::get_settings_store_with_handle;
 use crate::features::shared::{
-    DownloadResultResponse, OperationStage, ProgressMessage, StorageError,
+    export_to_content_uri, is_content_uri, staging_file, DownloadResultResponse, LogLevel,
+    MessageKey, OperationOutcome, OperationResult, OperationStage, ProgressMessage, StorageError,
+};
+use crate::features::storage::{
+    check_quota_thresholds, dataset_exists_locally, ensure_quota_for_dataset,
 };
 
 pub async fn download_file_with_progress(
+    operation_id: String,
     cid: String,
     save_path: PathBuf,
     app_handle: tauri::AppHandle,
 ) -> Result<DownloadResultResponse, StorageError> {
-    let manager = get_storage_manager_with_handle(Some(app_handle)).await?;
+    let manager = get_storage_manager_with_handle(Some(app_handle.clone())).await?;
+    let settings = get_settings_store_with_handle(&app_handle)
+        .await?
+        .get()
+        .await;
+
+    // Android SAF destinations are content URIs the node can't write to,
+    // download into a staging file and copy it over afterwards
+    let content_uri = is_content_uri(&save_path);
+    let download_path = if content_uri {
+        staging_file(&app_handle).await?
+    } else {
+        save_path.clone()
+    };
+
+    let result = download_file_with_verification(
+        &manager,
+        operation_id,
+        cid,
+        download_path.clone(),
+        settings.download_verification,
+    )
+    .await;
+
+    let result = if content_uri {
+        let exported = result.and_then(|mut result| {
+            export_to_content_uri(&app_handle, &download_path, &save_path)?;
+            result.filepath = Some(save_path.to_string_lossy().to_string());
+            Ok(result)
+        });
+        let _ = std::fs::remove_file(&download_path);
+        exported?
+    } else {
+        let result = result?;
+        // Content URIs have no "next to" to write a sidecar into
+        if let Some(algorithm) = settings.download_checksum_sidecar {
+            if let Err(e) = write_sidecar(&download_path, algorithm) {
+                eprintln!("Failed to write checksum sidecar: {}", e);
+            }
+        }
+        result
+    };
+
+    // The catalog lists what the repo holds, download-only mode keeps nothing
+    if !manager.is_download_only() {
+        let file_name = save_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string());
+        record_in_catalog(
+            &app_handle,
+            &result.cid,
+            file_name,
+            result.size as u64,
+            DatasetOrigin::Downloaded,
+            result.filepath.clone(),
+        )
+        .await;
+    }
+    record_recent_item(
+        &app_handle,
+        &result.cid,
+        RecentAction::Downloaded,
+        result.filepath.clone(),
+    )
+    .await;
+    record_bandwidth(&app_handle, TransferDirection::Download, result.size as u64).await;
+
+    // Downloaded blocks are stored in the repo too
+    let node = manager.get_node().await?;
+    if let Err(e) = check_quota_thresholds(&app_handle, &node).await {
+        eprintln!("Failed to check storage quota: {}", e);
+    }
+
+    Ok(result)
+}
+
+/// Downloads with only the size check for verification.
+pub async fn download_file_with_manager<B: StorageBackend>(
+    manager: &StorageManager<B>,
+    operation_id: String,
+    cid: String,
+    save_path: PathBuf,
+) -> Result<DownloadResultResponse, StorageError> {
+    download_file_with_verification(
+        manager,
+        operation_id,
+        cid,
+        save_path,
+        DownloadVerification::Size,
+    )
+    .await
+}
+
+/// Downloads and checks the file as thoroughly as `verification` asks for.
+pub async fn download_file_with_verification<B: StorageBackend>(
+    manager: &StorageManager<B>,
+    operation_id: String,
+    cid: String,
+    save_path: PathBuf,
+    verification: DownloadVerification,
+) -> Result<DownloadResultResponse, StorageError> {
+    manager.wake_from_inactivity().await?;
+    let started_at = chrono::Utc::now().timestamp_millis();
+    let cancel = manager.register_operation(operation_id.clone()).await;
+    let result = tokio::select! {
+        result = run_download(manager, operation_id.clone(), cid, save_path, verification) => result,
+        _ = cancel.cancelled() => Err(StorageError::Cancelled),
+        stalled = manager.watch_for_stall(&operation_id) => Err(stalled),
+    };
+    manager.finish_operation(&operation_id).await;
+
+    // Let progress listeners know the transfer is over
+    if let Err(e) = &result {
+        let reason = match e {
+            StorageError::Cancelled => "Cancelled".to_string(),
+            e => e.to_string(),
+        };
+        let failed_progress =
+            ProgressMessage::new(operation_id.clone()).with_stage(OperationStage::Failed(reason));
+        manager.send_progress(&operation_id, failed_progress).await;
+        manager.unregister_progress_sender(&operation_id).await;
+    }
+
+    let outcome = match &result {
+        Ok(response) => OperationOutcome::Download(response.clone()),
+        Err(e) => OperationOutcome::Failed(e.to_string()),
+    };
+    manager
+        .record_operation_result(OperationResult {
+            operation_id,
+            outcome,
+            started_at,
+            finished_at: chrono::Utc::now().timestamp_millis(),
+            log: Vec::new(),
+        })
+        .await;
+
+    result
+}
 
-    let operation_id = Uuid::new_v4().to_string();
+async fn run_download<B: StorageBackend>(
+    manager: &StorageManager<B>,
+    operation_id: String,
+    cid: String,
+    save_path: PathBuf,
+    verification: DownloadVerification,
+) -> Result<DownloadResultResponse, StorageError> {
     let cid_clone = cid.clone();
+    let started_at = Instant::now();
 
     // Register progress sender
     let _rx = manager.register_progress_sender(operation_id.clone()).await;
@@ -37,41 +195,125 @@ pub async fn download_file_with_progress(
         return Err(StorageError::InvalidCid("CID cannot be empty".to_string()));
     }
 
+    // Fetching from the network would store the dataset's blocks
+    let stored_before = dataset_exists_locally(&node, &cid).await?;
+    if manager.is_read_only() && !stored_before {
+        return Err(StorageError::ReadOnlyMode);
+    }
+
+    // Give the node every provider we know of to fetch blocks from
+    let providers = connect_to_providers(manager, &cid).await;
+    if !providers.peer_ids.is_empty() {
+        let message_key = if providers.on_lan {
+            MessageKey::ConnectedToLanProviders
+        } else {
+            MessageKey::ConnectedToProviders
+        };
+        let providers_progress = ProgressMessage::new(operation_id.clone())
+            .with_stage(OperationStage::Downloading)
+            .with_message(message_key, vec![providers.peer_ids.len().to_string()]);
+        manager
+            .send_progress(&operation_id, providers_progress)
+            .await;
+    }
+
+    // Refuse up front instead of filling the repo halfway
+    if !stored_before {
+        ensure_quota_for_dataset(&node, &cid).await?;
+    }
+
     // Send download start info
     let start_progress = ProgressMessage::new(operation_id.clone())
         .with_stage(OperationStage::Downloading)
-        .with_message(format!("Starting download of CID: {}", cid));
+        .with_message(MessageKey::StartingDownload, vec![cid.clone()]);
     manager.send_progress(&operation_id, start_progress).await;
 
-    // Create download options with progress callback
+    // Forward backend progress to the operation's progress sender
     let operation_id_clone = operation_id.clone();
     let manager_clone = manager.clone();
-    let download_options = DownloadStreamOptions::new(&cid)
-        .filepath(&save_path)
-        .on_progress(move |progress| {
-            let manager = manager_clone.clone();
-            let operation_id_for_callback = operation_id_clone.clone();
-            tokio::spawn(async move {
-                let progress_msg = ProgressMessage::new(operation_id_for_callback.clone())
-                    .with_stage(OperationStage::Downloading)
-                    .with_bytes(progress.bytes_downloaded, progress.total_bytes)
-                    .with_message(format!("Downloaded {} bytes", progress.bytes_downloaded));
-                manager
-                    .send_progress(&operation_id_for_callback, progress_msg)
-                    .await;
-            });
+    // Size announced by the node, 0 while unknown
+    let expected_size = Arc::new(AtomicUsize::new(0));
+    let expected_size_clone = Arc::clone(&expected_size);
+    let on_progress = Box::new(move |bytes_downloaded: usize, total_bytes: Option<usize>| {
+        if let Some(total) = total_bytes {
+            expected_size_clone.store(total, Ordering::SeqCst);
+        }
+        let manager = manager_clone.clone();
+        let operation_id_for_callback = operation_id_clone.clone();
+        tokio::spawn(async move {
+            let progress_msg = ProgressMessage::new(operation_id_for_callback.clone())
+                .with_stage(OperationStage::Downloading)
+                .with_bytes(bytes_downloaded, total_bytes)
+                .with_message(
+                    MessageKey::DownloadedBytes,
+                    vec![bytes_downloaded.to_string()],
+                );
+            manager
+                .send_progress(&operation_id_for_callback, progress_msg)
+                .await;
         });
+    });
 
     // Perform the download
-    let result = download_stream(&node, &cid, download_options)
-        .await
-        .map_err(|e| StorageError::Download(e.to_string()))?;
+    let size = node.download(&cid, &save_path, on_progress).await?;
+
+    // Check that everything the node reported actually landed on disk
+    let mut verified = false;
+    if verification != DownloadVerification::None {
+        let verifying_progress = ProgressMessage::new(operation_id.clone())
+            .with_stage(OperationStage::Verifying)
+            .with_bytes(size, Some(size))
+            .with_message(MessageKey::VerifyingDownload, vec![]);
+        manager
+            .send_progress(&operation_id, verifying_progress)
+            .await;
+        let expected_size = expected_size.load(Ordering::SeqCst);
+        verified = std::fs::metadata(&save_path)
+            .map(|metadata| metadata.len() as usize == size)
+            .unwrap_or(false)
+            && (expected_size == 0 || expected_size == size);
+    }
+    if verified && verification == DownloadVerification::FullHash {
+        let hashing_progress = ProgressMessage::new(operation_id.clone())
+            .with_stage(OperationStage::Verifying)
+            .with_bytes(size, Some(size))
+            .with_message(MessageKey::ComparingHash, vec![]);
+        manager.send_progress(&operation_id, hashing_progress).await;
+        verified = match verify_against_node(&node, &cid, &save_path).await {
+            Ok(matches) => matches,
+            Err(e) => {
+                manager
+                    .log_operation(
+                        &operation_id,
+                        LogLevel::Error,
+                        format!("Failed to verify download hash: {}", e),
+                    )
+                    .await;
+                false
+            }
+        };
+    }
+    let duration_ms = started_at.elapsed().as_millis() as u64;
+
+    // Don't keep the blocks around for other peers to fetch
+    if manager.is_download_only() && !stored_before {
+        if let Err(e) = node.delete(&cid).await {
+            manager
+                .log_operation(
+                    &operation_id,
+                    LogLevel::Error,
+                    format!("Failed to drop {} from the repo: {}", cid, e),
+                )
+                .await;
+        }
+    }
 
     // Send completion progress
     let completion_progress = ProgressMessage::new(operation_id.clone())
         .with_stage(OperationStage::Completed)
-        .with_bytes(result.size, Some(result.size))
-        .with_message("Download completed successfully".to_string());
+        .with_bytes(size, Some(size))
+        .with_message(MessageKey::DownloadCompleted, vec![])
+        .with_result(duration_ms, verified);
     manager
         .send_progress(&operation_id, completion_progress)
         .await;
@@ -81,9 +323,9 @@ pub async fn download_file_with_progress(
 
     Ok(DownloadResultResponse {
         cid: cid_clone,
-        size: result.size,
-        duration_ms: 0, // TODO: Track actual duration
-        verified: true,
+        size,
+        duration_ms,
+        verified,
         filepath: Some(save_path.to_string_lossy().to_string()),
     })
 }
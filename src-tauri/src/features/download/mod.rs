@@ -1,5 +1,11 @@
 pub mod commands;
+pub mod default_dir;
 pub mod download;
+pub mod sidecar;
+pub mod verify;
 
 pub use commands::*;
+pub use default_dir::*;
 pub use download::*;
+pub use sidecar::*;
+pub use verify::*;
@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use crate::features::download::sha256_file;
+use crate::features::shared::StorageError;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq)]
+pub enum ChecksumAlgorithm {
+    Sha256,
+    Blake3,
+}
+
+impl ChecksumAlgorithm {
+    const ALL: [ChecksumAlgorithm; 2] = [ChecksumAlgorithm::Sha256, ChecksumAlgorithm::Blake3];
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ChecksumAlgorithm::Sha256 => "sha256",
+            ChecksumAlgorithm::Blake3 => "blake3",
+        }
+    }
+
+    fn from_extension(extension: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|algorithm| algorithm.extension() == extension)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SidecarVerification {
+    pub file_path: String,
+    pub sidecar_path: String,
+    pub algorithm: ChecksumAlgorithm,
+    pub expected: String,
+    pub actual: String,
+    pub matches: bool,
+}
+
+fn blake3_file(path: &Path) -> Result<String, StorageError> {
+    let mut file = std::fs::File::open(path).map_err(|e| StorageError::Io(e.to_string()))?;
+    let mut hasher = blake3::Hasher::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .map_err(|e| StorageError::Io(e.to_string()))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hasher.finalize().to_hex().to_string())
+}
+
+fn hash_file(path: &Path, algorithm: ChecksumAlgorithm) -> Result<String, StorageError> {
+    match algorithm {
+        ChecksumAlgorithm::Sha256 => sha256_file(path),
+        ChecksumAlgorithm::Blake3 => blake3_file(path),
+    }
+}
+
+fn sidecar_path(file_path: &Path, algorithm: ChecksumAlgorithm) -> PathBuf {
+    let mut path = file_path.as_os_str().to_owned();
+    path.push(".");
+    path.push(algorithm.extension());
+    PathBuf::from(path)
+}
+
+/// Writes `<file>.<algorithm>` next to the file, in the `sha256sum`/`b3sum` format.
+pub fn write_sidecar(
+    file_path: &Path,
+    algorithm: ChecksumAlgorithm,
+) -> Result<PathBuf, StorageError> {
+    let hash = hash_file(file_path, algorithm)?;
+    let file_name = file_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let path = sidecar_path(file_path, algorithm);
+    std::fs::write(&path, format!("{}  {}\n", hash, file_name))
+        .map_err(|e| StorageError::Io(e.to_string()))?;
+    Ok(path)
+}
+
+/// Verifies a file against its sidecar; `path` may name either of the two.
+pub fn verify_sidecar_file(path: &Path) -> Result<SidecarVerification, StorageError> {
+    let sidecar_algorithm = path
+        .extension()
+        .and_then(|extension| ChecksumAlgorithm::from_extension(&extension.to_string_lossy()));
+    let (file_path, sidecar_path, algorithm) = match sidecar_algorithm {
+        Some(algorithm) => (path.with_extension(""), path.to_path_buf(), algorithm),
+        None => ChecksumAlgorithm::ALL
+            .into_iter()
+            .map(|algorithm| (path.to_path_buf(), sidecar_path(path, algorithm), algorithm))
+            .find(|(_, sidecar_path, _)| sidecar_path.exists())
+            .ok_or_else(|| {
+                StorageError::Io(format!("No checksum sidecar found for {}", path.display()))
+            })?,
+    };
+
+    let contents =
+        std::fs::read_to_string(&sidecar_path).map_err(|e| StorageError::Io(e.to_string()))?;
+    let expected = contents
+        .split_whitespace()
+        .next()
+        .map(|hash| hash.to_lowercase())
+        .ok_or_else(|| {
+            StorageError::Io(format!("Empty checksum sidecar {}", sidecar_path.display()))
+        })?;
+    let actual = hash_file(&file_path, algorithm)?;
+
+    Ok(SidecarVerification {
+        file_path: file_path.to_string_lossy().to_string(),
+        sidecar_path: sidecar_path.to_string_lossy().to_string(),
+        algorithm,
+        matches: expected == actual,
+        expected,
+        actual,
+    })
+}
@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use specta::Type;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use crate::features::backend::StorageBackend;
+use crate::features::shared::StorageError;
+
+/// How much checking a finished download gets.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Default)]
+pub enum DownloadVerification {
+    /// Trust the node, for slow machines.
+    None,
+    /// Compare the file size with the size the node reported.
+    #[default]
+    Size,
+    /// Size check plus re-hashing the file against the node's copy.
+    FullHash,
+}
+
+/// Hex encoded SHA-256 of a file, read in fixed size chunks.
+pub fn sha256_file(path: &Path) -> Result<String, StorageError> {
+    let mut file = std::fs::File::open(path).map_err(|e| StorageError::Io(e.to_string()))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .map_err(|e| StorageError::Io(e.to_string()))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+async fn sha256_file_blocking(path: PathBuf) -> Result<String, StorageError> {
+    tokio::task::spawn_blocking(move || sha256_file(&path))
+        .await
+        .map_err(|e| StorageError::Io(e.to_string()))?
+}
+
+/// Checks a downloaded file against the dataset held by the node.
+///
+/// The node checks every block against its CID as it arrives, so
+/// streaming the dataset again from the now local blocks and comparing
+/// hashes confirms the file on disk matches the CID.
+pub async fn verify_against_node<B: StorageBackend>(
+    node: &B,
+    cid: &str,
+    file_path: &Path,
+) -> Result<bool, StorageError> {
+    let file_name = file_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let reference_path = file_path.with_file_name(format!(".{}.verify", file_name));
+
+    let result = node
+        .download(cid, &reference_path, Box::new(|_, _| {}))
+        .await;
+    let matches = match result {
+        Ok(_) => {
+            let expected = sha256_file_blocking(reference_path.clone()).await;
+            let actual = sha256_file_blocking(file_path.to_path_buf()).await;
+            match (expected, actual) {
+                (Ok(expected), Ok(actual)) => Ok(expected == actual),
+                (Err(e), _) | (_, Err(e)) => Err(e),
+            }
+        }
+        Err(e) => Err(e),
+    };
+
+    let _ = std::fs::remove_file(&reference_path);
+    matches
+}
@@ -0,0 +1,132 @@
+use serde::Serialize;
+use specta::Type;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::features::connection::get_storage_manager_with_handle;
+use crate::features::storage::StorageUsage;
+
+/// Version of the event payload shapes, bumped whenever a payload loses or
+/// changes a field so the frontend can tell which shape it got.
+pub const EVENT_SCHEMA_VERSION: u32 = 1;
+
+pub const PROGRESS_EVENT: &str = "storage://progress";
+pub const TRANSFER_SUMMARY_EVENT: &str = "storage://transfer-summary";
+pub const NODE_HEALTH_EVENT: &str = "storage://node-health";
+pub const RECOVERY_REPORT_EVENT: &str = "storage://recovery-report";
+pub const QUOTA_WARNING_EVENT: &str = "storage://quota-warning";
+pub const BANDWIDTH_STATUS_EVENT: &str = "storage://bandwidth-status";
+pub const CATALOG_RECONCILED_EVENT: &str = "storage://catalog-reconciled";
+pub const PEER_CONNECTED_EVENT: &str = "storage://peer-connected";
+pub const PEER_DISCONNECTED_EVENT: &str = "storage://peer-disconnected";
+pub const LOCAL_PEER_DISCOVERED_EVENT: &str = "storage://local-peer-discovered";
+pub const LOCAL_PEER_LOST_EVENT: &str = "storage://local-peer-lost";
+pub const OFFER_RECEIVED_EVENT: &str = "storage://offer-received";
+pub const SYSTEM_EVENT: &str = "storage://system-event";
+pub const NETWORK_STATUS_EVENT: &str = "storage://network-status";
+pub const POWER_STATUS_EVENT: &str = "storage://power-status";
+pub const CLIPBOARD_CID_EVENT: &str = "storage://clipboard-cid";
+pub const ONBOARDING_CHECKS_EVENT: &str = "storage://onboarding-checks";
+pub const SHARE_FILE_OPENED_EVENT: &str = "storage://share-file-opened";
+
+/// What every event carries: the payload's own fields next to the schema
+/// version they follow.
+#[derive(Debug, Clone, Serialize)]
+struct VersionedEvent<T> {
+    schema_version: u32,
+    #[serde(flatten)]
+    payload: T,
+}
+
+/// Emits `payload` to the frontend tagged with the current schema version.
+///
+/// Payloads must serialize as maps, the version is added as one of their
+/// fields.
+pub fn emit_event<T: Serialize + Clone>(
+    app_handle: &AppHandle,
+    event: &str,
+    payload: T,
+) -> tauri::Result<()> {
+    app_handle.emit(
+        event,
+        VersionedEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            payload,
+        },
+    )
+}
+
+#[derive(Debug, Clone, Serialize, Type, PartialEq)]
+pub struct TransferSummary {
+    pub active_transfers: usize,
+    /// Bytes per second over the last interval.
+    pub upload_rate: u64,
+    pub download_rate: u64,
+    /// Jobs waiting in the transfer queue, the running one included.
+    pub queue_depth: usize,
+    pub queue_paused: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct NodeHealth {
+    pub healthy: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct QuotaWarning {
+    pub threshold: f64,
+    pub usage: StorageUsage,
+}
+
+#[derive(Debug, Clone, Serialize, Type, PartialEq)]
+pub enum PeerDirection {
+    /// We dialed the peer through `connect_to_peer`.
+    Outbound,
+    /// The peer showed up on its own, through discovery or by dialing us.
+    Inbound,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct PeerEvent {
+    pub peer_id: String,
+    pub addresses: Vec<String>,
+    pub direction: PeerDirection,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(tag = "kind")]
+pub enum SystemEvent {
+    Suspended,
+    Resumed,
+    NetworkLost,
+    NetworkChanged,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct ClipboardCid {
+    pub cid: String,
+}
+
+/// Forwards every operation's progress to the frontend.
+pub async fn run_progress_events(app_handle: AppHandle) {
+    let manager = match get_storage_manager_with_handle(Some(app_handle.clone())).await {
+        Ok(manager) => manager,
+        Err(e) => {
+            eprintln!("Progress events unavailable: {}", e);
+            return;
+        }
+    };
+
+    let mut progress_events = manager.subscribe_progress();
+    loop {
+        match progress_events.recv().await {
+            Ok(progress) => {
+                let _ = emit_event(&app_handle, PROGRESS_EVENT, progress);
+            }
+            // The next message of the operation supersedes the missed ones
+            Err(RecvError::Lagged(_)) => continue,
+            Err(RecvError::Closed) => break,
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use crate::features::catalog::{get_catalog_with_handle, ManifestSignature};
+use crate::features::confirmation::{consume_confirmation, ProtectedAction};
+use crate::features::connection::get_storage_manager_with_handle;
+use crate::features::identity::{
+    copy_identity_to, install_identity_from, reset_identity, sign_cid, verify_cid_signature,
+};
+use crate::features::settings::get_settings_store_with_handle;
+use crate::features::shared::{map_storage_error, validate_user_path, CommandError, StorageError};
+use crate::features::storage::dataset_exists_locally;
+use tauri::AppHandle;
+
+/// The key lives with whichever process runs the node, a remote node's
+/// identity can't be managed from here.
+async fn ensure_embedded_node(app_handle: &AppHandle) -> Result<(), CommandError> {
+    let settings = get_settings_store_with_handle(app_handle)
+        .await
+        .map_err(map_storage_error)?
+        .get()
+        .await;
+    if settings.remote_node.is_some() {
+        return Err(map_storage_error(StorageError::Unsupported(
+            "the identity of a remote node is managed on that node".to_string(),
+        )));
+    }
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn export_identity(
+    path: String,
+    confirmation_token: String,
+    app_handle: AppHandle,
+) -> Result<(), CommandError> {
+    ensure_embedded_node(&app_handle).await?;
+    consume_confirmation(ProtectedAction::ExportIdentity, &confirmation_token)
+        .await
+        .map_err(map_storage_error)?;
+    let path = validate_user_path(&app_handle, &path).map_err(map_storage_error)?;
+    let manager = get_storage_manager_with_handle(Some(app_handle))
+        .await
+        .map_err(map_storage_error)?;
+    copy_identity_to(&manager, &path).map_err(map_storage_error)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn import_identity(
+    path: String,
+    confirmation_token: String,
+    app_handle: AppHandle,
+) -> Result<(), CommandError> {
+    ensure_embedded_node(&app_handle).await?;
+    consume_confirmation(ProtectedAction::ImportIdentity, &confirmation_token)
+        .await
+        .map_err(map_storage_error)?;
+    let path = validate_user_path(&app_handle, &path).map_err(map_storage_error)?;
+    let manager = get_storage_manager_with_handle(Some(app_handle))
+        .await
+        .map_err(map_storage_error)?;
+    install_identity_from(&manager, &path)
+        .await
+        .map_err(map_storage_error)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn rotate_identity(
+    confirmation_token: String,
+    app_handle: AppHandle,
+) -> Result<(), CommandError> {
+    ensure_embedded_node(&app_handle).await?;
+    consume_confirmation(ProtectedAction::RotateIdentity, &confirmation_token)
+        .await
+        .map_err(map_storage_error)?;
+    let manager = get_storage_manager_with_handle(Some(app_handle))
+        .await
+        .map_err(map_storage_error)?;
+    reset_identity(&manager).await.map_err(map_storage_error)
+}
+
+/// Signs a locally stored dataset with the node key and keeps the signature
+/// in the catalog, ready to be shared alongside the CID.
+#[tauri::command]
+#[specta::specta]
+pub async fn sign_manifest(
+    cid: String,
+    app_handle: AppHandle,
+) -> Result<ManifestSignature, CommandError> {
+    ensure_embedded_node(&app_handle).await?;
+    let manager = get_storage_manager_with_handle(Some(app_handle.clone()))
+        .await
+        .map_err(map_storage_error)?;
+
+    // Only vouch for content we actually hold
+    let node = manager.get_node().await.map_err(map_storage_error)?;
+    if !dataset_exists_locally(&node, &cid)
+        .await
+        .map_err(map_storage_error)?
+    {
+        return Err(map_storage_error(StorageError::InvalidCid(format!(
+            "{} is not stored locally",
+            cid
+        ))));
+    }
+
+    let signature = sign_cid(manager.data_dir(), &cid).map_err(map_storage_error)?;
+
+    let catalog = get_catalog_with_handle(&app_handle)
+        .await
+        .map_err(map_storage_error)?;
+    catalog
+        .upsert(&cid, |entry| entry.signature = Some(signature.clone()))
+        .await
+        .map_err(map_storage_error)?;
+
+    Ok(signature)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn verify_manifest(
+    cid: String,
+    signature: String,
+    peer_id: String,
+) -> Result<bool, CommandError> {
+    verify_cid_signature(&cid, &signature, &peer_id).map_err(map_storage_error)
+}
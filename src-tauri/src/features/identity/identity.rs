@@ -0,0 +1,93 @@
+use std::path::{Path, PathBuf};
+
+use crate::features::backend::StorageBackend;
+use crate::features::connection::StorageManager;
+use crate::features::shared::StorageError;
+
+/// File in the node's data directory holding its libp2p private key.
+///
+/// The node creates it on first start and reuses it afterwards, which keeps
+/// the peer ID stable as long as the data directory is.
+pub const IDENTITY_KEY_FILE_NAME: &str = "key";
+
+pub fn identity_key_file(data_dir: &Path) -> PathBuf {
+    data_dir.join(IDENTITY_KEY_FILE_NAME)
+}
+
+fn restrict_permissions(path: &Path) -> Result<(), StorageError> {
+    // The key is the node's identity, only the current user should read it
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
+            .map_err(|e| StorageError::Io(e.to_string()))?;
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+    Ok(())
+}
+
+/// Copies the node's key to `destination` so the identity can be moved to
+/// another machine.
+pub fn copy_identity_to<B: StorageBackend>(
+    manager: &StorageManager<B>,
+    destination: &Path,
+) -> Result<(), StorageError> {
+    let key_file = identity_key_file(manager.data_dir());
+    if !key_file.exists() {
+        return Err(StorageError::FileNotFound(
+            "The node has no identity yet, start it once first".to_string(),
+        ));
+    }
+
+    std::fs::copy(&key_file, destination).map_err(|e| StorageError::Io(e.to_string()))?;
+    restrict_permissions(destination)
+}
+
+/// Replaces the node's key with an exported one and restarts the node with it.
+pub async fn install_identity_from<B: StorageBackend>(
+    manager: &StorageManager<B>,
+    source: &Path,
+) -> Result<(), StorageError> {
+    let key = std::fs::read(source).map_err(|e| StorageError::Io(e.to_string()))?;
+    if key.is_empty() {
+        return Err(StorageError::Configuration(
+            "Identity key file is empty".to_string(),
+        ));
+    }
+
+    replace_identity(manager, Some(&key)).await
+}
+
+/// Drops the node's key so it generates a new identity on restart.
+pub async fn reset_identity<B: StorageBackend>(
+    manager: &StorageManager<B>,
+) -> Result<(), StorageError> {
+    replace_identity(manager, None).await
+}
+
+async fn replace_identity<B: StorageBackend>(
+    manager: &StorageManager<B>,
+    key: Option<&[u8]>,
+) -> Result<(), StorageError> {
+    let key_file = identity_key_file(manager.data_dir());
+
+    // The node only reads its key when it is created
+    manager
+        .recreate_node(|| {
+            match key {
+                Some(key) => {
+                    std::fs::write(&key_file, key).map_err(|e| StorageError::Io(e.to_string()))?;
+                    restrict_permissions(&key_file)?;
+                }
+                None => {
+                    if key_file.exists() {
+                        std::fs::remove_file(&key_file)
+                            .map_err(|e| StorageError::Io(e.to_string()))?;
+                    }
+                }
+            }
+            Ok(())
+        })
+        .await
+}
@@ -0,0 +1,7 @@
+pub mod commands;
+pub mod identity;
+pub mod signing;
+
+pub use commands::*;
+pub use identity::*;
+pub use signing::*;
@@ -0,0 +1,101 @@
+use libp2p_identity::{Keypair, PeerId, PublicKey};
+use std::path::Path;
+
+use crate::features::catalog::ManifestSignature;
+use crate::features::identity::identity_key_file;
+use crate::features::shared::StorageError;
+
+// Keep these signatures from being replayed as signatures over anything else
+const SIGNATURE_DOMAIN: &[u8] = b"storeman-manifest-signature:";
+const OFFER_SIGNATURE_DOMAIN: &[u8] = b"storeman-offer-signature:";
+
+fn signed_message(domain: &[u8], cid: &str) -> Vec<u8> {
+    [domain, cid.as_bytes()].concat()
+}
+
+fn invalid_signature(reason: impl std::fmt::Display) -> StorageError {
+    StorageError::Configuration(format!("Invalid manifest signature: {}", reason))
+}
+
+/// Signs a CID with the node's identity key.
+///
+/// The signature carries the public key, since the default secp256k1 peer
+/// IDs only contain a hash of it.
+pub fn sign_cid(data_dir: &Path, cid: &str) -> Result<ManifestSignature, StorageError> {
+    sign_in_domain(data_dir, SIGNATURE_DOMAIN, cid)
+}
+
+/// Signs the manifest CID of an offer, so its recipient can tell the offer
+/// comes from the peer it names.
+pub fn sign_offer(data_dir: &Path, manifest_cid: &str) -> Result<ManifestSignature, StorageError> {
+    sign_in_domain(data_dir, OFFER_SIGNATURE_DOMAIN, manifest_cid)
+}
+
+fn sign_in_domain(
+    data_dir: &Path,
+    domain: &[u8],
+    cid: &str,
+) -> Result<ManifestSignature, StorageError> {
+    let key = std::fs::read(identity_key_file(data_dir)).map_err(|_| {
+        StorageError::FileNotFound("The node has no identity yet, start it once first".to_string())
+    })?;
+    let keypair = Keypair::from_protobuf_encoding(&key)
+        .map_err(|e| StorageError::Configuration(format!("Unreadable identity key: {}", e)))?;
+
+    let signature = keypair
+        .sign(&signed_message(domain, cid))
+        .map_err(|e| StorageError::Configuration(format!("Failed to sign manifest: {}", e)))?;
+    let public_key = keypair.public();
+
+    Ok(ManifestSignature {
+        peer_id: PeerId::from_public_key(&public_key).to_string(),
+        signature: format!(
+            "{}:{}",
+            hex::encode(public_key.encode_protobuf()),
+            hex::encode(signature)
+        ),
+        signed_at: chrono::Utc::now().timestamp_millis(),
+    })
+}
+
+/// Checks that `signature` was made over `cid` by the key behind `peer_id`.
+pub fn verify_cid_signature(
+    cid: &str,
+    signature: &str,
+    peer_id: &str,
+) -> Result<bool, StorageError> {
+    verify_in_domain(SIGNATURE_DOMAIN, cid, signature, peer_id)
+}
+
+/// Checks that `signature` was made over an offer's `manifest_cid` by the
+/// key behind `peer_id`.
+pub fn verify_offer_signature(
+    manifest_cid: &str,
+    signature: &str,
+    peer_id: &str,
+) -> Result<bool, StorageError> {
+    verify_in_domain(OFFER_SIGNATURE_DOMAIN, manifest_cid, signature, peer_id)
+}
+
+fn verify_in_domain(
+    domain: &[u8],
+    cid: &str,
+    signature: &str,
+    peer_id: &str,
+) -> Result<bool, StorageError> {
+    let (public_key, signature) = signature
+        .split_once(':')
+        .ok_or_else(|| invalid_signature("expected <public key>:<signature>"))?;
+    let public_key = hex::decode(public_key).map_err(invalid_signature)?;
+    let signature = hex::decode(signature).map_err(invalid_signature)?;
+    let public_key = PublicKey::try_decode_protobuf(&public_key).map_err(invalid_signature)?;
+
+    let expected_peer_id: PeerId = peer_id
+        .parse()
+        .map_err(|e| StorageError::Configuration(format!("Invalid peer ID: {}", e)))?;
+    if PeerId::from_public_key(&public_key) != expected_peer_id {
+        return Ok(false);
+    }
+
+    Ok(public_key.verify(&signed_message(domain, cid), &signature))
+}
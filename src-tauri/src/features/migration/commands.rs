@@ -0,0 +1,16 @@
+use crate::features::migration::{
+    latest_backup, latest_data_version, read_data_version, DataVersionInfo,
+};
+use crate::features::shared::{app_storage_dir, map_storage_error, CommandError};
+use tauri::AppHandle;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_data_version(app_handle: AppHandle) -> Result<DataVersionInfo, CommandError> {
+    let app_data_dir = app_storage_dir(&app_handle).map_err(map_storage_error)?;
+    Ok(DataVersionInfo {
+        current: read_data_version(&app_data_dir),
+        latest: latest_data_version(),
+        last_backup: latest_backup(&app_data_dir),
+    })
+}
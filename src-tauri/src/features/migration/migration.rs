@@ -0,0 +1,192 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::AppHandle;
+
+use crate::features::audit::AUDIT_LOG_FILE_NAME;
+use crate::features::bandwidth::BANDWIDTH_FILE_NAME;
+use crate::features::catalog::CATALOG_FILE_NAME;
+use crate::features::contacts::CONTACTS_FILE_NAME;
+use crate::features::offers::OFFERS_FILE_NAME;
+use crate::features::queue::QUEUE_FILE_NAME;
+use crate::features::recent::RECENT_FILE_NAME;
+use crate::features::settings::SETTINGS_FILE_NAME;
+use crate::features::shared::{app_storage_dir, read_json_file, write_json_file, StorageError};
+use crate::features::shares::SHARES_FILE_NAME;
+use crate::features::uptime::UPTIME_FILE_NAME;
+
+const DATA_VERSION_FILE_NAME: &str = "data_version.json";
+const BACKUPS_DIR_NAME: &str = "backups";
+
+/// Files in the app data directory covered by migrations and backed up
+/// before any migration runs, one per persisted store.
+///
+/// Key files are left out so backups never hold a second copy of them.
+const DATA_FILES: &[&str] = &[
+    SETTINGS_FILE_NAME,
+    QUEUE_FILE_NAME,
+    CATALOG_FILE_NAME,
+    RECENT_FILE_NAME,
+    SHARES_FILE_NAME,
+    CONTACTS_FILE_NAME,
+    OFFERS_FILE_NAME,
+    UPTIME_FILE_NAME,
+    BANDWIDTH_FILE_NAME,
+    AUDIT_LOG_FILE_NAME,
+];
+
+pub struct Migration {
+    pub version: u32,
+    pub description: &'static str,
+    pub apply: fn(&Path) -> Result<(), StorageError>,
+}
+
+/// Ordered list of migrations; each one brings the data directory from
+/// `version - 1` to `version`.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "Start tracking the data version",
+        apply: baseline,
+    },
+    Migration {
+        version: 2,
+        description: "Replace the download hash toggle with a verification level",
+        apply: download_verification_level,
+    },
+];
+
+fn baseline(_app_data_dir: &Path) -> Result<(), StorageError> {
+    Ok(())
+}
+
+fn download_verification_level(app_data_dir: &Path) -> Result<(), StorageError> {
+    let settings_file = app_data_dir.join("settings.json");
+    let Some(mut settings) = read_json_file::<serde_json::Value>(&settings_file) else {
+        return Ok(());
+    };
+    let Some(fields) = settings.as_object_mut() else {
+        return Ok(());
+    };
+
+    // Without the toggle the default size check applies
+    if let Some(verify_hashes) = fields.remove("verify_download_hashes") {
+        if verify_hashes.as_bool() == Some(true) {
+            fields.insert(
+                "download_verification".to_string(),
+                serde_json::Value::from("FullHash"),
+            );
+        }
+        write_json_file(&settings_file, &settings)?;
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct DataVersion {
+    pub version: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct DataVersionInfo {
+    pub current: u32,
+    pub latest: u32,
+    pub last_backup: Option<String>,
+}
+
+pub fn latest_data_version() -> u32 {
+    MIGRATIONS.last().map_or(0, |migration| migration.version)
+}
+
+pub fn read_data_version(app_data_dir: &Path) -> u32 {
+    read_json_file::<DataVersion>(&app_data_dir.join(DATA_VERSION_FILE_NAME))
+        .map_or(0, |data_version| data_version.version)
+}
+
+/// Most recent pre-migration backup directory, if any.
+pub fn latest_backup(app_data_dir: &Path) -> Option<String> {
+    std::fs::read_dir(app_data_dir.join(BACKUPS_DIR_NAME))
+        .ok()?
+        .flatten()
+        .filter_map(|entry| Some((entry.metadata().ok()?.modified().ok()?, entry.path())))
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path.to_string_lossy().to_string())
+}
+
+/// Copies every existing data file into a fresh backup directory.
+fn backup_data_files(app_data_dir: &Path, from_version: u32) -> Result<String, StorageError> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let backup_dir = app_data_dir
+        .join(BACKUPS_DIR_NAME)
+        .join(format!("v{}-{}", from_version, timestamp));
+    std::fs::create_dir_all(&backup_dir).map_err(|e| StorageError::Io(e.to_string()))?;
+
+    for file_name in DATA_FILES {
+        let source = app_data_dir.join(file_name);
+        if source.exists() {
+            std::fs::copy(&source, backup_dir.join(file_name))
+                .map_err(|e| StorageError::Io(e.to_string()))?;
+        }
+    }
+
+    Ok(backup_dir.to_string_lossy().to_string())
+}
+
+/// Brings the app data directory up to the latest data version.
+///
+/// Must run before any store loads its files.
+pub fn run_migrations(app_handle: &AppHandle) -> Result<DataVersionInfo, StorageError> {
+    let app_data_dir = app_storage_dir(app_handle)?;
+    std::fs::create_dir_all(&app_data_dir).map_err(|e| StorageError::Io(e.to_string()))?;
+
+    let current = read_data_version(&app_data_dir);
+    let latest = latest_data_version();
+
+    if current > latest {
+        return Err(StorageError::Configuration(format!(
+            "Data directory was written by a newer version (data version {}, supported {})",
+            current, latest
+        )));
+    }
+
+    let pending: Vec<&Migration> = MIGRATIONS
+        .iter()
+        .filter(|migration| migration.version > current)
+        .collect();
+    if pending.is_empty() {
+        return Ok(DataVersionInfo {
+            current,
+            latest,
+            last_backup: latest_backup(&app_data_dir),
+        });
+    }
+
+    let backup = backup_data_files(&app_data_dir, current)?;
+    println!("Backed up data files to {}", backup);
+
+    for migration in pending {
+        println!(
+            "Migrating data to version {}: {}",
+            migration.version, migration.description
+        );
+        (migration.apply)(&app_data_dir)?;
+
+        // Record each step so an interrupted run resumes where it stopped
+        write_json_file(
+            &app_data_dir.join(DATA_VERSION_FILE_NAME),
+            &DataVersion {
+                version: migration.version,
+            },
+        )?;
+    }
+
+    Ok(DataVersionInfo {
+        current: latest,
+        latest,
+        last_backup: Some(backup),
+    })
+}
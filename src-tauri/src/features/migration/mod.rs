@@ -0,0 +1,5 @@
+pub mod commands;
+pub mod migration;
+
+pub use commands::*;
+pub use migration::*;
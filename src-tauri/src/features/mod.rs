@@ -1,4 +1,28 @@
+pub mod audit;
+pub mod backend;
+pub mod bandwidth;
+pub mod catalog;
+pub mod cleanup;
+pub mod cli;
+pub mod confirmation;
 pub mod connection;
+pub mod contacts;
+pub mod crash;
 pub mod download;
+pub mod events;
+pub mod identity;
+pub mod migration;
+pub mod offers;
+pub mod onboarding;
+pub mod peers;
+pub mod queue;
+pub mod recent;
+pub mod rpc;
+pub mod settings;
 pub mod shared;
+pub mod shares;
+pub mod storage;
+pub mod system;
+pub mod tasks;
 pub mod upload;
+pub mod uptime;
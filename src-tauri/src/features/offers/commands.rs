@@ -0,0 +1,55 @@
+use crate::features::offers::{
+    accept_received_offer, get_offer_inbox_with_handle, publish_offer, Offer, OfferStatus,
+};
+use crate::features::queue::EnqueueDownloadResult;
+use crate::features::shared::{map_storage_error, validate_user_path, CommandError};
+use tauri::AppHandle;
+
+/// Offers a stored dataset to a peer, returning the offer manifest's CID.
+#[tauri::command]
+#[specta::specta]
+pub async fn send_offer(
+    peer_id: String,
+    cid: String,
+    message: Option<String>,
+    app_handle: AppHandle,
+) -> Result<String, CommandError> {
+    publish_offer(&app_handle, &peer_id, &cid, message)
+        .await
+        .map_err(map_storage_error)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_offers(app_handle: AppHandle) -> Result<Vec<Offer>, CommandError> {
+    let inbox = get_offer_inbox_with_handle(&app_handle)
+        .await
+        .map_err(map_storage_error)?;
+    Ok(inbox.list().await)
+}
+
+/// Queues the download of an offered dataset.
+#[tauri::command]
+#[specta::specta]
+pub async fn accept_offer(
+    id: String,
+    save_path: String,
+    app_handle: AppHandle,
+) -> Result<EnqueueDownloadResult, CommandError> {
+    let save_path = validate_user_path(&app_handle, &save_path).map_err(map_storage_error)?;
+    accept_received_offer(&app_handle, &id, save_path.to_string_lossy().to_string())
+        .await
+        .map_err(map_storage_error)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn decline_offer(id: String, app_handle: AppHandle) -> Result<Offer, CommandError> {
+    let inbox = get_offer_inbox_with_handle(&app_handle)
+        .await
+        .map_err(map_storage_error)?;
+    inbox
+        .set_status(&id, OfferStatus::Declined)
+        .await
+        .map_err(map_storage_error)
+}
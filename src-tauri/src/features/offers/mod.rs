@@ -0,0 +1,7 @@
+pub mod commands;
+pub mod offers;
+pub mod server;
+
+pub use commands::*;
+pub use offers::*;
+pub use server::*;
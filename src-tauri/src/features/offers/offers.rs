@@ -0,0 +1,436 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::AppHandle;
+use tokio::sync::{Mutex, Notify, OnceCell, Semaphore, SemaphorePermit};
+
+use crate::features::backend::StorageBackend;
+use crate::features::catalog::get_catalog_with_handle;
+use crate::features::connection::get_storage_manager_with_handle;
+use crate::features::contacts::get_contact_book_with_handle;
+use crate::features::events::{emit_event, OFFER_RECEIVED_EVENT};
+use crate::features::identity::{sign_offer, verify_offer_signature};
+use crate::features::peers::{find_local_peer, node_address_report};
+use crate::features::queue::{enqueue_download_unless_present, EnqueueDownloadResult};
+use crate::features::settings::{get_settings_store_with_handle, http_client};
+use crate::features::shared::{
+    app_data_file, read_json_file, staging_file, write_json_file, StorageError,
+};
+use crate::features::storage::dataset_exists_locally;
+
+/// Inbox port assumed for peers when ours isn't configured.
+pub const DEFAULT_OFFER_PORT: u16 = 8473;
+
+pub const OFFERS_FILE_NAME: &str = "offers.json";
+
+/// Format version written by this build; newer manifests are refused.
+const OFFER_MANIFEST_VERSION: u32 = 1;
+
+/// Offer manifests are a few hundred bytes, anything past this isn't one.
+const MAX_MANIFEST_BYTES: usize = 64 * 1024;
+
+/// Largest notice body the inbox reads.
+pub const MAX_NOTICE_BYTES: usize = 8 * 1024;
+
+/// Manifests fetched at once, further notices are turned away until one
+/// finishes.
+const MAX_CONCURRENT_OFFER_FETCHES: usize = 4;
+
+const MANIFEST_FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+const NOTIFY_TIMEOUT: Duration = Duration::from_secs(10);
+
+static OFFER_FETCHES: Semaphore = Semaphore::const_new(MAX_CONCURRENT_OFFER_FETCHES);
+
+/// What the sender publishes for the recipient to look at before accepting.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct OfferManifest {
+    pub version: u32,
+    pub from_peer_id: String,
+    #[serde(default)]
+    pub from_addresses: Vec<String>,
+    pub cid: String,
+    #[serde(default)]
+    pub filename: Option<String>,
+    #[serde(default)]
+    pub size: Option<u64>,
+    #[serde(default)]
+    pub message: Option<String>,
+    /// Milliseconds since the Unix epoch.
+    pub created_at: i64,
+}
+
+/// Sent to the recipient's inbox, which fetches the manifest from the sender.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct OfferNotice {
+    pub manifest_cid: String,
+    pub from_peer_id: String,
+    #[serde(default)]
+    pub from_addresses: Vec<String>,
+    /// The sender's signature over `manifest_cid`, made with the key behind
+    /// `from_peer_id`.
+    pub signature: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum OfferStatus {
+    Pending,
+    Accepted,
+    Declined,
+}
+
+/// A received offer, identified by its manifest's CID.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct Offer {
+    pub id: String,
+    pub manifest: OfferManifest,
+    /// Milliseconds since the Unix epoch.
+    pub received_at: i64,
+    pub status: OfferStatus,
+}
+
+/// Received offers keyed by ID, mirrored to disk.
+pub struct OfferInbox {
+    offers: Mutex<BTreeMap<String, Offer>>,
+    offers_file: PathBuf,
+}
+
+impl OfferInbox {
+    pub fn load(offers_file: PathBuf) -> Self {
+        let offers = read_json_file(&offers_file).unwrap_or_default();
+        Self {
+            offers: Mutex::new(offers),
+            offers_file,
+        }
+    }
+
+    /// Stores an offer, returning `false` if it was already received.
+    pub async fn insert(&self, offer: Offer) -> Result<bool, StorageError> {
+        let mut offers = self.offers.lock().await;
+        if offers.contains_key(&offer.id) {
+            return Ok(false);
+        }
+        offers.insert(offer.id.clone(), offer);
+        write_json_file(&self.offers_file, &*offers)?;
+        Ok(true)
+    }
+
+    pub async fn get(&self, id: &str) -> Option<Offer> {
+        self.offers.lock().await.get(id).cloned()
+    }
+
+    pub async fn list(&self) -> Vec<Offer> {
+        self.offers.lock().await.values().cloned().collect()
+    }
+
+    pub async fn set_status(&self, id: &str, status: OfferStatus) -> Result<Offer, StorageError> {
+        let mut offers = self.offers.lock().await;
+        let offer = offers
+            .get_mut(id)
+            .ok_or_else(|| StorageError::Configuration(format!("No offer {}", id)))?;
+        offer.status = status;
+        let offer = offer.clone();
+        write_json_file(&self.offers_file, &*offers)?;
+        Ok(offer)
+    }
+}
+
+// Global offer inbox instance
+pub static OFFER_INBOX: OnceCell<Arc<OfferInbox>> = OnceCell::const_new();
+
+pub async fn get_offer_inbox_with_handle(
+    app_handle: &AppHandle,
+) -> Result<Arc<OfferInbox>, StorageError> {
+    OFFER_INBOX
+        .get_or_try_init(|| async {
+            let offers_file = app_data_file(app_handle, OFFERS_FILE_NAME)?;
+            Ok(Arc::new(OfferInbox::load(offers_file)))
+        })
+        .await
+        .map(Arc::clone)
+}
+
+/// IP address of a multiaddress starting with `/ip4/` or `/ip6/`.
+fn address_host(address: &str) -> Option<IpAddr> {
+    let mut parts = address.split('/').skip(1);
+    match (parts.next(), parts.next()) {
+        (Some("ip4" | "ip6"), Some(ip)) => ip.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Hosts a peer may be reachable on, from mDNS and the contact list.
+async fn peer_hosts(app_handle: &AppHandle, peer_id: &str) -> Vec<IpAddr> {
+    let mut addresses = find_local_peer(peer_id)
+        .await
+        .map(|peer| peer.addresses)
+        .unwrap_or_default();
+    if let Ok(book) = get_contact_book_with_handle(app_handle).await {
+        for contact in book.list().await {
+            if contact.peer_id == peer_id {
+                addresses.extend(contact.addresses);
+            }
+        }
+    }
+
+    let mut hosts: Vec<IpAddr> = Vec::new();
+    for host in addresses.iter().filter_map(|address| address_host(address)) {
+        if !hosts.contains(&host) {
+            hosts.push(host);
+        }
+    }
+    hosts
+}
+
+/// Publishes an offer manifest for a stored dataset and notifies the peer's
+/// inbox. Returns the manifest's CID.
+pub async fn publish_offer(
+    app_handle: &AppHandle,
+    peer_id: &str,
+    cid: &str,
+    message: Option<String>,
+) -> Result<String, StorageError> {
+    let manager = get_storage_manager_with_handle(Some(app_handle.clone())).await?;
+    manager.ensure_hosting()?;
+    let node = manager.get_node().await?;
+    if !node.is_started() {
+        return Err(StorageError::NodeNotStarted);
+    }
+    if !dataset_exists_locally(&node, cid).await? {
+        return Err(StorageError::InvalidCid(format!(
+            "{} is not stored locally",
+            cid
+        )));
+    }
+
+    let hosts = peer_hosts(app_handle, peer_id).await;
+    if hosts.is_empty() {
+        return Err(StorageError::Configuration(format!(
+            "No known address for peer {}",
+            peer_id
+        )));
+    }
+
+    let settings = get_settings_store_with_handle(app_handle)
+        .await?
+        .get()
+        .await;
+    let own_addresses = node_address_report(&node, &settings).await?;
+    let entry = get_catalog_with_handle(app_handle).await?.get(cid).await;
+    let manifest = OfferManifest {
+        version: OFFER_MANIFEST_VERSION,
+        from_peer_id: own_addresses.peer_id.clone(),
+        from_addresses: own_addresses.announced_addresses.clone(),
+        cid: cid.to_string(),
+        filename: entry.as_ref().and_then(|entry| entry.name.clone()),
+        size: entry.as_ref().and_then(|entry| entry.size),
+        message,
+        created_at: chrono::Utc::now().timestamp_millis(),
+    };
+
+    let manifest_path = staging_file(app_handle).await?;
+    let contents = serde_json::to_vec(&manifest).map_err(|e| StorageError::Io(e.to_string()))?;
+    std::fs::write(&manifest_path, contents).map_err(|e| StorageError::Io(e.to_string()))?;
+    let uploaded = node.upload(&manifest_path, None, Box::new(|_, _| {})).await;
+    let _ = std::fs::remove_file(&manifest_path);
+    let manifest_cid = uploaded?;
+    let signature = sign_offer(manager.data_dir(), &manifest_cid)?;
+
+    let notice = OfferNotice {
+        manifest_cid: manifest_cid.clone(),
+        from_peer_id: manifest.from_peer_id,
+        from_addresses: manifest.from_addresses,
+        signature: signature.signature,
+    };
+    let port = settings
+        .offer_inbox
+        .map(|inbox| inbox.port)
+        .unwrap_or(DEFAULT_OFFER_PORT);
+    // Inboxes are reached directly, never through the proxy
+    let client = http_client(None)?;
+    let mut last_error = None;
+    for host in hosts {
+        let url = format!("http://{}/offers", std::net::SocketAddr::new(host, port));
+        match client
+            .post(&url)
+            .json(&notice)
+            .timeout(NOTIFY_TIMEOUT)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+        {
+            Ok(_) => return Ok(manifest_cid),
+            Err(e) => last_error = Some(e.to_string()),
+        }
+    }
+    Err(StorageError::Io(format!(
+        "Failed to reach the offer inbox of {}: {}",
+        peer_id,
+        last_error.unwrap_or_default()
+    )))
+}
+
+/// Reserves one of the manifest fetch slots, `None` if they are all taken.
+pub fn try_reserve_offer_fetch() -> Option<SemaphorePermit<'static>> {
+    OFFER_FETCHES.try_acquire().ok()
+}
+
+/// Checks that a notice was signed by the peer it claims to come from,
+/// before anything is dialed or fetched for it.
+pub fn verify_offer_notice(notice: &OfferNotice) -> Result<(), StorageError> {
+    if !verify_offer_signature(
+        &notice.manifest_cid,
+        &notice.signature,
+        &notice.from_peer_id,
+    )? {
+        return Err(StorageError::Configuration(format!(
+            "Offer notice isn't signed by {}",
+            notice.from_peer_id
+        )));
+    }
+    Ok(())
+}
+
+/// Downloads the manifest to `manifest_path`, giving up as soon as it grows
+/// past what a manifest can be.
+async fn fetch_manifest<B: StorageBackend>(
+    node: &B,
+    manifest_cid: &str,
+    manifest_path: &std::path::Path,
+) -> Result<Vec<u8>, StorageError> {
+    let too_large = Arc::new(Notify::new());
+    let on_progress = {
+        let too_large = Arc::clone(&too_large);
+        Box::new(move |bytes: usize, total: Option<usize>| {
+            if bytes > MAX_MANIFEST_BYTES || total.is_some_and(|total| total > MAX_MANIFEST_BYTES) {
+                too_large.notify_one();
+            }
+        })
+    };
+
+    let too_large_error = || {
+        StorageError::Configuration(format!(
+            "Offer manifest is over {} bytes, too large to be one",
+            MAX_MANIFEST_BYTES
+        ))
+    };
+    let size = tokio::select! {
+        fetched = tokio::time::timeout(
+            MANIFEST_FETCH_TIMEOUT,
+            node.download(manifest_cid, manifest_path, on_progress),
+        ) => fetched.map_err(|_| {
+            StorageError::Download("Timed out fetching the offer manifest".to_string())
+        })??,
+        _ = too_large.notified() => return Err(too_large_error()),
+    };
+    if size > MAX_MANIFEST_BYTES {
+        return Err(too_large_error());
+    }
+    std::fs::read(manifest_path).map_err(|e| StorageError::Io(e.to_string()))
+}
+
+/// Fetches the manifest a notice points to and files the offer.
+pub async fn receive_offer(
+    app_handle: &AppHandle,
+    notice: OfferNotice,
+) -> Result<(), StorageError> {
+    verify_offer_notice(&notice)?;
+
+    let manager = get_storage_manager_with_handle(Some(app_handle.clone())).await?;
+    // Fetching the manifest would store its blocks
+    manager.ensure_writable()?;
+    let node = manager.get_node().await?;
+    if !node.is_started() {
+        return Err(StorageError::NodeNotStarted);
+    }
+
+    let inbox = get_offer_inbox_with_handle(app_handle).await?;
+    if inbox.get(&notice.manifest_cid).await.is_some() {
+        return Ok(());
+    }
+
+    // Whatever we already hosted stays, only a manifest fetched for this
+    // offer is dropped again
+    let held_before = dataset_exists_locally(&node, &notice.manifest_cid)
+        .await
+        .unwrap_or(true);
+
+    if let Err(e) = manager
+        .connect_to_peer(notice.from_peer_id.clone(), notice.from_addresses.clone())
+        .await
+    {
+        eprintln!("Failed to dial offer sender {}: {}", notice.from_peer_id, e);
+    }
+
+    let manifest_path = staging_file(app_handle).await?;
+    let contents = fetch_manifest(&node, &notice.manifest_cid, &manifest_path).await;
+    let _ = std::fs::remove_file(&manifest_path);
+    if !held_before {
+        // The manifest was only needed to read it, don't host it
+        if let Err(e) = node.delete(&notice.manifest_cid).await {
+            eprintln!("Failed to drop offer manifest: {}", e);
+        }
+    }
+
+    let manifest: OfferManifest = serde_json::from_slice(&contents?)
+        .map_err(|e| StorageError::Configuration(format!("Invalid offer manifest: {}", e)))?;
+    if manifest.version > OFFER_MANIFEST_VERSION {
+        return Err(StorageError::Unsupported(format!(
+            "offer manifest version {} is newer than this app supports",
+            manifest.version
+        )));
+    }
+    if manifest.from_peer_id != notice.from_peer_id {
+        return Err(StorageError::Configuration(
+            "Offer manifest wasn't made by the peer that sent it".to_string(),
+        ));
+    }
+
+    let offer = Offer {
+        id: notice.manifest_cid,
+        manifest,
+        received_at: chrono::Utc::now().timestamp_millis(),
+        status: OfferStatus::Pending,
+    };
+    if inbox.insert(offer.clone()).await? {
+        let _ = emit_event(app_handle, OFFER_RECEIVED_EVENT, offer);
+    }
+    Ok(())
+}
+
+/// Accepts a pending offer by queueing the download of its dataset.
+pub async fn accept_received_offer(
+    app_handle: &AppHandle,
+    id: &str,
+    save_path: String,
+) -> Result<EnqueueDownloadResult, StorageError> {
+    let inbox = get_offer_inbox_with_handle(app_handle).await?;
+    let offer = inbox
+        .get(id)
+        .await
+        .ok_or_else(|| StorageError::Configuration(format!("No offer {}", id)))?;
+
+    // Best effort, the node can still find the sender through the DHT
+    let manager = get_storage_manager_with_handle(Some(app_handle.clone())).await?;
+    if let Err(e) = manager
+        .connect_to_peer(
+            offer.manifest.from_peer_id.clone(),
+            offer.manifest.from_addresses.clone(),
+        )
+        .await
+    {
+        eprintln!(
+            "Failed to dial offer sender {}: {}",
+            offer.manifest.from_peer_id, e
+        );
+    }
+
+    let result =
+        enqueue_download_unless_present(app_handle, offer.manifest.cid, save_path, false).await?;
+    inbox.set_status(id, OfferStatus::Accepted).await?;
+    Ok(result)
+}
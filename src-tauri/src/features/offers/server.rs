@@ -0,0 +1,72 @@
+use axum::extract::{DefaultBodyLimit, State};
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::{Json, Router};
+use tauri::AppHandle;
+
+use crate::features::offers::{
+    receive_offer, try_reserve_offer_fetch, verify_offer_notice, OfferNotice, MAX_NOTICE_BYTES,
+};
+use crate::features::settings::get_settings_store_with_handle;
+use crate::features::shared::StorageError;
+use crate::features::tasks::task_manager;
+
+/// Takes the notice and fetches its manifest in the background, the sender
+/// only waits for delivery.
+///
+/// Unsigned notices are refused outright, and so is everything past the
+/// fetches already running.
+async fn handle_offer(
+    State(app_handle): State<AppHandle>,
+    Json(notice): Json<OfferNotice>,
+) -> StatusCode {
+    if let Err(e) = verify_offer_notice(&notice) {
+        eprintln!("Refused offer notice: {}", e);
+        return StatusCode::FORBIDDEN;
+    }
+    let Some(permit) = try_reserve_offer_fetch() else {
+        return StatusCode::TOO_MANY_REQUESTS;
+    };
+
+    // Named per notice, a second one must not cancel a fetch still running
+    let task_name = format!("receive-offer-{}", uuid::Uuid::new_v4());
+    task_manager().spawn(&task_name, async move {
+        let from_peer_id = notice.from_peer_id.clone();
+        if let Err(e) = receive_offer(&app_handle, notice).await {
+            eprintln!("Failed to receive offer from {}: {}", from_peer_id, e);
+        }
+        drop(permit);
+    });
+    StatusCode::ACCEPTED
+}
+
+/// Serves the offer inbox on every interface until the app exits.
+pub async fn serve_offer_inbox(app_handle: AppHandle, port: u16) -> Result<(), StorageError> {
+    let router = Router::new()
+        .route("/offers", post(handle_offer))
+        .layer(DefaultBodyLimit::max(MAX_NOTICE_BYTES))
+        .with_state(app_handle);
+
+    let listener = tokio::net::TcpListener::bind(("0.0.0.0", port))
+        .await
+        .map_err(|e| StorageError::Io(e.to_string()))?;
+    println!("Offer inbox listening on port {}", port);
+
+    axum::serve(listener, router)
+        .await
+        .map_err(|e| StorageError::Io(e.to_string()))
+}
+
+/// Starts the offer inbox if it is enabled in the settings.
+pub async fn run_offer_inbox(app_handle: AppHandle) {
+    let offer_inbox = match get_settings_store_with_handle(&app_handle).await {
+        Ok(store) => store.get().await.offer_inbox,
+        Err(_) => None,
+    };
+
+    if let Some(offer_inbox) = offer_inbox {
+        if let Err(e) = serve_offer_inbox(app_handle, offer_inbox.port).await {
+            eprintln!("Offer inbox stopped: {}", e);
+        }
+    }
+}
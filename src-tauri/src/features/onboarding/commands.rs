@@ -0,0 +1,11 @@
+use crate::features::onboarding::{check_onboarding, OnboardingReport};
+use crate::features::shared::CommandError;
+use tauri::AppHandle;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn run_onboarding_checks(
+    app_handle: AppHandle,
+) -> Result<OnboardingReport, CommandError> {
+    Ok(check_onboarding(&app_handle).await)
+}
@@ -0,0 +1,5 @@
+pub mod commands;
+pub mod onboarding;
+
+pub use commands::*;
+pub use onboarding::*;
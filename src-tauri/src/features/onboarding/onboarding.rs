@@ -0,0 +1,200 @@
+use serde::Serialize;
+use specta::Type;
+use std::net::UdpSocket;
+use tauri::AppHandle;
+
+use crate::features::connection::{node_data_dir, DISCOVERY_PORT, STORAGE_MANAGER};
+use crate::features::events::{emit_event, ONBOARDING_CHECKS_EVENT};
+use crate::features::settings::get_settings_store_with_handle;
+use crate::features::shared::{app_storage_dir, StorageConnectionStatus, StorageError};
+use crate::features::system::network_addresses;
+
+/// Below this the node can't fill its default 1 GB quota.
+const RECOMMENDED_FREE_BYTES: u64 = 1024 * 1024 * 1024;
+/// Below this the node won't even get its repo set up.
+const MIN_FREE_BYTES: u64 = 64 * 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize, Type, PartialEq)]
+pub enum OnboardingCheckKind {
+    DataDirWritable,
+    PortAvailable,
+    BootstrapReachable,
+    DiskSpace,
+}
+
+#[derive(Debug, Clone, Serialize, Type, PartialEq)]
+pub enum CheckStatus {
+    Passed,
+    /// The node can start but the user should know about it.
+    Warning,
+    /// The node won't start until this is fixed.
+    Failed,
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct OnboardingCheck {
+    pub kind: OnboardingCheckKind,
+    pub status: CheckStatus,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct OnboardingReport {
+    pub checks: Vec<OnboardingCheck>,
+    /// No check failed, the first connect is expected to work.
+    pub ready: bool,
+}
+
+fn check(kind: OnboardingCheckKind, status: CheckStatus, message: String) -> OnboardingCheck {
+    OnboardingCheck {
+        kind,
+        status,
+        message,
+    }
+}
+
+fn check_data_dir(app_handle: &AppHandle) -> OnboardingCheck {
+    let kind = OnboardingCheckKind::DataDirWritable;
+    let dir = match node_data_dir(app_handle) {
+        Ok(dir) => dir,
+        Err(e) => return check(kind, CheckStatus::Failed, e.to_string()),
+    };
+
+    let probe = dir.join(".write_probe");
+    let result = std::fs::create_dir_all(&dir)
+        .and_then(|_| std::fs::write(&probe, b"probe"))
+        .and_then(|_| std::fs::remove_file(&probe));
+    match result {
+        Ok(()) => check(kind, CheckStatus::Passed, dir.display().to_string()),
+        Err(e) => check(
+            kind,
+            CheckStatus::Failed,
+            format!("Can't write to {}: {}", dir.display(), e),
+        ),
+    }
+}
+
+async fn check_port(remote_node: bool) -> OnboardingCheck {
+    let kind = OnboardingCheckKind::PortAvailable;
+    if remote_node {
+        return check(
+            kind,
+            CheckStatus::Skipped,
+            "A remote node is configured".to_string(),
+        );
+    }
+
+    if let Some(manager) = STORAGE_MANAGER.get() {
+        if manager.get_status().await == StorageConnectionStatus::Connected {
+            return check(
+                kind,
+                CheckStatus::Passed,
+                format!("Port {} is in use by the running node", DISCOVERY_PORT),
+            );
+        }
+    }
+
+    match UdpSocket::bind(("0.0.0.0", DISCOVERY_PORT)) {
+        Ok(_) => check(
+            kind,
+            CheckStatus::Passed,
+            format!("Port {} is free", DISCOVERY_PORT),
+        ),
+        Err(e) => check(
+            kind,
+            CheckStatus::Failed,
+            format!(
+                "Port {} is taken, close the other application or node using it: {}",
+                DISCOVERY_PORT, e
+            ),
+        ),
+    }
+}
+
+/// The bootstrap records are built into codex-bindings and only dialed by
+/// the node itself, so all that can be checked up front is being online.
+fn check_bootstrap() -> OnboardingCheck {
+    let kind = OnboardingCheckKind::BootstrapReachable;
+    if network_addresses().is_empty() {
+        check(
+            kind,
+            CheckStatus::Failed,
+            "No network connection, the node can't reach the bootstrap nodes".to_string(),
+        )
+    } else {
+        check(
+            kind,
+            CheckStatus::Passed,
+            "Network available, bootstrap nodes are contacted when the node starts".to_string(),
+        )
+    }
+}
+
+fn check_disk_space(app_handle: &AppHandle) -> OnboardingCheck {
+    let kind = OnboardingCheckKind::DiskSpace;
+    let available = app_storage_dir(app_handle)
+        .and_then(|dir| fs2::available_space(dir).map_err(|e| StorageError::Io(e.to_string())));
+    match available {
+        Ok(bytes) if bytes < MIN_FREE_BYTES => check(
+            kind,
+            CheckStatus::Failed,
+            format!("Only {} bytes free, free up some disk space", bytes),
+        ),
+        Ok(bytes) if bytes < RECOMMENDED_FREE_BYTES => check(
+            kind,
+            CheckStatus::Warning,
+            format!(
+                "Only {} bytes free, less than the node's storage quota",
+                bytes
+            ),
+        ),
+        Ok(bytes) => check(kind, CheckStatus::Passed, format!("{} bytes free", bytes)),
+        Err(e) => check(kind, CheckStatus::Warning, e.to_string()),
+    }
+}
+
+/// Checks what commonly keeps the node from starting, so the UI can walk
+/// the user through it instead of showing a failed connect.
+pub async fn check_onboarding(app_handle: &AppHandle) -> OnboardingReport {
+    let remote_node = match get_settings_store_with_handle(app_handle).await {
+        Ok(store) => store.get().await.remote_node.is_some(),
+        Err(_) => false,
+    };
+
+    let checks = vec![
+        check_data_dir(app_handle),
+        check_port(remote_node).await,
+        check_bootstrap(),
+        check_disk_space(app_handle),
+    ];
+    let ready = checks
+        .iter()
+        .all(|check| check.status != CheckStatus::Failed);
+    OnboardingReport { checks, ready }
+}
+
+/// Runs the checks on the first launch, before the node is started, until
+/// they pass once.
+pub async fn run_first_launch_checks(app_handle: &AppHandle) {
+    let Ok(store) = get_settings_store_with_handle(app_handle).await else {
+        return;
+    };
+    let mut settings = store.get().await;
+    if settings.onboarding_completed {
+        return;
+    }
+
+    let report = check_onboarding(app_handle).await;
+    if !report.ready {
+        println!("Onboarding checks failed: {:?}", report.checks);
+    }
+    let _ = emit_event(app_handle, ONBOARDING_CHECKS_EVENT, report.clone());
+
+    if report.ready {
+        settings.onboarding_completed = true;
+        if let Err(e) = store.update(settings).await {
+            eprintln!("Failed to save onboarding state: {}", e);
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use crate::features::backend::StorageBackend;
+use crate::features::connection::get_storage_manager_with_handle;
+use crate::features::peers::{
+    find_local_peer, local_peers, measure_throughput, node_address_report, LocalPeer,
+    NodeAddresses, PeerThroughput,
+};
+use crate::features::settings::get_settings_store_with_handle;
+use crate::features::shared::{map_storage_error, CommandError, ProviderInfo, StorageError};
+use crate::features::storage::dataset_exists_locally;
+use tauri::AppHandle;
+
+const DEFAULT_PROVIDER_LIMIT: usize = 20;
+
+/// Asks the DHT which peers provide a dataset.
+///
+/// Only nodes with `NodeInfo::provider_lookup` set can, the others fail
+/// with `Unsupported`.
+#[tauri::command]
+#[specta::specta]
+pub async fn find_providers(
+    cid: String,
+    limit: Option<usize>,
+    app_handle: AppHandle,
+) -> Result<Vec<ProviderInfo>, CommandError> {
+    if cid.is_empty() {
+        return Err(map_storage_error(StorageError::InvalidCid(
+            "CID cannot be empty".to_string(),
+        )));
+    }
+
+    let manager = get_storage_manager_with_handle(Some(app_handle))
+        .await
+        .map_err(map_storage_error)?;
+    let node = manager.get_node().await.map_err(map_storage_error)?;
+    if !node.supports_provider_lookup() {
+        return Err(map_storage_error(StorageError::Unsupported(
+            "this node can't look up providers, codex-bindings and the REST API have no DHT query"
+                .to_string(),
+        )));
+    }
+    if !node.is_started() {
+        return Err(map_storage_error(StorageError::NodeNotStarted));
+    }
+
+    node.find_providers(&cid, limit.unwrap_or(DEFAULT_PROVIDER_LIMIT))
+        .await
+        .map_err(map_storage_error)
+}
+
+/// Makes a locally stored dataset discoverable without waiting for the
+/// node's periodic announcements.
+///
+/// Only nodes with `NodeInfo::announce` set can, the others fail with
+/// `Unsupported`.
+#[tauri::command]
+#[specta::specta]
+pub async fn announce_dataset(cid: String, app_handle: AppHandle) -> Result<(), CommandError> {
+    let manager = get_storage_manager_with_handle(Some(app_handle))
+        .await
+        .map_err(map_storage_error)?;
+    manager.ensure_hosting().map_err(map_storage_error)?;
+    let node = manager.get_node().await.map_err(map_storage_error)?;
+    if !node.supports_announce() {
+        return Err(map_storage_error(StorageError::Unsupported(
+            "this node can't announce on demand, codex-bindings and the REST API have no DHT announce"
+                .to_string(),
+        )));
+    }
+    if !node.is_started() {
+        return Err(map_storage_error(StorageError::NodeNotStarted));
+    }
+
+    // Only content we can actually serve should be advertised
+    if !dataset_exists_locally(&node, &cid)
+        .await
+        .map_err(map_storage_error)?
+    {
+        return Err(map_storage_error(StorageError::InvalidCid(format!(
+            "{} is not stored locally",
+            cid
+        ))));
+    }
+
+    node.announce(&cid).await.map_err(map_storage_error)
+}
+
+/// Peers found on the local network through mDNS.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_local_peers() -> Result<Vec<LocalPeer>, CommandError> {
+    Ok(local_peers().await)
+}
+
+/// Dials a peer found on the local network.
+#[tauri::command]
+#[specta::specta]
+pub async fn connect_local_peer(
+    peer_id: String,
+    app_handle: AppHandle,
+) -> Result<(), CommandError> {
+    let peer = find_local_peer(&peer_id).await.ok_or_else(|| {
+        map_storage_error(StorageError::Configuration(format!(
+            "{} is not on the local network",
+            peer_id
+        )))
+    })?;
+
+    let manager = get_storage_manager_with_handle(Some(app_handle))
+        .await
+        .map_err(map_storage_error)?;
+    manager
+        .connect_to_peer(peer.peer_id, peer.addresses)
+        .await
+        .map_err(map_storage_error)
+}
+
+/// Addresses to share so other peers can dial this node.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_node_addresses(app_handle: AppHandle) -> Result<NodeAddresses, CommandError> {
+    let manager = get_storage_manager_with_handle(Some(app_handle.clone()))
+        .await
+        .map_err(map_storage_error)?;
+    let node = manager.get_node().await.map_err(map_storage_error)?;
+    if !node.is_started() {
+        return Err(map_storage_error(StorageError::NodeNotStarted));
+    }
+
+    let settings = get_settings_store_with_handle(&app_handle)
+        .await
+        .map_err(map_storage_error)?
+        .get()
+        .await;
+    node_address_report(&node, &settings)
+        .await
+        .map_err(map_storage_error)
+}
+
+/// Measures how fast a dataset the peer provides can be fetched, reading
+/// at most `sample_size` bytes.
+#[tauri::command]
+#[specta::specta]
+pub async fn measure_peer_throughput(
+    peer_id: String,
+    cid: String,
+    sample_size: Option<usize>,
+    app_handle: AppHandle,
+) -> Result<PeerThroughput, CommandError> {
+    measure_throughput(&app_handle, &peer_id, &cid, sample_size)
+        .await
+        .map_err(map_storage_error)
+}
@@ -0,0 +1,203 @@
+use mdns_sd::{ServiceDaemon, ServiceEvent, ServiceInfo};
+use serde::Serialize;
+use specta::Type;
+use std::collections::HashMap;
+use std::time::Duration;
+use tauri::AppHandle;
+use tokio::sync::Mutex;
+
+use crate::features::backend::StorageBackend;
+use crate::features::connection::get_storage_manager_with_handle;
+use crate::features::events::{emit_event, LOCAL_PEER_DISCOVERED_EVENT, LOCAL_PEER_LOST_EVENT};
+use crate::features::peers::node_addresses;
+use crate::features::settings::get_settings_store_with_handle;
+use crate::features::shared::{StorageConnectionStatus, StorageError};
+use crate::features::tasks::task_manager;
+
+/// Service type the app advertises its node under.
+const SERVICE_TYPE: &str = "_dextools._udp.local.";
+
+const LOCAL_DISCOVERY_INTERVAL: Duration = Duration::from_secs(15);
+/// Background task listening for other nodes while we advertise.
+const MDNS_BROWSE_TASK: &str = "mdns-browse";
+
+/// A node found on the local network through mDNS.
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct LocalPeer {
+    pub peer_id: String,
+    /// Multiaddresses built from the addresses the peer resolved to.
+    pub addresses: Vec<String>,
+    pub host_name: String,
+}
+
+/// Discovered peers, keyed by their mDNS service name.
+static LOCAL_PEERS: Mutex<Option<HashMap<String, LocalPeer>>> = Mutex::const_new(None);
+
+/// Peers currently seen on the local network.
+pub async fn local_peers() -> Vec<LocalPeer> {
+    LOCAL_PEERS
+        .lock()
+        .await
+        .as_ref()
+        .map(|peers| peers.values().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Looks up a discovered peer by its peer ID.
+pub async fn find_local_peer(peer_id: &str) -> Option<LocalPeer> {
+    local_peers()
+        .await
+        .into_iter()
+        .find(|peer| peer.peer_id == peer_id)
+}
+
+/// TCP port of the first multiaddress listening on one.
+fn listen_port(addresses: &[String]) -> Option<u16> {
+    addresses.iter().find_map(|address| {
+        let mut parts = address.split('/');
+        parts.find(|part| *part == "tcp")?;
+        parts.next()?.parse().ok().filter(|port| *port != 0)
+    })
+}
+
+struct LocalAdvertisement {
+    daemon: ServiceDaemon,
+    peer_id: String,
+}
+
+fn advertise(
+    app_handle: &AppHandle,
+    peer_id: &str,
+    port: u16,
+) -> Result<LocalAdvertisement, StorageError> {
+    let mdns_error = |e: mdns_sd::Error| StorageError::Configuration(format!("mDNS: {}", e));
+
+    let daemon = ServiceDaemon::new().map_err(mdns_error)?;
+    let properties = HashMap::from([("peer_id".to_string(), peer_id.to_string())]);
+    // Peer IDs are unique and short enough for an mDNS instance name
+    let host_name = format!("{}.local.", peer_id);
+    let service = ServiceInfo::new(SERVICE_TYPE, peer_id, &host_name, "", port, properties)
+        .map_err(mdns_error)?
+        .enable_addr_auto();
+    daemon.register(service).map_err(mdns_error)?;
+
+    let receiver = daemon.browse(SERVICE_TYPE).map_err(mdns_error)?;
+    let app_handle = app_handle.clone();
+    let own_peer_id = peer_id.to_string();
+    // Ends once the daemon shuts down and closes the channel
+    task_manager().spawn(MDNS_BROWSE_TASK, async move {
+        while let Ok(event) = receiver.recv_async().await {
+            match event {
+                ServiceEvent::ServiceResolved(info) => {
+                    let Some(peer_id) = info.get_property_val_str("peer_id") else {
+                        continue;
+                    };
+                    if peer_id == own_peer_id {
+                        continue;
+                    }
+                    let addresses = info
+                        .get_addresses()
+                        .iter()
+                        .map(|ip| match ip {
+                            std::net::IpAddr::V4(ip) => {
+                                format!("/ip4/{}/tcp/{}", ip, info.get_port())
+                            }
+                            std::net::IpAddr::V6(ip) => {
+                                format!("/ip6/{}/tcp/{}", ip, info.get_port())
+                            }
+                        })
+                        .collect();
+                    let peer = LocalPeer {
+                        peer_id: peer_id.to_string(),
+                        addresses,
+                        host_name: info.get_hostname().to_string(),
+                    };
+                    LOCAL_PEERS
+                        .lock()
+                        .await
+                        .get_or_insert_with(HashMap::new)
+                        .insert(info.get_fullname().to_string(), peer.clone());
+                    let _ = emit_event(&app_handle, LOCAL_PEER_DISCOVERED_EVENT, peer);
+                }
+                ServiceEvent::ServiceRemoved(_, fullname) => {
+                    let removed = LOCAL_PEERS
+                        .lock()
+                        .await
+                        .as_mut()
+                        .and_then(|peers| peers.remove(&fullname));
+                    if let Some(peer) = removed {
+                        let _ = emit_event(&app_handle, LOCAL_PEER_LOST_EVENT, peer);
+                    }
+                }
+                _ => {}
+            }
+        }
+    });
+
+    println!("Advertising node {} on the local network", peer_id);
+    Ok(LocalAdvertisement {
+        daemon,
+        peer_id: peer_id.to_string(),
+    })
+}
+
+async fn stop_advertising(app_handle: &AppHandle, advertisement: LocalAdvertisement) {
+    if let Err(e) = advertisement.daemon.shutdown() {
+        eprintln!("Failed to stop mDNS discovery: {}", e);
+    }
+    task_manager().stop(MDNS_BROWSE_TASK).await;
+    let lost = LOCAL_PEERS.lock().await.take().unwrap_or_default();
+    for peer in lost.into_values() {
+        let _ = emit_event(app_handle, LOCAL_PEER_LOST_EVENT, peer);
+    }
+}
+
+/// Advertises the node on the local network and watches for other nodes
+/// doing the same, while local discovery is enabled and the node runs.
+pub async fn run_local_discovery(app_handle: AppHandle) {
+    let mut advertisement: Option<LocalAdvertisement> = None;
+
+    loop {
+        tokio::time::sleep(LOCAL_DISCOVERY_INTERVAL).await;
+
+        let enabled = match get_settings_store_with_handle(&app_handle).await {
+            Ok(store) => store.get().await.local_discovery,
+            Err(_) => false,
+        };
+        let mut node_peer = None;
+        if enabled {
+            if let Ok(manager) = get_storage_manager_with_handle(Some(app_handle.clone())).await {
+                if manager.get_status().await == StorageConnectionStatus::Connected {
+                    if let Ok(node) = manager.get_node().await {
+                        if let (Ok(peer_id), Ok(debug_info)) =
+                            (node.peer_id().await, node.debug().await)
+                        {
+                            node_peer = Some((peer_id, node_addresses(&debug_info)));
+                        }
+                    }
+                }
+            }
+        }
+
+        // Identity rotations change the peer ID, advertise the new one
+        let current = advertisement.as_ref().map(|ad| ad.peer_id.clone());
+        if current.is_some() && current != node_peer.as_ref().map(|(id, _)| id.clone()) {
+            if let Some(ad) = advertisement.take() {
+                stop_advertising(&app_handle, ad).await;
+            }
+        }
+
+        if advertisement.is_none() {
+            if let Some((peer_id, addresses)) = node_peer {
+                let Some(port) = listen_port(&addresses) else {
+                    eprintln!("Node has no TCP listen address to advertise over mDNS");
+                    continue;
+                };
+                match advertise(&app_handle, &peer_id, port) {
+                    Ok(ad) => advertisement = Some(ad),
+                    Err(e) => eprintln!("Failed to start mDNS discovery: {}", e),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,9 @@
+pub mod commands;
+pub mod local;
+pub mod peers;
+pub mod throughput;
+
+pub use commands::*;
+pub use local::*;
+pub use peers::*;
+pub use throughput::*;
@@ -0,0 +1,324 @@
+use codex_bindings::DebugInfo;
+use serde::Serialize;
+use specta::Type;
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::time::Duration;
+use tauri::AppHandle;
+
+use crate::features::backend::StorageBackend;
+use crate::features::connection::{get_storage_manager_with_handle, StorageManager};
+use crate::features::events::{
+    emit_event, PeerDirection, PeerEvent, PEER_CONNECTED_EVENT, PEER_DISCONNECTED_EVENT,
+};
+use crate::features::settings::AppSettings;
+use crate::features::shared::{Connectivity, ProviderInfo, StorageConnectionStatus, StorageError};
+
+const PEER_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Peers currently known to the node, keyed by peer ID.
+async fn current_peers<B: StorageBackend>(node: &B) -> HashMap<String, Vec<String>> {
+    let Ok(debug_info) = node.debug().await else {
+        return HashMap::new();
+    };
+
+    let mut peers: HashMap<String, Vec<String>> = HashMap::new();
+    for peer in debug_info.table.nodes {
+        peers.entry(peer.peer_id).or_default().push(peer.address);
+    }
+    peers
+}
+
+/// A list of strings from the node's debug info.
+///
+/// Read through the serialized form, the bindings' `DebugInfo` mirrors the
+/// REST API's `/debug/info`.
+fn debug_info_list(debug_info: &DebugInfo, keys: &[&str]) -> Vec<String> {
+    let Ok(value) = serde_json::to_value(debug_info) else {
+        return Vec::new();
+    };
+    keys.iter()
+        .find_map(|key| value.get(*key))
+        .and_then(|list| serde_json::from_value(list.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Addresses the node listens on.
+pub fn node_addresses(debug_info: &DebugInfo) -> Vec<String> {
+    debug_info_list(debug_info, &["addrs"])
+}
+
+/// Addresses the node announces to other peers.
+pub fn announce_addresses(debug_info: &DebugInfo) -> Vec<String> {
+    debug_info_list(debug_info, &["announceAddresses", "announce_addresses"])
+}
+
+/// The node's signed peer record, if it has one yet.
+pub fn node_spr(debug_info: &DebugInfo) -> Option<String> {
+    serde_json::to_value(debug_info)
+        .ok()?
+        .get("spr")?
+        .as_str()
+        .filter(|spr| !spr.is_empty())
+        .map(str::to_string)
+}
+
+/// Where the node can be reached.
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct NodeAddresses {
+    pub peer_id: String,
+    pub listen_addresses: Vec<String>,
+    /// Addresses to hand to other peers, each ending in `/p2p/<peer id>`.
+    /// The configured external address replaces what the node announces,
+    /// addresses outside the configured IP family are left out.
+    pub announced_addresses: Vec<String>,
+    pub ipv4_bound: bool,
+    pub ipv6_bound: bool,
+}
+
+pub async fn node_address_report<B: StorageBackend>(
+    node: &B,
+    settings: &AppSettings,
+) -> Result<NodeAddresses, StorageError> {
+    let peer_id = node.peer_id().await?;
+    let debug_info = node.debug().await?;
+    let listen_addresses = node_addresses(&debug_info);
+    let announced = match settings
+        .external_address
+        .as_ref()
+        .and_then(|address| address.multiaddr())
+    {
+        Some(address) => vec![address],
+        None => announce_addresses(&debug_info),
+    };
+    let announced_addresses = announced
+        .into_iter()
+        .filter(|address| settings.ip_family.allows(address))
+        .map(|address| {
+            if address.contains("/p2p/") {
+                address
+            } else {
+                format!("{}/p2p/{}", address, peer_id)
+            }
+        })
+        .collect();
+
+    Ok(NodeAddresses {
+        ipv4_bound: listen_addresses.iter().any(|a| a.starts_with("/ip4/")),
+        ipv6_bound: listen_addresses.iter().any(|a| a.starts_with("/ip6/")),
+        listen_addresses,
+        announced_addresses,
+        peer_id,
+    })
+}
+
+/// Fails when the running node isn't listening on every family the
+/// settings ask for.
+pub async fn check_ip_family<B: StorageBackend>(
+    node: &B,
+    settings: &AppSettings,
+) -> Result<(), StorageError> {
+    let report = node_address_report(node, settings).await?;
+    let mut missing = Vec::new();
+    if settings.ip_family.includes_ipv4() && !report.ipv4_bound {
+        missing.push("IPv4");
+    }
+    if settings.ip_family.includes_ipv6() && !report.ipv6_bound {
+        missing.push("IPv6");
+    }
+    if missing.is_empty() {
+        return Ok(());
+    }
+    Err(StorageError::Configuration(format!(
+        "The node isn't listening on {}",
+        missing.join(" or ")
+    )))
+}
+
+/// How other peers can reach the node, judged from what it announces.
+pub fn node_connectivity(debug_info: &DebugInfo) -> Connectivity {
+    let announced = announce_addresses(debug_info);
+    if announced
+        .iter()
+        .any(|address| !address.contains("/p2p-circuit") && !is_lan_address(address))
+    {
+        Connectivity::Direct
+    } else if announced
+        .iter()
+        .any(|address| address.contains("/p2p-circuit"))
+    {
+        Connectivity::Relayed
+    } else {
+        Connectivity::LocalOnly
+    }
+}
+
+async fn emit_peer_event(
+    app_handle: &AppHandle,
+    manager: &StorageManager,
+    event: &str,
+    peer_id: &str,
+    addresses: &[String],
+) {
+    let direction = if manager.was_dialed(peer_id).await {
+        PeerDirection::Outbound
+    } else {
+        PeerDirection::Inbound
+    };
+    let _ = emit_event(
+        app_handle,
+        event,
+        PeerEvent {
+            peer_id: peer_id.to_string(),
+            addresses: addresses.to_vec(),
+            direction,
+        },
+    );
+}
+
+/// Polls the node's peer table and emits an event for every peer that
+/// appears or goes away, for as long as the app is running.
+pub async fn run_peer_polling(app_handle: AppHandle) {
+    let mut known: HashMap<String, Vec<String>> = HashMap::new();
+
+    loop {
+        tokio::time::sleep(PEER_POLL_INTERVAL).await;
+
+        let Ok(manager) = get_storage_manager_with_handle(Some(app_handle.clone())).await else {
+            continue;
+        };
+        // A stopped node has no peers
+        let current = match manager.get_status().await {
+            StorageConnectionStatus::Connected => match manager.get_node().await {
+                Ok(node) => current_peers(&node).await,
+                Err(_) => continue,
+            },
+            _ => HashMap::new(),
+        };
+
+        for (peer_id, addresses) in &current {
+            if !known.contains_key(peer_id) {
+                emit_peer_event(
+                    &app_handle,
+                    &manager,
+                    PEER_CONNECTED_EVENT,
+                    peer_id,
+                    addresses,
+                )
+                .await;
+            }
+        }
+        for (peer_id, addresses) in &known {
+            if !current.contains_key(peer_id) {
+                emit_peer_event(
+                    &app_handle,
+                    &manager,
+                    PEER_DISCONNECTED_EVENT,
+                    peer_id,
+                    addresses,
+                )
+                .await;
+            }
+        }
+
+        known = current;
+    }
+}
+
+/// Providers dialed before a download at most.
+const MAX_DOWNLOAD_PROVIDERS: usize = 5;
+
+/// Whether a multiaddress points into a private or link-local network.
+pub fn is_lan_address(address: &str) -> bool {
+    let mut parts = address.split('/').skip(1);
+    match (parts.next(), parts.next()) {
+        (Some("ip4"), Some(ip)) => ip
+            .parse::<Ipv4Addr>()
+            .is_ok_and(|ip| ip.is_private() || ip.is_link_local()),
+        (Some("ip6"), Some(ip)) => ip.parse::<Ipv6Addr>().is_ok_and(|ip| {
+            let first = ip.segments()[0];
+            // Unique local fc00::/7 and link-local fe80::/10
+            first & 0xfe00 == 0xfc00 || first & 0xffc0 == 0xfe80
+        }),
+        _ => false,
+    }
+}
+
+/// Providers dialed ahead of a download.
+#[derive(Debug, Clone, Default)]
+pub struct ConnectedProviders {
+    pub peer_ids: Vec<String>,
+    /// The providers were reached over the local network only.
+    pub on_lan: bool,
+}
+
+/// Dials the providers of a dataset ahead of a download so the node's block
+/// exchange can spread its requests over all of them.
+///
+/// When some providers share our local network only those are dialed, on
+/// their local addresses, so the blocks come over the LAN instead of being
+/// spread over slower peers elsewhere. Providers that can't be dialed are
+/// skipped in favour of the next ones. The node decides which peer serves
+/// each block, so there is no per-provider accounting here.
+pub async fn connect_to_providers<B: StorageBackend>(
+    manager: &StorageManager<B>,
+    cid: &str,
+) -> ConnectedProviders {
+    let Ok(node) = manager.get_node().await else {
+        return ConnectedProviders::default();
+    };
+    // Nothing to spread over, the node finds peers on its own
+    if !node.supports_provider_lookup() {
+        return ConnectedProviders::default();
+    }
+    let providers = match node.find_providers(cid, MAX_DOWNLOAD_PROVIDERS * 2).await {
+        Ok(providers) => providers,
+        Err(e) => {
+            eprintln!("Failed to look up providers of {}: {}", cid, e);
+            return ConnectedProviders::default();
+        }
+    };
+
+    let lan_providers: Vec<ProviderInfo> = providers
+        .iter()
+        .filter_map(|provider| {
+            let addresses: Vec<String> = provider
+                .addresses
+                .iter()
+                .filter(|address| is_lan_address(address))
+                .cloned()
+                .collect();
+            (!addresses.is_empty()).then(|| ProviderInfo {
+                peer_id: provider.peer_id.clone(),
+                addresses,
+            })
+        })
+        .collect();
+
+    let mut connected = dial_providers(manager, lan_providers).await;
+    if !connected.peer_ids.is_empty() {
+        connected.on_lan = true;
+        return connected;
+    }
+    dial_providers(manager, providers).await
+}
+
+async fn dial_providers<B: StorageBackend>(
+    manager: &StorageManager<B>,
+    providers: Vec<ProviderInfo>,
+) -> ConnectedProviders {
+    let mut connected = ConnectedProviders::default();
+    for provider in providers {
+        if connected.peer_ids.len() == MAX_DOWNLOAD_PROVIDERS {
+            break;
+        }
+        match manager
+            .connect_to_peer(provider.peer_id.clone(), provider.addresses)
+            .await
+        {
+            Ok(()) => connected.peer_ids.push(provider.peer_id),
+            Err(e) => eprintln!("Skipping provider {}: {}", provider.peer_id, e),
+        }
+    }
+    connected
+}
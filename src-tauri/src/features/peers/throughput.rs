@@ -0,0 +1,121 @@
+use serde::Serialize;
+use specta::Type;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+use crate::features::backend::StorageBackend;
+use crate::features::connection::get_storage_manager_with_handle;
+use crate::features::peers::find_local_peer;
+use crate::features::settings::get_settings_store_with_handle;
+use crate::features::shared::{staging_file, StorageError};
+use crate::features::storage::dataset_exists_locally;
+
+/// Bytes fetched when no sample size is given.
+const DEFAULT_SAMPLE_BYTES: usize = 8 * 1024 * 1024;
+/// A measurement ends here even if the sample isn't complete.
+const MEASUREMENT_TIMEOUT: Duration = Duration::from_secs(60);
+const SAMPLE_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct PeerThroughput {
+    pub peer_id: String,
+    pub cid: String,
+    pub bytes: usize,
+    pub duration_ms: u64,
+    pub bytes_per_sec: u64,
+    /// Whether the measurement stopped at the timeout before the sample
+    /// or the dataset was complete.
+    pub timed_out: bool,
+}
+
+/// Times fetching up to `sample_size` bytes of `cid`, a dataset the peer
+/// provides and this node doesn't hold, after dialing the peer.
+///
+/// The node picks the peers it fetches blocks from, so other providers of
+/// the same dataset may contribute; use a dataset only this peer holds for
+/// a clean measurement. The fetched blocks are dropped afterwards.
+pub async fn measure_throughput(
+    app_handle: &AppHandle,
+    peer_id: &str,
+    cid: &str,
+    sample_size: Option<usize>,
+) -> Result<PeerThroughput, StorageError> {
+    let sample_size = sample_size.unwrap_or(DEFAULT_SAMPLE_BYTES).max(1);
+    let manager = get_storage_manager_with_handle(Some(app_handle.clone())).await?;
+    manager.ensure_writable()?;
+    let node = manager.get_node().await?;
+    if !node.is_started() {
+        return Err(StorageError::NodeNotStarted);
+    }
+    // A stored dataset would be read from disk rather than the peer
+    if dataset_exists_locally(&node, cid).await? {
+        return Err(StorageError::Configuration(format!(
+            "{} is already stored locally, pick a dataset only the peer holds",
+            cid
+        )));
+    }
+
+    let settings = get_settings_store_with_handle(app_handle)
+        .await?
+        .get()
+        .await;
+    let mut addresses: Vec<String> = settings
+        .bookmarked_peers
+        .into_iter()
+        .filter(|peer| peer.peer_id == peer_id)
+        .flat_map(|peer| peer.addresses)
+        .collect();
+    if let Some(peer) = find_local_peer(peer_id).await {
+        addresses.extend(peer.addresses);
+    }
+    manager
+        .connect_to_peer(peer_id.to_string(), addresses)
+        .await?;
+
+    let probe_path = staging_file(app_handle).await?;
+    let fetched = Arc::new(AtomicUsize::new(0));
+    let fetched_clone = Arc::clone(&fetched);
+    let on_progress = Box::new(move |bytes: usize, _total: Option<usize>| {
+        fetched_clone.store(bytes, Ordering::SeqCst);
+    });
+
+    let started_at = Instant::now();
+    let download = node.download(cid, &probe_path, on_progress);
+    tokio::pin!(download);
+    let mut timed_out = false;
+    let result = loop {
+        tokio::select! {
+            result = &mut download => break result.map(|_| ()),
+            _ = tokio::time::sleep(SAMPLE_CHECK_INTERVAL) => {
+                if fetched.load(Ordering::SeqCst) >= sample_size {
+                    break Ok(());
+                }
+                if started_at.elapsed() >= MEASUREMENT_TIMEOUT {
+                    timed_out = true;
+                    break Ok(());
+                }
+            }
+        }
+    };
+    let elapsed = started_at.elapsed();
+    drop(download);
+
+    let _ = std::fs::remove_file(&probe_path);
+    if let Err(e) = node.delete(cid).await {
+        eprintln!("Failed to drop throughput probe {}: {}", cid, e);
+    }
+    result?;
+
+    let bytes = fetched.load(Ordering::SeqCst);
+    let duration_ms = elapsed.as_millis().max(1) as u64;
+    Ok(PeerThroughput {
+        peer_id: peer_id.to_string(),
+        cid: cid.to_string(),
+        bytes,
+        duration_ms,
+        bytes_per_sec: bytes as u64 * 1000 / duration_ms,
+        timed_out,
+    })
+}
@@ -0,0 +1,119 @@
+use crate::features::connection::STORAGE_MANAGER;
+use crate::features::queue::{
+    enqueue_download_unless_present, get_transfer_queue_with_handle, EnqueueDownloadResult,
+    TransferJob, TransferRequest,
+};
+use crate::features::shared::{map_storage_error, validate_user_path, CommandError};
+use crate::features::upload::ensure_file_upload_confirmed;
+use tauri::AppHandle;
+
+/// Queues an upload. Files above the configured size limit fail with
+/// `UploadConfirmationRequired` instead, confirm by calling again with its
+/// token.
+#[tauri::command]
+#[specta::specta]
+pub async fn enqueue_upload(
+    file_path: String,
+    ttl_secs: Option<u64>,
+    confirmation_token: Option<String>,
+    app_handle: AppHandle,
+) -> Result<String, CommandError> {
+    let file_path = validate_user_path(&app_handle, &file_path).map_err(map_storage_error)?;
+    ensure_file_upload_confirmed(&app_handle, &file_path, confirmation_token.as_deref())
+        .await
+        .map_err(map_storage_error)?;
+    let queue = get_transfer_queue_with_handle(&app_handle)
+        .await
+        .map_err(map_storage_error)?;
+    queue
+        .enqueue(TransferRequest::Upload {
+            file_path: file_path.to_string_lossy().to_string(),
+            ttl_secs,
+        })
+        .await
+        .map_err(map_storage_error)
+}
+
+/// Queues a download, or reports where the CID already exists unless `force` is set.
+#[tauri::command]
+#[specta::specta]
+pub async fn enqueue_download(
+    cid: String,
+    save_path: String,
+    force: Option<bool>,
+    app_handle: AppHandle,
+) -> Result<EnqueueDownloadResult, CommandError> {
+    let save_path = validate_user_path(&app_handle, &save_path).map_err(map_storage_error)?;
+    enqueue_download_unless_present(
+        &app_handle,
+        cid,
+        save_path.to_string_lossy().to_string(),
+        force.unwrap_or(false),
+    )
+    .await
+    .map_err(map_storage_error)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_transfer_queue(app_handle: AppHandle) -> Result<Vec<TransferJob>, CommandError> {
+    let queue = get_transfer_queue_with_handle(&app_handle)
+        .await
+        .map_err(map_storage_error)?;
+    Ok(queue.list().await)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn remove_queued_transfer(
+    operation_id: String,
+    app_handle: AppHandle,
+) -> Result<bool, CommandError> {
+    let queue = get_transfer_queue_with_handle(&app_handle)
+        .await
+        .map_err(map_storage_error)?;
+    queue.remove(&operation_id).await.map_err(map_storage_error)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn force_start(
+    operation_id: String,
+    app_handle: AppHandle,
+) -> Result<bool, CommandError> {
+    let queue = get_transfer_queue_with_handle(&app_handle)
+        .await
+        .map_err(map_storage_error)?;
+    queue
+        .force_start(&operation_id)
+        .await
+        .map_err(map_storage_error)
+}
+
+/// Stops running transfers and holds back the queue until `resume_all_transfers`.
+///
+/// Interrupted queued transfers start over once resumed, direct transfers
+/// are cancelled.
+#[tauri::command]
+#[specta::specta]
+pub async fn pause_all_transfers(app_handle: AppHandle) -> Result<usize, CommandError> {
+    let queue = get_transfer_queue_with_handle(&app_handle)
+        .await
+        .map_err(map_storage_error)?;
+    queue.set_paused_by_user(true);
+
+    Ok(match STORAGE_MANAGER.get() {
+        Some(manager) => manager.cancel_all_operations().await,
+        None => 0,
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn resume_all_transfers(app_handle: AppHandle) -> Result<(), CommandError> {
+    let queue = get_transfer_queue_with_handle(&app_handle)
+        .await
+        .map_err(map_storage_error)?;
+    queue.set_paused_by_user(false);
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::Path;
+use tauri::AppHandle;
+
+use crate::features::backend::StorageBackend;
+use crate::features::catalog::get_catalog_with_handle;
+use crate::features::connection::get_storage_manager_with_handle;
+use crate::features::queue::{get_transfer_queue_with_handle, TransferRequest};
+use crate::features::shared::StorageError;
+
+/// Where a copy of a dataset already exists on this machine.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+pub enum ExistingCopy {
+    /// A queued download of the same CID.
+    Queued { operation_id: String },
+    /// A file previously uploaded from or downloaded to this path.
+    File { path: String },
+    /// The blocks are in the node's repo, downloading only reads them back.
+    Repo,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub enum EnqueueDownloadResult {
+    Queued { operation_id: String },
+    AlreadyPresent(ExistingCopy),
+}
+
+/// Looks for the CID in the transfer queue, the catalog's known files and
+/// the node's repo, in that order.
+pub async fn find_existing_copy(
+    app_handle: &AppHandle,
+    cid: &str,
+) -> Result<Option<ExistingCopy>, StorageError> {
+    let queue = get_transfer_queue_with_handle(app_handle).await?;
+    let queued = queue.list().await.into_iter().find(|job| {
+        matches!(&job.request, TransferRequest::Download { cid: queued_cid, .. } if queued_cid == cid)
+    });
+    if let Some(job) = queued {
+        return Ok(Some(ExistingCopy::Queued {
+            operation_id: job.operation_id,
+        }));
+    }
+
+    let catalog = get_catalog_with_handle(app_handle).await?;
+    let local_path = catalog
+        .get(cid)
+        .await
+        .and_then(|entry| entry.local_path)
+        .filter(|path| Path::new(path).is_file());
+    if let Some(path) = local_path {
+        return Ok(Some(ExistingCopy::File { path }));
+    }
+
+    let manager = get_storage_manager_with_handle(Some(app_handle.clone())).await?;
+    if let Ok(node) = manager.get_node().await {
+        if node.is_started() && node.exists(cid).await.unwrap_or(false) {
+            return Ok(Some(ExistingCopy::Repo));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Queues a download unless a copy already exists, or always with `force`.
+pub async fn enqueue_download_unless_present(
+    app_handle: &AppHandle,
+    cid: String,
+    save_path: String,
+    force: bool,
+) -> Result<EnqueueDownloadResult, StorageError> {
+    if !force {
+        if let Some(existing) = find_existing_copy(app_handle, &cid).await? {
+            return Ok(EnqueueDownloadResult::AlreadyPresent(existing));
+        }
+    }
+
+    let queue = get_transfer_queue_with_handle(app_handle).await?;
+    let operation_id = queue
+        .enqueue(TransferRequest::Download { cid, save_path })
+        .await?;
+    Ok(EnqueueDownloadResult::Queued { operation_id })
+}
@@ -0,0 +1,9 @@
+pub mod commands;
+pub mod duplicates;
+pub mod queue;
+pub mod summary;
+
+pub use commands::*;
+pub use duplicates::*;
+pub use queue::*;
+pub use summary::*;
@@ -0,0 +1,324 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::AppHandle;
+use tokio::sync::{Mutex, Notify, OnceCell};
+
+use crate::features::connection::get_storage_manager_with_handle;
+use crate::features::download::download_file_with_progress;
+use crate::features::settings::get_settings_store_with_handle;
+use crate::features::shared::{
+    app_data_file, read_json_file, write_json_file, StorageConnectionStatus, StorageError,
+};
+use crate::features::system::{active_battery_policy, active_metered_policy, held_back_by};
+use crate::features::upload::upload_file_with_progress;
+
+pub const QUEUE_FILE_NAME: &str = "transfer_queue.json";
+const NODE_WAIT_INTERVAL: Duration = Duration::from_secs(2);
+const WINDOW_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+/// Times a stalled queued transfer is started again before it is dropped.
+const MAX_STALL_RETRIES: u32 = 2;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub enum TransferRequest {
+    Upload {
+        file_path: String,
+        /// Seconds until the local copy expires after the upload.
+        #[serde(default)]
+        ttl_secs: Option<u64>,
+    },
+    Download {
+        cid: String,
+        save_path: String,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct TransferJob {
+    pub operation_id: String,
+    pub request: TransferRequest,
+    pub queued_at: u64,
+    /// Set by `force_start` to let the job run outside the transfer window.
+    #[serde(default)]
+    pub forced: bool,
+}
+
+impl TransferJob {
+    pub fn new(request: TransferRequest) -> Self {
+        let queued_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        Self {
+            operation_id: uuid::Uuid::new_v4().to_string(),
+            request,
+            queued_at,
+            forced: false,
+        }
+    }
+}
+
+/// Transfer jobs waiting to run, mirrored to disk so they survive restarts.
+///
+/// A job stays in the queue (and in the queue file) until it has finished,
+/// so a transfer interrupted by a crash or quit is picked up again on the
+/// next launch.
+pub struct TransferQueue {
+    jobs: Arc<Mutex<VecDeque<TransferJob>>>,
+    queue_file: PathBuf,
+    notify: Arc<Notify>,
+    paused: AtomicBool,
+    paused_by_user: AtomicBool,
+}
+
+impl TransferQueue {
+    pub fn load(queue_file: PathBuf) -> Self {
+        let jobs: VecDeque<TransferJob> = read_json_file(&queue_file).unwrap_or_default();
+
+        if !jobs.is_empty() {
+            println!("Restored {} queued transfer(s) from disk", jobs.len());
+        }
+
+        Self {
+            jobs: Arc::new(Mutex::new(jobs)),
+            queue_file,
+            notify: Arc::new(Notify::new()),
+            paused: AtomicBool::new(false),
+            paused_by_user: AtomicBool::new(false),
+        }
+    }
+
+    pub async fn enqueue(&self, request: TransferRequest) -> Result<String, StorageError> {
+        let job = TransferJob::new(request);
+        let operation_id = job.operation_id.clone();
+
+        {
+            let mut jobs = self.jobs.lock().await;
+            jobs.push_back(job);
+            self.persist(&jobs)?;
+        }
+
+        self.notify.notify_one();
+        Ok(operation_id)
+    }
+
+    pub async fn remove(&self, operation_id: &str) -> Result<bool, StorageError> {
+        let mut jobs = self.jobs.lock().await;
+        let before = jobs.len();
+        jobs.retain(|job| job.operation_id != operation_id);
+        let removed = jobs.len() != before;
+        if removed {
+            self.persist(&jobs)?;
+        }
+        Ok(removed)
+    }
+
+    /// Moves a job to the front of the queue and lets it bypass the transfer window.
+    pub async fn force_start(&self, operation_id: &str) -> Result<bool, StorageError> {
+        {
+            let mut jobs = self.jobs.lock().await;
+            let Some(index) = jobs.iter().position(|job| job.operation_id == operation_id) else {
+                return Ok(false);
+            };
+            if let Some(mut job) = jobs.remove(index) {
+                job.forced = true;
+                jobs.push_front(job);
+            }
+            self.persist(&jobs)?;
+        }
+
+        self.notify.notify_one();
+        Ok(true)
+    }
+
+    /// Holds back queued jobs, e.g. while the system is suspended.
+    ///
+    /// Transfers that are already running are not interrupted.
+    pub fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::SeqCst);
+        if !paused {
+            self.notify.notify_one();
+        }
+    }
+
+    /// Like `set_paused`, but only lifted by the user, not by system events.
+    pub fn set_paused_by_user(&self, paused: bool) {
+        self.paused_by_user.store(paused, Ordering::SeqCst);
+        if !paused {
+            self.notify.notify_one();
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst) || self.paused_by_user.load(Ordering::SeqCst)
+    }
+
+    pub async fn list(&self) -> Vec<TransferJob> {
+        self.jobs.lock().await.iter().cloned().collect()
+    }
+
+    /// Waits until a job is available and returns it without removing it.
+    pub async fn next_job(&self) -> TransferJob {
+        loop {
+            let notified = self.notify.notified();
+            if let Some(job) = self.jobs.lock().await.front().cloned() {
+                return job;
+            }
+            notified.await;
+        }
+    }
+
+    /// Sleeps until the queue changes or the timeout elapses.
+    pub async fn wait_for_change(&self, timeout: Duration) {
+        let _ = tokio::time::timeout(timeout, self.notify.notified()).await;
+    }
+
+    fn persist(&self, jobs: &VecDeque<TransferJob>) -> Result<(), StorageError> {
+        write_json_file(&self.queue_file, jobs)
+    }
+}
+
+// Global queue instance
+pub static TRANSFER_QUEUE: OnceCell<Arc<TransferQueue>> = OnceCell::const_new();
+
+pub async fn get_transfer_queue_with_handle(
+    app_handle: &AppHandle,
+) -> Result<Arc<TransferQueue>, StorageError> {
+    TRANSFER_QUEUE
+        .get_or_try_init(|| async {
+            let queue_file = app_data_file(app_handle, QUEUE_FILE_NAME)?;
+            Ok(Arc::new(TransferQueue::load(queue_file)))
+        })
+        .await
+        .map(Arc::clone)
+}
+
+/// Runs queued transfers one at a time for as long as the app is running.
+pub async fn run_transfer_queue(app_handle: AppHandle) {
+    let queue = match get_transfer_queue_with_handle(&app_handle).await {
+        Ok(queue) => queue,
+        Err(e) => {
+            eprintln!("Failed to load transfer queue: {}", e);
+            return;
+        }
+    };
+
+    // Stall retries per job, reset when the app restarts
+    let mut stall_retries: HashMap<String, u32> = HashMap::new();
+
+    loop {
+        let job = queue.next_job().await;
+
+        if queue.is_paused() {
+            queue.wait_for_change(NODE_WAIT_INTERVAL).await;
+            continue;
+        }
+
+        // Outside the configured transfer window only forced jobs may start
+        if !job.forced {
+            let allowed = match get_settings_store_with_handle(&app_handle).await {
+                Ok(store) => store.get().await.transfers_allowed_now(),
+                Err(_) => true,
+            };
+            if !allowed {
+                queue.wait_for_change(WINDOW_CHECK_INTERVAL).await;
+                continue;
+            }
+
+            // Large transfers wait for an unmetered connection
+            if let Some(policy) = active_metered_policy(&app_handle).await {
+                if held_back_by(&policy, &job.request) {
+                    queue.wait_for_change(WINDOW_CHECK_INTERVAL).await;
+                    continue;
+                }
+            }
+
+            // Uploads wait for the charger when the battery runs low
+            let upload = matches!(job.request, TransferRequest::Upload { .. });
+            if upload
+                && active_battery_policy(&app_handle)
+                    .await
+                    .is_some_and(|policy| policy.pause_uploads)
+            {
+                queue.wait_for_change(WINDOW_CHECK_INTERVAL).await;
+                continue;
+            }
+        }
+
+        // Queued transfers only make progress while the node is running
+        let connected = match get_storage_manager_with_handle(Some(app_handle.clone())).await {
+            Ok(manager) => manager.get_status().await == StorageConnectionStatus::Connected,
+            Err(_) => false,
+        };
+        if !connected {
+            tokio::time::sleep(NODE_WAIT_INTERVAL).await;
+            continue;
+        }
+
+        let result = match job.request.clone() {
+            TransferRequest::Upload {
+                file_path,
+                ttl_secs,
+            } => upload_file_with_progress(
+                job.operation_id.clone(),
+                file_path.into(),
+                None,
+                ttl_secs,
+                app_handle.clone(),
+            )
+            .await
+            .map(|_| ()),
+            TransferRequest::Download { cid, save_path } => download_file_with_progress(
+                job.operation_id.clone(),
+                cid,
+                save_path.into(),
+                app_handle.clone(),
+            )
+            .await
+            .map(|_| ()),
+        };
+
+        match result {
+            Ok(()) => {}
+            Err(StorageError::Cancelled) => {
+                let by_user = match get_storage_manager_with_handle(Some(app_handle.clone())).await
+                {
+                    Ok(manager) => manager.take_user_cancel(&job.operation_id).await,
+                    Err(_) => false,
+                };
+                if !by_user {
+                    // Interrupted by pausing or the node stopping, run it
+                    // again once the queue can go on
+                    tokio::time::sleep(NODE_WAIT_INTERVAL).await;
+                    continue;
+                }
+                println!("Queued transfer {} cancelled", job.operation_id);
+            }
+            Err(StorageError::Stalled(_))
+                if stall_retries.get(&job.operation_id).copied().unwrap_or(0)
+                    < MAX_STALL_RETRIES =>
+            {
+                // Dialing again may turn up peers that still have the data
+                *stall_retries.entry(job.operation_id.clone()).or_insert(0) += 1;
+                eprintln!("Queued transfer {} stalled, retrying", job.operation_id);
+                continue;
+            }
+            Err(StorageError::NodeNotInitialized) | Err(StorageError::NodeNotStarted) => {
+                // The node went away underneath us, keep the job for later
+                tokio::time::sleep(NODE_WAIT_INTERVAL).await;
+                continue;
+            }
+            Err(e) => eprintln!("Queued transfer {} failed: {}", job.operation_id, e),
+        }
+
+        stall_retries.remove(&job.operation_id);
+        if let Err(e) = queue.remove(&job.operation_id).await {
+            eprintln!("Failed to update transfer queue: {}", e);
+        }
+    }
+}
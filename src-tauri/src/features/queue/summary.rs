@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::features::connection::get_storage_manager_with_handle;
+use crate::features::events::{emit_event, TransferSummary, TRANSFER_SUMMARY_EVENT};
+use crate::features::queue::get_transfer_queue_with_handle;
+use crate::features::shared::{OperationStage, ProgressMessage};
+
+const SUMMARY_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Bytes moved since the last summary, from each transfer's progress.
+#[derive(Default)]
+struct Throughput {
+    last_bytes: HashMap<String, usize>,
+    uploaded: u64,
+    downloaded: u64,
+}
+
+impl Throughput {
+    fn update(&mut self, progress: &ProgressMessage) {
+        // Estimates run ahead of the node's reports, which alone count
+        if progress.estimated {
+            return;
+        }
+        let counter = match progress.stage {
+            OperationStage::Uploading => &mut self.uploaded,
+            OperationStage::Downloading => &mut self.downloaded,
+            OperationStage::Completed | OperationStage::Failed(_) => {
+                self.last_bytes.remove(&progress.operation_id);
+                return;
+            }
+            _ => return,
+        };
+        let last = self
+            .last_bytes
+            .insert(progress.operation_id.clone(), progress.bytes_processed)
+            .unwrap_or(0);
+        *counter += progress.bytes_processed.saturating_sub(last) as u64;
+    }
+
+    /// Rates since the last call, resetting the counters.
+    fn take_rates(&mut self, elapsed: Duration) -> (u64, u64) {
+        let seconds = elapsed.as_secs_f64().max(f64::EPSILON);
+        let rates = (
+            (self.uploaded as f64 / seconds) as u64,
+            (self.downloaded as f64 / seconds) as u64,
+        );
+        self.uploaded = 0;
+        self.downloaded = 0;
+        rates
+    }
+}
+
+/// Emits a summary of running transfers every second, so the status bar
+/// doesn't have to poll several commands.
+///
+/// Stays quiet while nothing is transferring, after one idle summary.
+pub async fn run_transfer_summary(app_handle: AppHandle) {
+    let (manager, queue) = match (
+        get_storage_manager_with_handle(Some(app_handle.clone())).await,
+        get_transfer_queue_with_handle(&app_handle).await,
+    ) {
+        (Ok(manager), Ok(queue)) => (manager, queue),
+        (Err(e), _) | (_, Err(e)) => {
+            eprintln!("Transfer summary unavailable: {}", e);
+            return;
+        }
+    };
+
+    let mut progress_events = manager.subscribe_progress();
+    let mut throughput = Throughput::default();
+    let mut last_summary = None;
+    let mut last_tick = Instant::now();
+    let mut ticker = tokio::time::interval(SUMMARY_INTERVAL);
+
+    loop {
+        tokio::select! {
+            event = progress_events.recv() => match event {
+                Ok(progress) => throughput.update(&progress),
+                // Missed bytes show up in the next update of the transfer
+                Err(RecvError::Lagged(_)) => {}
+                Err(RecvError::Closed) => break,
+            },
+            _ = ticker.tick() => {
+                let (upload_rate, download_rate) = throughput.take_rates(last_tick.elapsed());
+                last_tick = Instant::now();
+                let summary = TransferSummary {
+                    active_transfers: manager.running_operation_count().await,
+                    upload_rate,
+                    download_rate,
+                    queue_depth: queue.list().await.len(),
+                    queue_paused: queue.is_paused(),
+                };
+
+                let idle = summary.active_transfers == 0 && summary.queue_depth == 0;
+                if idle && last_summary.as_ref() == Some(&summary) {
+                    continue;
+                }
+                let _ = emit_event(&app_handle, TRANSFER_SUMMARY_EVENT, summary.clone());
+                last_summary = Some(summary);
+            }
+        }
+    }
+}
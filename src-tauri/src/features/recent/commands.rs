@@ -0,0 +1,44 @@
+use crate::features::catalog::get_catalog_with_handle;
+use crate::features::recent::{
+    get_recent_items_with_handle, record_recent_item, RecentAction, RecentItem,
+};
+use crate::features::shared::{map_storage_error, CommandError};
+use tauri::AppHandle;
+
+const DEFAULT_RECENT_LIMIT: usize = 10;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_recent_items(
+    limit: Option<usize>,
+    app_handle: AppHandle,
+) -> Result<Vec<RecentItem>, CommandError> {
+    let recent = get_recent_items_with_handle(&app_handle)
+        .await
+        .map_err(map_storage_error)?;
+    Ok(recent.list(limit.unwrap_or(DEFAULT_RECENT_LIMIT)).await)
+}
+
+/// Lets the frontend note that the user opened a downloaded file.
+#[tauri::command]
+#[specta::specta]
+pub async fn mark_item_opened(
+    cid: String,
+    path: Option<String>,
+    app_handle: AppHandle,
+) -> Result<(), CommandError> {
+    record_recent_item(&app_handle, &cid, RecentAction::Opened, path).await;
+
+    let catalog = get_catalog_with_handle(&app_handle)
+        .await
+        .map_err(map_storage_error)?;
+    if catalog.get(&cid).await.is_some() {
+        catalog
+            .upsert(&cid, |entry| {
+                entry.last_accessed_at = Some(chrono::Utc::now().timestamp_millis())
+            })
+            .await
+            .map_err(map_storage_error)?;
+    }
+    Ok(())
+}
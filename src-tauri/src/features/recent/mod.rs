@@ -0,0 +1,5 @@
+pub mod commands;
+pub mod recent;
+
+pub use commands::*;
+pub use recent::*;
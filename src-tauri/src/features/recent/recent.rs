@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tauri::AppHandle;
+use tokio::sync::{Mutex, OnceCell};
+
+use crate::features::shared::{app_data_file, read_json_file, write_json_file, StorageError};
+
+pub const RECENT_FILE_NAME: &str = "recent_items.json";
+const MAX_RECENT_ITEMS: usize = 50;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+pub enum RecentAction {
+    Uploaded,
+    Downloaded,
+    Opened,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct RecentItem {
+    pub cid: String,
+    pub action: RecentAction,
+    /// Local file involved, if any.
+    pub path: Option<String>,
+    /// Milliseconds since the Unix epoch.
+    pub used_at: i64,
+}
+
+/// Most recently used CIDs, newest first, bounded to `MAX_RECENT_ITEMS`.
+///
+/// Each CID appears once, with its latest action.
+pub struct RecentItems {
+    items: Mutex<VecDeque<RecentItem>>,
+    recent_file: PathBuf,
+}
+
+impl RecentItems {
+    pub fn load(recent_file: PathBuf) -> Self {
+        let items = read_json_file(&recent_file).unwrap_or_default();
+        Self {
+            items: Mutex::new(items),
+            recent_file,
+        }
+    }
+
+    pub async fn record(
+        &self,
+        cid: &str,
+        action: RecentAction,
+        path: Option<String>,
+    ) -> Result<(), StorageError> {
+        let mut items = self.items.lock().await;
+        items.retain(|item| item.cid != cid);
+        items.push_front(RecentItem {
+            cid: cid.to_string(),
+            action,
+            path,
+            used_at: chrono::Utc::now().timestamp_millis(),
+        });
+        items.truncate(MAX_RECENT_ITEMS);
+
+        write_json_file(&self.recent_file, &*items)
+    }
+
+    pub async fn list(&self, limit: usize) -> Vec<RecentItem> {
+        self.items
+            .lock()
+            .await
+            .iter()
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+}
+
+// Global recent items instance
+pub static RECENT_ITEMS: OnceCell<Arc<RecentItems>> = OnceCell::const_new();
+
+pub async fn get_recent_items_with_handle(
+    app_handle: &AppHandle,
+) -> Result<Arc<RecentItems>, StorageError> {
+    RECENT_ITEMS
+        .get_or_try_init(|| async {
+            let recent_file = app_data_file(app_handle, RECENT_FILE_NAME)?;
+            Ok(Arc::new(RecentItems::load(recent_file)))
+        })
+        .await
+        .map(Arc::clone)
+}
+
+/// Best effort, a failure to remember an item shouldn't fail the transfer.
+pub async fn record_recent_item(
+    app_handle: &AppHandle,
+    cid: &str,
+    action: RecentAction,
+    path: Option<String>,
+) {
+    let result = match get_recent_items_with_handle(app_handle).await {
+        Ok(recent) => recent.record(cid, action, path).await,
+        Err(e) => Err(e),
+    };
+    if let Err(e) = result {
+        eprintln!("Failed to record recent item {}: {}", cid, e);
+    }
+}
@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::AppHandle;
+
+use crate::features::rpc::load_or_create_token;
+use crate::features::settings::get_settings_store_with_handle;
+use crate::features::shared::{map_storage_error, CommandError};
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct RpcApiInfo {
+    pub enabled: bool,
+    pub url: Option<String>,
+    pub token: Option<String>,
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_rpc_api_info(app_handle: AppHandle) -> Result<RpcApiInfo, CommandError> {
+    let settings = get_settings_store_with_handle(&app_handle)
+        .await
+        .map_err(map_storage_error)?
+        .get()
+        .await;
+
+    let Some(rpc_api) = settings.rpc_api else {
+        return Ok(RpcApiInfo {
+            enabled: false,
+            url: None,
+            token: None,
+        });
+    };
+
+    let token = load_or_create_token(&app_handle).map_err(map_storage_error)?;
+    Ok(RpcApiInfo {
+        enabled: true,
+        url: Some(format!("http://127.0.0.1:{}/rpc", rpc_api.port)),
+        token: Some(token),
+    })
+}
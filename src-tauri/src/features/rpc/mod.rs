@@ -0,0 +1,5 @@
+pub mod commands;
+pub mod server;
+
+pub use commands::*;
+pub use server::*;
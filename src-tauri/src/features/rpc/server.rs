@@ -0,0 +1,307 @@
+use axum::extract::State;
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::routing::post;
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::io::Write;
+use std::path::Path;
+use tauri::AppHandle;
+
+use crate::features::connection::get_storage_manager_with_handle;
+use crate::features::download::download_file_with_progress;
+use crate::features::queue::{enqueue_download_unless_present, get_transfer_queue_with_handle};
+use crate::features::settings::get_settings_store_with_handle;
+use crate::features::shared::{app_data_file, StorageError};
+use crate::features::upload::{ensure_file_upload_confirmed, upload_file_with_progress};
+
+const TOKEN_FILE_NAME: &str = "rpc_token";
+
+// JSON-RPC 2.0 error codes
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const SERVER_ERROR: i64 = -32000;
+
+#[derive(Debug, Deserialize)]
+pub struct RpcRequest {
+    #[serde(default)]
+    pub id: Value,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+    /// The `StorageError` behind a server error, e.g. to get the token of
+    /// an upload waiting for confirmation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RpcResponse {
+    pub jsonrpc: &'static str,
+    pub id: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcError>,
+}
+
+impl RpcResponse {
+    fn new(id: Value, outcome: Result<Value, RpcError>) -> Self {
+        let (result, error) = match outcome {
+            Ok(result) => (Some(result), None),
+            Err(error) => (None, Some(error)),
+        };
+        Self {
+            jsonrpc: "2.0",
+            id,
+            result,
+            error,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct UploadParams {
+    file_path: String,
+    #[serde(default)]
+    ttl_secs: Option<u64>,
+    #[serde(default)]
+    confirmation_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DownloadParams {
+    cid: String,
+    save_path: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct EnqueueDownloadParams {
+    cid: String,
+    save_path: String,
+    #[serde(default)]
+    force: bool,
+}
+
+#[derive(Clone)]
+struct RpcState {
+    app_handle: AppHandle,
+    token: String,
+}
+
+/// Loads the API token, generating one on first use.
+pub fn load_or_create_token(app_handle: &AppHandle) -> Result<String, StorageError> {
+    let token_file = app_data_file(app_handle, TOKEN_FILE_NAME)?;
+    if let Ok(token) = std::fs::read_to_string(&token_file) {
+        let token = token.trim().to_string();
+        if !token.is_empty() {
+            return Ok(token);
+        }
+    }
+
+    let token = format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    );
+    // Created with owner-only permissions, so the token is never readable
+    // by other users, not even between creating and restricting the file
+    let _ = std::fs::remove_file(&token_file);
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options
+        .open(&token_file)
+        .and_then(|mut file| file.write_all(token.as_bytes()))
+        .map_err(|e| StorageError::Io(e.to_string()))?;
+
+    Ok(token)
+}
+
+fn is_authorized(headers: &HeaderMap, token: &str) -> bool {
+    let Some(provided) = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+    else {
+        return false;
+    };
+
+    // Compare without short-circuiting to avoid leaking the token through timing
+    provided.len() == token.len()
+        && provided
+            .bytes()
+            .zip(token.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+fn params<T: serde::de::DeserializeOwned>(params: Value) -> Result<T, RpcError> {
+    serde_json::from_value(params).map_err(|e| RpcError {
+        code: INVALID_PARAMS,
+        message: e.to_string(),
+        data: None,
+    })
+}
+
+fn to_value<T: Serialize>(value: T) -> Result<Value, RpcError> {
+    serde_json::to_value(value).map_err(|e| RpcError {
+        code: SERVER_ERROR,
+        message: e.to_string(),
+        data: None,
+    })
+}
+
+fn server_error(err: StorageError) -> RpcError {
+    RpcError {
+        code: SERVER_ERROR,
+        message: err.to_string(),
+        data: serde_json::to_value(&err).ok(),
+    }
+}
+
+/// The curated set of operations available to external scripts.
+async fn dispatch(app_handle: &AppHandle, request: RpcRequest) -> Result<Value, RpcError> {
+    match request.method.as_str() {
+        "status" => {
+            let manager = get_storage_manager_with_handle(Some(app_handle.clone()))
+                .await
+                .map_err(server_error)?;
+            to_value(manager.get_status().await)
+        }
+        "node_info" => {
+            let manager = get_storage_manager_with_handle(Some(app_handle.clone()))
+                .await
+                .map_err(server_error)?;
+            to_value(manager.get_node_info().await.map_err(server_error)?)
+        }
+        "upload" => {
+            let UploadParams {
+                file_path,
+                ttl_secs,
+                confirmation_token,
+            } = params(request.params)?;
+            ensure_file_upload_confirmed(
+                app_handle,
+                Path::new(&file_path),
+                confirmation_token.as_deref(),
+            )
+            .await
+            .map_err(server_error)?;
+            let result = upload_file_with_progress(
+                uuid::Uuid::new_v4().to_string(),
+                file_path.into(),
+                None,
+                ttl_secs,
+                app_handle.clone(),
+            )
+            .await
+            .map_err(server_error)?;
+            to_value(result)
+        }
+        "download" => {
+            let DownloadParams { cid, save_path } = params(request.params)?;
+            let result = download_file_with_progress(
+                uuid::Uuid::new_v4().to_string(),
+                cid,
+                save_path.into(),
+                app_handle.clone(),
+            )
+            .await
+            .map_err(server_error)?;
+            to_value(result)
+        }
+        "enqueue_download" => {
+            let EnqueueDownloadParams {
+                cid,
+                save_path,
+                force,
+            } = params(request.params)?;
+            let result = enqueue_download_unless_present(app_handle, cid, save_path, force)
+                .await
+                .map_err(server_error)?;
+            to_value(result)
+        }
+        "transfer_queue" => {
+            let queue = get_transfer_queue_with_handle(app_handle)
+                .await
+                .map_err(server_error)?;
+            to_value(queue.list().await)
+        }
+        method => Err(RpcError {
+            code: METHOD_NOT_FOUND,
+            message: format!("Unknown method: {}", method),
+            data: None,
+        }),
+    }
+}
+
+async fn handle_rpc(
+    State(state): State<RpcState>,
+    headers: HeaderMap,
+    body: String,
+) -> Result<Json<RpcResponse>, StatusCode> {
+    if !is_authorized(&headers, &state.token) {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
+    let request: RpcRequest = match serde_json::from_str(&body) {
+        Ok(request) => request,
+        Err(e) => {
+            return Ok(Json(RpcResponse::new(
+                Value::Null,
+                Err(RpcError {
+                    code: PARSE_ERROR,
+                    message: e.to_string(),
+                    data: None,
+                }),
+            )));
+        }
+    };
+
+    let id = request.id.clone();
+    let outcome = dispatch(&state.app_handle, request).await;
+    Ok(Json(RpcResponse::new(id, outcome)))
+}
+
+/// Serves the JSON-RPC API on the loopback interface until the app exits.
+pub async fn serve_rpc_api(app_handle: AppHandle, port: u16) -> Result<(), StorageError> {
+    let token = load_or_create_token(&app_handle)?;
+    let router = Router::new()
+        .route("/rpc", post(handle_rpc))
+        .with_state(RpcState { app_handle, token });
+
+    let listener = tokio::net::TcpListener::bind(("127.0.0.1", port))
+        .await
+        .map_err(|e| StorageError::Io(e.to_string()))?;
+    println!("JSON-RPC API listening on http://127.0.0.1:{}/rpc", port);
+
+    axum::serve(listener, router)
+        .await
+        .map_err(|e| StorageError::Io(e.to_string()))
+}
+
+/// Starts the JSON-RPC API if it is enabled in the settings.
+pub async fn run_rpc_api(app_handle: AppHandle) {
+    let rpc_api = match get_settings_store_with_handle(&app_handle).await {
+        Ok(store) => store.get().await.rpc_api,
+        Err(_) => None,
+    };
+
+    if let Some(rpc_api) = rpc_api {
+        if let Err(e) = serve_rpc_api(app_handle, rpc_api.port).await {
+            eprintln!("JSON-RPC API stopped: {}", e);
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use crate::features::connection::STORAGE_MANAGER;
+use crate::features::download::allow_downloads_dir;
+use crate::features::settings::{get_settings_store_with_handle, AppSettings};
+use crate::features::shared::{
+    map_storage_error, set_current_locale, validate_user_path, CommandError, Locale,
+};
+use tauri::AppHandle;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_settings(app_handle: AppHandle) -> Result<AppSettings, CommandError> {
+    let store = get_settings_store_with_handle(&app_handle)
+        .await
+        .map_err(map_storage_error)?;
+    Ok(store.get().await)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn update_settings(
+    settings: AppSettings,
+    app_handle: AppHandle,
+) -> Result<AppSettings, CommandError> {
+    let store = get_settings_store_with_handle(&app_handle)
+        .await
+        .map_err(map_storage_error)?;
+    // A new downloads directory gets added to the fs scope below, so it has
+    // to be somewhere already allowed: picked in a dialog or inside the
+    // app's own directories
+    if let Some(dir) = &settings.downloads_dir {
+        if store.get().await.downloads_dir.as_ref() != Some(dir) {
+            validate_user_path(&app_handle, dir).map_err(map_storage_error)?;
+        }
+    }
+    let settings = store.update(settings).await.map_err(map_storage_error)?;
+
+    // The downloads directory may have moved
+    allow_downloads_dir(&app_handle, &settings);
+    set_current_locale(settings.locale.unwrap_or_default());
+    if let Some(manager) = STORAGE_MANAGER.get() {
+        manager.set_read_only(settings.read_only);
+        manager.set_download_only(settings.download_only);
+        manager.set_stall_timeout(settings.stall_timeout_secs);
+    }
+    Ok(settings)
+}
+
+/// Switches the language of messages produced by the backend.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_locale(lang: String, app_handle: AppHandle) -> Result<Locale, CommandError> {
+    let locale = Locale::from_tag(&lang).map_err(map_storage_error)?;
+    let store = get_settings_store_with_handle(&app_handle)
+        .await
+        .map_err(map_storage_error)?;
+    let mut settings = store.get().await;
+    settings.locale = Some(locale);
+    store.update(settings).await.map_err(map_storage_error)?;
+
+    set_current_locale(locale);
+    Ok(locale)
+}
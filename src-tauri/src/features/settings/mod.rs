@@ -0,0 +1,7 @@
+pub mod commands;
+pub mod settings;
+pub mod window_state;
+
+pub use commands::*;
+pub use settings::*;
+pub use window_state::*;
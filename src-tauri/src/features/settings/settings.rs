@@ -0,0 +1,634 @@
+use chrono::Timelike;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tauri::AppHandle;
+use tokio::sync::{OnceCell, RwLock};
+
+use crate::features::download::{ChecksumAlgorithm, DownloadVerification};
+use crate::features::settings::WindowGeometry;
+use crate::features::shared::{
+    app_data_file, read_json_file, validate_directory_setting, write_json_file, Locale,
+    StorageError,
+};
+
+pub const SETTINGS_FILE_NAME: &str = "settings.json";
+const DEFAULT_DOWNLOADS_DIR_NAME: &str = "Storage Downloads";
+
+/// Hours of the day (local time) during which queued transfers may run.
+///
+/// The window wraps around midnight when `start_hour` is greater than
+/// `end_hour`, e.g. 22 → 6.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+pub struct TransferWindow {
+    pub start_hour: u32,
+    pub end_hour: u32,
+}
+
+impl TransferWindow {
+    pub fn contains(&self, hour: u32) -> bool {
+        if self.start_hour <= self.end_hour {
+            hour >= self.start_hour && hour < self.end_hour
+        } else {
+            hour >= self.start_hour || hour < self.end_hour
+        }
+    }
+
+    pub fn is_open_now(&self) -> bool {
+        self.contains(chrono::Local::now().hour())
+    }
+}
+
+/// Deletes files the app downloaded into the downloads directory once they
+/// are older than `max_age_days`.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+pub struct CleanupPolicy {
+    pub max_age_days: u64,
+    /// Also move downloaded datasets untouched for as long to the trash, so
+    /// their repo blocks go once the trash retention runs out.
+    #[serde(default)]
+    pub drop_blocks: bool,
+}
+
+/// Local JSON-RPC API for scripts, only bound to the loopback interface.
+///
+/// Changes take effect on the next launch.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+pub struct RpcApiSettings {
+    pub port: u16,
+}
+
+/// Inbox other users send file offers to, bound to every interface.
+/// Offers are sent to peers on the same port.
+///
+/// Changes take effect on the next launch.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+pub struct OfferInboxSettings {
+    pub port: u16,
+}
+
+/// Use an already running node instead of the embedded one.
+///
+/// Changes take effect on the next launch.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+pub struct RemoteNodeSettings {
+    /// Base URL of the node's REST API, e.g. `http://localhost:8080`.
+    pub url: String,
+}
+
+/// What to hold back while the connection is metered (cellular, tethering).
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+pub struct MeteredPolicy {
+    /// Stop the node so it doesn't serve content to other peers.
+    pub pause_seeding: bool,
+    /// Queued transfers above this size wait for an unmetered connection.
+    pub max_transfer_bytes: u64,
+}
+
+/// What to hold back while running on a low battery.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+pub struct BatteryPolicy {
+    /// The policy applies on battery below this charge, in percent.
+    pub min_charge_percent: u8,
+    /// Queued uploads wait until the machine is plugged in.
+    pub pause_uploads: bool,
+    /// Stop the node so it doesn't serve content to other peers.
+    pub pause_seeding: bool,
+}
+
+/// Restricts the node to peers sharing the same swarm key.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+pub struct PrivateNetworkSettings {
+    /// 32-byte pre-shared key, hex encoded as in a libp2p `swarm.key` file.
+    pub swarm_key: String,
+}
+
+impl PrivateNetworkSettings {
+    pub fn is_valid_key(&self) -> bool {
+        self.swarm_key.len() == 64 && self.swarm_key.chars().all(|c| c.is_ascii_hexdigit())
+    }
+}
+
+/// Public address the node is reachable at, for port-forwarded or VPS
+/// setups where it can't find out on its own.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+pub struct ExternalAddress {
+    pub ip: String,
+    pub port: u16,
+}
+
+impl ExternalAddress {
+    /// The address as a TCP multiaddress, `None` if the IP doesn't parse.
+    pub fn multiaddr(&self) -> Option<String> {
+        match self.ip.parse::<IpAddr>().ok()? {
+            IpAddr::V4(ip) => Some(format!("/ip4/{}/tcp/{}", ip, self.port)),
+            IpAddr::V6(ip) => Some(format!("/ip6/{}/tcp/{}", ip, self.port)),
+        }
+    }
+}
+
+/// Proxy for the app's own HTTP traffic: URL fetches and the remote node's
+/// API. The embedded node's peer connections can't be proxied.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+pub struct ProxySettings {
+    /// `http://`, `https://`, `socks5://` or `socks5h://` URL, credentials
+    /// may be given in the URL.
+    pub url: String,
+}
+
+impl ProxySettings {
+    pub fn is_valid_url(&self) -> bool {
+        ["http://", "https://", "socks5://", "socks5h://"]
+            .iter()
+            .any(|scheme| self.url.starts_with(scheme))
+            && reqwest::Proxy::all(&self.url).is_ok()
+    }
+}
+
+/// HTTP client going through the proxy, if one is configured.
+///
+/// Loopback addresses are always reached directly so a local remote node
+/// keeps working.
+pub fn http_client(proxy: Option<&ProxySettings>) -> Result<reqwest::Client, StorageError> {
+    let mut builder = reqwest::Client::builder();
+    if let Some(proxy) = proxy {
+        let proxy = reqwest::Proxy::all(&proxy.url)
+            .map_err(|e| StorageError::Configuration(format!("Invalid proxy: {}", e)))?
+            .no_proxy(reqwest::NoProxy::from_string("localhost,127.0.0.1,::1"));
+        builder = builder.proxy(proxy);
+    }
+    builder
+        .build()
+        .map_err(|e| StorageError::Configuration(format!("Failed to create HTTP client: {}", e)))
+}
+
+/// Address families the node has to listen on, and announces.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Default)]
+pub enum IpFamily {
+    #[default]
+    Ipv4,
+    Ipv6,
+    DualStack,
+}
+
+impl IpFamily {
+    pub fn includes_ipv4(&self) -> bool {
+        matches!(self, IpFamily::Ipv4 | IpFamily::DualStack)
+    }
+
+    pub fn includes_ipv6(&self) -> bool {
+        matches!(self, IpFamily::Ipv6 | IpFamily::DualStack)
+    }
+
+    /// Whether a multiaddress belongs to the family; DNS names could resolve
+    /// to either.
+    pub fn allows(&self, address: &str) -> bool {
+        if address.starts_with("/ip4/") {
+            self.includes_ipv4()
+        } else if address.starts_with("/ip6/") {
+            self.includes_ipv6()
+        } else {
+            true
+        }
+    }
+}
+
+/// A peer the node reconnects to after the system wakes up or the network changes.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+pub struct BookmarkedPeer {
+    pub peer_id: String,
+    pub addresses: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(default)]
+pub struct AppSettings {
+    pub transfer_window: Option<TransferWindow>,
+    /// Directory managed by the app for downloaded files, `None` for the default.
+    /// Has to be picked in a dialog, it is added to the fs scope.
+    pub downloads_dir: Option<String>,
+    pub cleanup_policy: Option<CleanupPolicy>,
+    /// Fractions of the storage quota at which a warning event is emitted.
+    pub quota_warning_thresholds: Vec<f64>,
+    pub rpc_api: Option<RpcApiSettings>,
+    pub offer_inbox: Option<OfferInboxSettings>,
+    pub remote_node: Option<RemoteNodeSettings>,
+    pub bookmarked_peers: Vec<BookmarkedPeer>,
+    pub metered_policy: Option<MeteredPolicy>,
+    pub battery_policy: Option<BatteryPolicy>,
+    /// Seeding pauses once this many GiB were transferred in the calendar
+    /// month, `None` for no cap.
+    pub monthly_cap_gb: Option<u64>,
+    /// Peer limit of the embedded node, `None` for the default.
+    pub max_peers: Option<u32>,
+    pub private_network: Option<PrivateNetworkSettings>,
+    /// Find peers through the DHT; off means bookmarked peers only.
+    pub dht_discovery: bool,
+    /// Advertise the node and find other nodes on the LAN over mDNS.
+    pub local_discovery: bool,
+    /// Relays to reserve a slot on when the node can't be dialed directly,
+    /// as multiaddresses ending in `/p2p/<peer id>`.
+    pub relay_peers: Vec<String>,
+    /// Replaces the announced addresses shown for a remote node; the
+    /// embedded node can't be told to announce it.
+    pub external_address: Option<ExternalAddress>,
+    pub ip_family: IpFamily,
+    pub proxy: Option<ProxySettings>,
+    /// Only serve stored content: no uploads, no fetching from the network.
+    pub read_only: bool,
+    /// Fetch without hosting: no uploads or announcements, and downloaded
+    /// datasets aren't kept in the repo.
+    pub download_only: bool,
+    /// Concurrent range requests per download with a remote node; the
+    /// embedded node's bindings only offer a sequential stream.
+    ///
+    /// Changes take effect on the next launch.
+    pub download_parallelism: u32,
+    /// Default dataset block size for uploads in bytes, `None` for the node's default.
+    pub upload_block_size: Option<u64>,
+    /// Cap in MiB on transfer data buffered in memory, `None` for no cap.
+    /// Only remote node downloads buffer whole ranges; the embedded node
+    /// and sequential streams write through as data arrives.
+    ///
+    /// Changes take effect on the next launch.
+    pub transfer_memory_limit_mb: Option<u32>,
+    /// Seconds without transfer progress before a transfer is reported as
+    /// stalled; it fails after three times as long. `None` to wait forever.
+    pub stall_timeout_secs: Option<u32>,
+    /// Checks run on finished downloads.
+    pub download_verification: DownloadVerification,
+    /// Checksum file written next to each download, `None` for none.
+    pub download_checksum_sidecar: Option<ChecksumAlgorithm>,
+    /// Offer to download CIDs copied to the clipboard, off unless opted in.
+    pub watch_clipboard: bool,
+    /// Main window geometry per monitor layout, saved when the window closes.
+    pub window_states: BTreeMap<String, WindowGeometry>,
+    /// Days a deleted dataset stays restorable before its blocks are removed.
+    pub trash_retention_days: u32,
+    /// Directory for intermediate transfer files, staged into a
+    /// `storeman-staging` subdirectory; `None` for one inside the app data
+    /// directory. Orphaned staged files are removed at every launch.
+    pub staging_dir: Option<String>,
+    /// Run at a lower CPU priority so seeding doesn't compete with
+    /// foreground work. Turning it off takes effect on the next launch.
+    pub background_seeding: bool,
+    /// Stop the embedded node after this many minutes without transfers,
+    /// it starts again with the next one.
+    pub auto_disconnect_minutes: Option<u32>,
+    /// The first-launch checks passed once and aren't run on startup anymore.
+    pub onboarding_completed: bool,
+    /// Language of messages produced by the backend, `None` for English.
+    pub locale: Option<Locale>,
+    /// Upload a copy of files that change mid-upload instead of failing.
+    pub snapshot_changed_uploads: bool,
+    /// Uploads above this many GiB need to be confirmed, `None` for no limit.
+    pub upload_confirmation_gb: Option<u64>,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            transfer_window: None,
+            downloads_dir: None,
+            cleanup_policy: None,
+            quota_warning_thresholds: vec![0.8, 0.95],
+            rpc_api: None,
+            offer_inbox: None,
+            remote_node: None,
+            bookmarked_peers: Vec::new(),
+            metered_policy: None,
+            battery_policy: None,
+            monthly_cap_gb: None,
+            max_peers: None,
+            private_network: None,
+            dht_discovery: true,
+            local_discovery: false,
+            relay_peers: Vec::new(),
+            external_address: None,
+            ip_family: IpFamily::default(),
+            proxy: None,
+            read_only: false,
+            download_only: false,
+            download_parallelism: 4,
+            upload_block_size: None,
+            transfer_memory_limit_mb: None,
+            stall_timeout_secs: Some(120),
+            download_verification: DownloadVerification::default(),
+            download_checksum_sidecar: None,
+            watch_clipboard: false,
+            window_states: BTreeMap::new(),
+            trash_retention_days: 30,
+            staging_dir: None,
+            background_seeding: false,
+            auto_disconnect_minutes: None,
+            onboarding_completed: false,
+            locale: None,
+            snapshot_changed_uploads: true,
+            upload_confirmation_gb: None,
+        }
+    }
+}
+
+impl AppSettings {
+    pub fn validate(&self) -> Result<(), StorageError> {
+        if let Some(window) = &self.transfer_window {
+            if window.start_hour > 23 || window.end_hour > 23 {
+                return Err(StorageError::Configuration(
+                    "Transfer window hours must be between 0 and 23".to_string(),
+                ));
+            }
+            if window.start_hour == window.end_hour {
+                return Err(StorageError::Configuration(
+                    "Transfer window must not be empty".to_string(),
+                ));
+            }
+        }
+
+        if let Some(policy) = &self.cleanup_policy {
+            if policy.max_age_days == 0 {
+                return Err(StorageError::Configuration(
+                    "Cleanup age must be at least one day".to_string(),
+                ));
+            }
+        }
+
+        if self
+            .quota_warning_thresholds
+            .iter()
+            .any(|threshold| !(0.0..=1.0).contains(threshold))
+        {
+            return Err(StorageError::Configuration(
+                "Quota warning thresholds must be between 0 and 1".to_string(),
+            ));
+        }
+
+        if let Some(rpc_api) = &self.rpc_api {
+            if rpc_api.port == 0 {
+                return Err(StorageError::Configuration(
+                    "JSON-RPC API port must not be 0".to_string(),
+                ));
+            }
+        }
+
+        if self
+            .offer_inbox
+            .as_ref()
+            .is_some_and(|inbox| inbox.port == 0)
+        {
+            return Err(StorageError::Configuration(
+                "Offer inbox port must not be 0".to_string(),
+            ));
+        }
+
+        if let Some(remote_node) = &self.remote_node {
+            if !remote_node.url.starts_with("http://") && !remote_node.url.starts_with("https://") {
+                return Err(StorageError::Configuration(
+                    "Remote node URL must start with http:// or https://".to_string(),
+                ));
+            }
+        }
+
+        if let Some(private_network) = &self.private_network {
+            if !private_network.is_valid_key() {
+                return Err(StorageError::Configuration(
+                    "Swarm key must be 64 hexadecimal characters".to_string(),
+                ));
+            }
+            // The embedded node would refuse to start with it on every launch
+            if self.remote_node.is_none() {
+                return Err(StorageError::Unsupported(
+                    "private networks need swarm key support in codex-bindings, \
+                     the embedded node can't join one"
+                        .to_string(),
+                ));
+            }
+        }
+
+        // The embedded node always joins the DHT and would refuse to start
+        // on every launch otherwise
+        if !self.dht_discovery && self.remote_node.is_none() {
+            return Err(StorageError::Unsupported(
+                "DHT discovery can't be turned off in codex-bindings".to_string(),
+            ));
+        }
+
+        if self
+            .relay_peers
+            .iter()
+            .any(|relay| !relay.starts_with('/') || !relay.contains("/p2p/"))
+        {
+            return Err(StorageError::Configuration(
+                "Relay peers must be multiaddresses ending in /p2p/<peer id>".to_string(),
+            ));
+        }
+        // Nor does the embedded node have a circuit relay client to use them
+        if !self.relay_peers.is_empty() && self.remote_node.is_none() {
+            return Err(StorageError::Unsupported(
+                "relays need circuit relay support in codex-bindings".to_string(),
+            ));
+        }
+
+        if let Some(external_address) = &self.external_address {
+            if !self
+                .ip_family
+                .allows(&external_address.multiaddr().unwrap_or_default())
+            {
+                return Err(StorageError::Configuration(
+                    "External address doesn't match the IP family setting".to_string(),
+                ));
+            }
+            if external_address.multiaddr().is_none() || external_address.port == 0 {
+                return Err(StorageError::Configuration(
+                    "External address needs a valid IP address and a non-zero port".to_string(),
+                ));
+            }
+            // The embedded node works out its announced addresses on its own
+            // and would refuse to start on every launch
+            if self.remote_node.is_none() {
+                return Err(StorageError::Unsupported(
+                    "codex-bindings can't override the announced address of the embedded node"
+                        .to_string(),
+                ));
+            }
+        }
+
+        if self
+            .proxy
+            .as_ref()
+            .is_some_and(|proxy| !proxy.is_valid_url())
+        {
+            return Err(StorageError::Configuration(
+                "Proxy must be an http, https, socks5 or socks5h URL".to_string(),
+            ));
+        }
+
+        if !(1..=32).contains(&self.download_parallelism) {
+            return Err(StorageError::Configuration(
+                "Download parallelism must be between 1 and 32".to_string(),
+            ));
+        }
+
+        validate_block_size(self.upload_block_size.map(|block_size| block_size as usize))?;
+
+        if self.transfer_memory_limit_mb == Some(0) {
+            return Err(StorageError::Configuration(
+                "Transfer memory limit must be at least 1 MiB".to_string(),
+            ));
+        }
+
+        if self
+            .battery_policy
+            .as_ref()
+            .is_some_and(|policy| policy.min_charge_percent > 100)
+        {
+            return Err(StorageError::Configuration(
+                "Battery threshold must be a percentage".to_string(),
+            ));
+        }
+
+        if self.stall_timeout_secs.is_some_and(|secs| secs < 10) {
+            return Err(StorageError::Configuration(
+                "Stall timeout must be at least 10 seconds".to_string(),
+            ));
+        }
+
+        // Purging a trash kept for no days would skip the EmptyTrash
+        // confirmation
+        if self.trash_retention_days == 0 {
+            return Err(StorageError::Configuration(
+                "Trash retention must be at least 1 day".to_string(),
+            ));
+        }
+
+        if let Some(downloads_dir) = &self.downloads_dir {
+            validate_directory_setting("Downloads directory", downloads_dir)?;
+        }
+
+        if let Some(staging_dir) = &self.staging_dir {
+            validate_directory_setting("Staging directory", staging_dir)?;
+        }
+
+        if self.monthly_cap_gb == Some(0) {
+            return Err(StorageError::Configuration(
+                "Monthly bandwidth cap must be at least 1 GiB".to_string(),
+            ));
+        }
+
+        if self.upload_confirmation_gb == Some(0) {
+            return Err(StorageError::Configuration(
+                "Upload confirmation limit must be at least 1 GiB".to_string(),
+            ));
+        }
+
+        if self.auto_disconnect_minutes == Some(0) {
+            return Err(StorageError::Configuration(
+                "Auto-disconnect needs at least 1 minute of inactivity".to_string(),
+            ));
+        }
+
+        if self.max_peers == Some(0) {
+            return Err(StorageError::Configuration(
+                "Max peers must be at least 1".to_string(),
+            ));
+        }
+
+        if self
+            .bookmarked_peers
+            .iter()
+            .any(|peer| peer.peer_id.trim().is_empty())
+        {
+            return Err(StorageError::Configuration(
+                "Bookmarked peers must have a peer ID".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Resolves the managed downloads directory, falling back to a folder
+    /// inside the user's downloads directory.
+    pub fn resolve_downloads_dir(&self) -> Result<PathBuf, StorageError> {
+        if let Some(dir) = &self.downloads_dir {
+            return Ok(PathBuf::from(dir));
+        }
+
+        dirs::download_dir()
+            .or_else(dirs::home_dir)
+            .map(|dir| dir.join(DEFAULT_DOWNLOADS_DIR_NAME))
+            .ok_or_else(|| {
+                StorageError::Configuration("Unable to determine downloads directory".to_string())
+            })
+    }
+
+    /// Whether the queue may start a transfer right now.
+    pub fn transfers_allowed_now(&self) -> bool {
+        self.transfer_window
+            .as_ref()
+            .map_or(true, |window| window.is_open_now())
+    }
+}
+
+const MIN_BLOCK_SIZE: usize = 4 * 1024;
+const MAX_BLOCK_SIZE: usize = 4 * 1024 * 1024;
+
+/// Block sizes must be a power of two between 4 KiB and 4 MiB.
+pub fn validate_block_size(block_size: Option<usize>) -> Result<(), StorageError> {
+    match block_size {
+        Some(block_size)
+            if !block_size.is_power_of_two()
+                || !(MIN_BLOCK_SIZE..=MAX_BLOCK_SIZE).contains(&block_size) =>
+        {
+            Err(StorageError::Configuration(
+                "Block size must be a power of two between 4 KiB and 4 MiB".to_string(),
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+pub struct SettingsStore {
+    settings: RwLock<AppSettings>,
+    settings_file: PathBuf,
+}
+
+impl SettingsStore {
+    pub fn load(settings_file: PathBuf) -> Self {
+        let settings = read_json_file(&settings_file).unwrap_or_default();
+        Self {
+            settings: RwLock::new(settings),
+            settings_file,
+        }
+    }
+
+    pub async fn get(&self) -> AppSettings {
+        self.settings.read().await.clone()
+    }
+
+    pub async fn update(&self, settings: AppSettings) -> Result<AppSettings, StorageError> {
+        settings.validate()?;
+
+        let mut current = self.settings.write().await;
+        write_json_file(&self.settings_file, &settings)?;
+        *current = settings.clone();
+
+        Ok(settings)
+    }
+}
+
+// Global settings instance
+pub static SETTINGS_STORE: OnceCell<Arc<SettingsStore>> = OnceCell::const_new();
+
+pub async fn get_settings_store_with_handle(
+    app_handle: &AppHandle,
+) -> Result<Arc<SettingsStore>, StorageError> {
+    SETTINGS_STORE
+        .get_or_try_init(|| async {
+            let settings_file = app_data_file(app_handle, SETTINGS_FILE_NAME)?;
+            Ok(Arc::new(SettingsStore::load(settings_file)))
+        })
+        .await
+        .map(Arc::clone)
+}
@@ -0,0 +1,112 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Manager, PhysicalPosition, PhysicalSize, WebviewWindow};
+
+use crate::features::settings::get_settings_store_with_handle;
+use crate::features::shared::StorageError;
+
+pub const MAIN_WINDOW_LABEL: &str = "main";
+
+/// Last geometry of the main window, in physical pixels.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+pub struct WindowGeometry {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub maximized: bool,
+}
+
+/// Identifies the set of connected monitors, so docking and undocking a
+/// laptop each get their own remembered geometry.
+fn monitor_layout_key(window: &WebviewWindow) -> Option<String> {
+    let mut monitors: Vec<String> = window
+        .available_monitors()
+        .ok()?
+        .iter()
+        .map(|monitor| {
+            format!(
+                "{}@{}x{}+{}+{}",
+                monitor.name().map(String::as_str).unwrap_or("unknown"),
+                monitor.size().width,
+                monitor.size().height,
+                monitor.position().x,
+                monitor.position().y
+            )
+        })
+        .collect();
+    if monitors.is_empty() {
+        return None;
+    }
+    monitors.sort();
+    Some(monitors.join("|"))
+}
+
+/// Remembers the main window's geometry for the current monitor layout.
+pub async fn save_window_state(app_handle: &AppHandle) -> Result<(), StorageError> {
+    let Some(window) = app_handle.get_webview_window(MAIN_WINDOW_LABEL) else {
+        return Ok(());
+    };
+    let Some(key) = monitor_layout_key(&window) else {
+        return Ok(());
+    };
+    // Minimized windows report a meaningless position
+    if window.is_minimized().unwrap_or(false) {
+        return Ok(());
+    }
+
+    let store = get_settings_store_with_handle(app_handle).await?;
+    let mut settings = store.get().await;
+    let maximized = window.is_maximized().unwrap_or(false);
+
+    let geometry = match settings.window_states.get(&key) {
+        // A maximized window covers the screen, keep the size to restore to
+        Some(previous) if maximized => WindowGeometry {
+            maximized,
+            ..previous.clone()
+        },
+        _ => {
+            let position = window
+                .outer_position()
+                .map_err(|e| StorageError::Configuration(e.to_string()))?;
+            let size = window
+                .inner_size()
+                .map_err(|e| StorageError::Configuration(e.to_string()))?;
+            WindowGeometry {
+                x: position.x,
+                y: position.y,
+                width: size.width,
+                height: size.height,
+                maximized,
+            }
+        }
+    };
+
+    if settings.window_states.get(&key) == Some(&geometry) {
+        return Ok(());
+    }
+    settings.window_states.insert(key, geometry);
+    store.update(settings).await?;
+    Ok(())
+}
+
+/// Puts the main window back where it was last left on this monitor layout.
+pub async fn restore_window_state(app_handle: &AppHandle) -> Result<(), StorageError> {
+    let Some(window) = app_handle.get_webview_window(MAIN_WINDOW_LABEL) else {
+        return Ok(());
+    };
+    let Some(key) = monitor_layout_key(&window) else {
+        return Ok(());
+    };
+    let store = get_settings_store_with_handle(app_handle).await?;
+    let Some(geometry) = store.get().await.window_states.get(&key).cloned() else {
+        return Ok(());
+    };
+
+    let _ = window.set_size(PhysicalSize::new(geometry.width, geometry.height));
+    let _ = window.set_position(PhysicalPosition::new(geometry.x, geometry.y));
+    if geometry.maximized {
+        let _ = window.maximize();
+    }
+    Ok(())
+}
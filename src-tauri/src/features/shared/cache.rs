@@ -0,0 +1,45 @@
+use std::future::Future;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+use crate::features::shared::StorageError;
+
+/// The last answer to a status query, served again while it is younger than
+/// `max_age` so frontend polling doesn't reach the node on every call.
+///
+/// Callers arriving during a refresh wait for it and share its answer.
+/// Errors aren't kept.
+pub struct CachedValue<T> {
+    entry: Mutex<Option<(Instant, T)>>,
+    max_age: Duration,
+}
+
+impl<T: Clone> CachedValue<T> {
+    pub const fn new(max_age: Duration) -> Self {
+        Self {
+            entry: Mutex::const_new(None),
+            max_age,
+        }
+    }
+
+    pub async fn get_or_refresh<F, Fut>(&self, refresh: F) -> Result<T, StorageError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, StorageError>>,
+    {
+        let mut entry = self.entry.lock().await;
+        if let Some((fetched_at, value)) = entry.as_ref() {
+            if fetched_at.elapsed() < self.max_age {
+                return Ok(value.clone());
+            }
+        }
+        let value = refresh().await?;
+        *entry = Some((Instant::now(), value.clone()));
+        Ok(value)
+    }
+
+    /// Drops the cached answer, for when the caller knows it changed.
+    pub async fn invalidate(&self) {
+        *self.entry.lock().await = None;
+    }
+}
@@ -0,0 +1,68 @@
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use tauri::AppHandle;
+use tauri_plugin_fs::{FilePath, FsExt, OpenOptions};
+
+use crate::features::shared::{
+    copy_with_progress, ensure_staging_space, staging_file, StorageError,
+};
+
+const CONTENT_URI_SCHEME: &str = "content://";
+
+/// Android Storage Access Framework documents are handed to us as
+/// `content://` URIs rather than filesystem paths.
+pub fn is_content_uri(path: &Path) -> bool {
+    path.to_string_lossy().starts_with(CONTENT_URI_SCHEME)
+}
+
+fn content_file_path(uri: &Path) -> Result<FilePath, StorageError> {
+    FilePath::from_str(&uri.to_string_lossy())
+        .map_err(|e| StorageError::Io(format!("Invalid content URI: {}", e)))
+}
+
+/// Copies the document behind a content URI into a staging file the node
+/// can read, reporting the bytes copied so far.
+pub async fn stage_content_uri(
+    app_handle: &AppHandle,
+    uri: &Path,
+    on_progress: impl FnMut(u64, Option<u64>),
+) -> Result<PathBuf, StorageError> {
+    let mut options = OpenOptions::new();
+    options.read(true);
+    let mut source = app_handle
+        .fs()
+        .open(content_file_path(uri)?, options)
+        .map_err(|e| StorageError::FileNotFound(format!("{}: {}", uri.display(), e)))?;
+    let total = source.metadata().ok().map(|metadata| metadata.len());
+
+    let staged = staging_file(app_handle).await?;
+    if let (Some(total), Some(dir)) = (total, staged.parent()) {
+        ensure_staging_space(dir, total)?;
+    }
+    let mut target = std::fs::File::create(&staged).map_err(|e| StorageError::Io(e.to_string()))?;
+    if let Err(e) = copy_with_progress(&mut source, &mut target, total, on_progress) {
+        let _ = std::fs::remove_file(&staged);
+        return Err(e);
+    }
+
+    Ok(staged)
+}
+
+/// Writes a downloaded file into the document behind a content URI.
+pub fn export_to_content_uri(
+    app_handle: &AppHandle,
+    source: &Path,
+    uri: &Path,
+) -> Result<(), StorageError> {
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    let mut target = app_handle
+        .fs()
+        .open(content_file_path(uri)?, options)
+        .map_err(|e| StorageError::Io(format!("{}: {}", uri.display(), e)))?;
+
+    let mut source = std::fs::File::open(source).map_err(|e| StorageError::Io(e.to_string()))?;
+    std::io::copy(&mut source, &mut target).map_err(|e| StorageError::Io(e.to_string()))?;
+
+    Ok(())
+}
@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
+use specta::Type;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use crate::features::shared::{translate, LargeUploadConfirmation, MessageKey};
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub enum StorageError {
     NodeCreation(String),
     NodeStart(String),
@@ -12,28 +15,150 @@ pub enum StorageError {
     InvalidCid(String),
     Io(String),
     Configuration(String),
+    QuotaExceeded {
+        required: u64,
+        available: u64,
+    },
+    Cancelled,
+    /// The node in use doesn't offer the requested capability.
+    Unsupported(String),
+    /// Another process holds the node's listening port.
+    PortInUse(String),
+    /// The repo is locked, usually by another instance of the app.
+    RepoLocked(String),
+    /// The native node library couldn't be loaded.
+    MissingLibrary(String),
+    /// The repo was written by a node version this one can't read.
+    IncompatibleRepo(String),
+    /// The file was modified while it was being uploaded.
+    SourceChanged(String),
+    /// The node claims to be running but stopped answering.
+    NodeUnresponsive(String),
+    /// Another start or stop of the node is still in progress.
+    Busy,
+    /// The node only serves what it already stores.
+    ReadOnlyMode,
+    /// The node doesn't host content for other peers.
+    DownloadOnlyMode,
+    /// No bytes moved for this many seconds, retrying may find other peers.
+    Stalled(u64),
+    /// A protected action ran without a valid confirmation from the user.
+    NotConfirmed,
+    /// A path from the frontend lies outside the directories the app may touch.
+    PathNotAllowed(String),
+    /// The upload is above the configured size limit, nothing was uploaded.
+    UploadConfirmationRequired(LargeUploadConfirmation),
 }
 
-impl std::fmt::Display for StorageError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl StorageError {
+    /// The catalog message for this error and its arguments.
+    pub fn message(&self) -> (MessageKey, Vec<String>) {
         match self {
-            StorageError::NodeCreation(msg) => write!(f, "Failed to create node: {}", msg),
-            StorageError::NodeStart(msg) => write!(f, "Failed to start node: {}", msg),
-            StorageError::NodeNotInitialized => write!(f, "Node is not initialized"),
-            StorageError::NodeNotStarted => write!(f, "Node is not started"),
-            StorageError::Upload(msg) => write!(f, "Upload failed: {}", msg),
-            StorageError::Download(msg) => write!(f, "Download failed: {}", msg),
-            StorageError::FileNotFound(path) => write!(f, "File not found: {}", path),
-            StorageError::InvalidCid(msg) => write!(f, "Invalid CID: {}", msg),
-            StorageError::Io(msg) => write!(f, "IO error: {}", msg),
-            StorageError::Configuration(msg) => write!(f, "Configuration error: {}", msg),
+            StorageError::NodeCreation(msg) => (MessageKey::NodeCreationFailed, vec![msg.clone()]),
+            StorageError::NodeStart(msg) => (MessageKey::NodeStartFailed, vec![msg.clone()]),
+            StorageError::NodeNotInitialized => (MessageKey::NodeNotInitialized, vec![]),
+            StorageError::NodeNotStarted => (MessageKey::NodeNotStarted, vec![]),
+            StorageError::Upload(msg) => (MessageKey::UploadFailed, vec![msg.clone()]),
+            StorageError::Download(msg) => (MessageKey::DownloadFailed, vec![msg.clone()]),
+            StorageError::FileNotFound(path) => (MessageKey::FileNotFound, vec![path.clone()]),
+            StorageError::InvalidCid(msg) => (MessageKey::InvalidCid, vec![msg.clone()]),
+            StorageError::Io(msg) => (MessageKey::IoError, vec![msg.clone()]),
+            StorageError::Configuration(msg) => (MessageKey::ConfigurationError, vec![msg.clone()]),
+            StorageError::QuotaExceeded {
+                required,
+                available,
+            } => (
+                MessageKey::QuotaExceeded,
+                vec![required.to_string(), available.to_string()],
+            ),
+            StorageError::Cancelled => (MessageKey::Cancelled, vec![]),
+            StorageError::Unsupported(msg) => (MessageKey::Unsupported, vec![msg.clone()]),
+            StorageError::PortInUse(msg) => (MessageKey::PortInUse, vec![msg.clone()]),
+            StorageError::RepoLocked(msg) => (MessageKey::RepoLocked, vec![msg.clone()]),
+            StorageError::MissingLibrary(msg) => (MessageKey::MissingLibrary, vec![msg.clone()]),
+            StorageError::IncompatibleRepo(msg) => {
+                (MessageKey::IncompatibleRepo, vec![msg.clone()])
+            }
+            StorageError::SourceChanged(path) => (MessageKey::SourceChanged, vec![path.clone()]),
+            StorageError::NodeUnresponsive(msg) => {
+                (MessageKey::NodeUnresponsive, vec![msg.clone()])
+            }
+            StorageError::Busy => (MessageKey::NodeBusy, vec![]),
+            StorageError::ReadOnlyMode => (MessageKey::ReadOnlyMode, vec![]),
+            StorageError::DownloadOnlyMode => (MessageKey::DownloadOnlyMode, vec![]),
+            StorageError::Stalled(secs) => (MessageKey::TransferStalled, vec![secs.to_string()]),
+            StorageError::NotConfirmed => (MessageKey::NotConfirmed, vec![]),
+            StorageError::PathNotAllowed(path) => (MessageKey::PathNotAllowed, vec![path.clone()]),
+            StorageError::UploadConfirmationRequired(confirmation) => (
+                MessageKey::UploadConfirmationRequired,
+                vec![
+                    confirmation.file_size.to_string(),
+                    confirmation.limit_bytes.to_string(),
+                ],
+            ),
         }
     }
 }
 
+/// Displays in the current locale.
+impl std::fmt::Display for StorageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (key, args) = self.message();
+        write!(f, "{}", translate(key, &args))
+    }
+}
+
 impl std::error::Error for StorageError {}
 
-// Convert StorageError to String for Tauri commands
-pub fn map_storage_error(err: StorageError) -> String {
-    format!("{}", err)
+/// Turns a native node creation or start failure into the variant the user
+/// can act on, `fallback` for anything unrecognized.
+pub fn classify_node_failure(
+    message: String,
+    fallback: fn(String) -> StorageError,
+) -> StorageError {
+    let lower = message.to_lowercase();
+    let mentions = |patterns: &[&str]| patterns.iter().any(|pattern| lower.contains(pattern));
+
+    if mentions(&[
+        "address already in use",
+        "addrinuse",
+        "only one usage of each socket address",
+    ]) {
+        StorageError::PortInUse(message)
+    } else if mentions(&[" lock ", "/lock", "locked"]) {
+        StorageError::RepoLocked(message)
+    } else if mentions(&[
+        "cannot open shared object",
+        "library not loaded",
+        "dlopen",
+        "dll not found",
+    ]) {
+        StorageError::MissingLibrary(message)
+    } else if mentions(&["incompatible", "repo version", "unsupported version"]) {
+        StorageError::IncompatibleRepo(message)
+    } else {
+        fallback(message)
+    }
+}
+
+/// What a failed command returns to the frontend: the error to branch on
+/// and its message in the current locale to show.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CommandError {
+    pub error: StorageError,
+    pub message: String,
+}
+
+impl From<StorageError> for CommandError {
+    fn from(error: StorageError) -> Self {
+        Self {
+            message: error.to_string(),
+            error,
+        }
+    }
+}
+
+// Convert StorageError to CommandError for Tauri commands
+pub fn map_storage_error(err: StorageError) -> CommandError {
+    CommandError::from(err)
 }
@@ -0,0 +1,287 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::RwLock;
+
+use crate::features::shared::StorageError;
+
+/// Languages the backend has messages for.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Default)]
+pub enum Locale {
+    #[default]
+    En,
+    De,
+}
+
+impl Locale {
+    /// Matches a language tag such as `de` or `de-AT`.
+    pub fn from_tag(tag: &str) -> Result<Self, StorageError> {
+        let language = tag.split(['-', '_']).next().unwrap_or_default();
+        match language.to_lowercase().as_str() {
+            "en" => Ok(Locale::En),
+            "de" => Ok(Locale::De),
+            _ => Err(StorageError::Configuration(format!(
+                "Unsupported language: {}",
+                tag
+            ))),
+        }
+    }
+}
+
+static LOCALE: RwLock<Locale> = RwLock::new(Locale::En);
+
+pub fn set_current_locale(locale: Locale) {
+    *LOCALE.write().unwrap() = locale;
+}
+
+pub fn current_locale() -> Locale {
+    *LOCALE.read().unwrap()
+}
+
+/// User-facing messages produced by the backend.
+///
+/// Serialized alongside the translated text, so the frontend can use its
+/// own catalog with the same arguments instead.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageKey {
+    NodeCreationFailed,
+    NodeStartFailed,
+    NodeNotInitialized,
+    NodeNotStarted,
+    UploadFailed,
+    DownloadFailed,
+    FileNotFound,
+    InvalidCid,
+    IoError,
+    ConfigurationError,
+    QuotaExceeded,
+    Cancelled,
+    Unsupported,
+    PortInUse,
+    RepoLocked,
+    MissingLibrary,
+    IncompatibleRepo,
+    SourceChanged,
+    NodeUnresponsive,
+    NodeBusy,
+    ReadOnlyMode,
+    DownloadOnlyMode,
+    TransferStalled,
+    NotConfirmed,
+    PathNotAllowed,
+    UploadConfirmationRequired,
+    ConfirmEmptyTrash,
+    ConfirmRunCleanup,
+    ConfirmExportIdentity,
+    ConfirmImportIdentity,
+    ConfirmRotateIdentity,
+    ConfirmRevokeShare,
+    ConfirmPurgeCatalogEntry,
+    FetchedBytes,
+    StagedBytes,
+    StartingUpload,
+    UploadedBytes,
+    VerifyingUpload,
+    UploadCompleted,
+    ConnectedToProviders,
+    ConnectedToLanProviders,
+    StartingDownload,
+    DownloadedBytes,
+    VerifyingDownload,
+    ComparingHash,
+    DownloadCompleted,
+}
+
+fn english(key: MessageKey) -> &'static str {
+    match key {
+        MessageKey::NodeCreationFailed => "Failed to create node: {0}",
+        MessageKey::NodeStartFailed => "Failed to start node: {0}",
+        MessageKey::NodeNotInitialized => "Node is not initialized",
+        MessageKey::NodeNotStarted => "Node is not started",
+        MessageKey::UploadFailed => "Upload failed: {0}",
+        MessageKey::DownloadFailed => "Download failed: {0}",
+        MessageKey::FileNotFound => "File not found: {0}",
+        MessageKey::InvalidCid => "Invalid CID: {0}",
+        MessageKey::IoError => "IO error: {0}",
+        MessageKey::ConfigurationError => "Configuration error: {0}",
+        MessageKey::QuotaExceeded => {
+            "Storage quota exceeded: {0} bytes required, {1} bytes available"
+        }
+        MessageKey::Cancelled => "Operation was cancelled",
+        MessageKey::Unsupported => "Not supported by this node: {0}",
+        MessageKey::PortInUse => {
+            "The node's port is already in use, close the other application or node using it: {0}"
+        }
+        MessageKey::RepoLocked => {
+            "The node's data is locked, make sure no other instance of the app is running: {0}"
+        }
+        MessageKey::MissingLibrary => "The node library couldn't be loaded, reinstall the app: {0}",
+        MessageKey::IncompatibleRepo => {
+            "The node's data was written by an incompatible version, update the app or move \
+             the data directory aside: {0}"
+        }
+        MessageKey::SourceChanged => "The file changed while it was being uploaded: {0}",
+        MessageKey::NodeUnresponsive => "The node stopped responding: {0}",
+        MessageKey::NodeBusy => "The node is already starting or stopping, try again in a moment",
+        MessageKey::ReadOnlyMode => "Read-only mode is on, nothing new can be stored",
+        MessageKey::DownloadOnlyMode => {
+            "Download-only mode is on, nothing is shared with other peers"
+        }
+        MessageKey::TransferStalled => "No data moved for {0} seconds",
+        MessageKey::NotConfirmed => "The action wasn't confirmed, confirm it and try again",
+        MessageKey::PathNotAllowed => {
+            "The app isn't allowed to access this path, pick it in a file dialog: {0}"
+        }
+        MessageKey::UploadConfirmationRequired => {
+            "The upload is {0} bytes, above the limit of {1} bytes, confirm it to go ahead"
+        }
+        MessageKey::ConfirmEmptyTrash => {
+            "Permanently delete every dataset in the trash? This can't be undone."
+        }
+        MessageKey::ConfirmRunCleanup => {
+            "Delete the downloads matched by the cleanup policy? This can't be undone."
+        }
+        MessageKey::ConfirmExportIdentity => {
+            "Export the node's private key? Anyone holding the file can act as this node."
+        }
+        MessageKey::ConfirmImportIdentity => {
+            "Replace the node's identity with the imported key? The current key is lost \
+             unless it was exported."
+        }
+        MessageKey::ConfirmRotateIdentity => {
+            "Replace the node's identity with a new key? The current key is lost unless it \
+             was exported."
+        }
+        MessageKey::ConfirmRevokeShare => {
+            "Revoke the share and delete its data from this node? This can't be undone."
+        }
+        MessageKey::ConfirmPurgeCatalogEntry => {
+            "Remove the dataset from the catalog, with its name, note and signature? This \
+             can't be undone."
+        }
+        MessageKey::FetchedBytes => "Fetched {0} bytes",
+        MessageKey::StagedBytes => "Staged {0} bytes",
+        MessageKey::StartingUpload => "Starting upload of {0} bytes",
+        MessageKey::UploadedBytes => "Uploaded {0} bytes",
+        MessageKey::VerifyingUpload => "Verifying upload",
+        MessageKey::UploadCompleted => "Upload completed successfully",
+        MessageKey::ConnectedToProviders => "Connected to {0} provider(s)",
+        MessageKey::ConnectedToLanProviders => "Connected to {0} provider(s) on the local network",
+        MessageKey::StartingDownload => "Starting download of CID: {0}",
+        MessageKey::DownloadedBytes => "Downloaded {0} bytes",
+        MessageKey::VerifyingDownload => "Verifying download",
+        MessageKey::ComparingHash => "Comparing file hash with the dataset",
+        MessageKey::DownloadCompleted => "Download completed successfully",
+    }
+}
+
+fn german(key: MessageKey) -> &'static str {
+    match key {
+        MessageKey::NodeCreationFailed => "Knoten konnte nicht erstellt werden: {0}",
+        MessageKey::NodeStartFailed => "Knoten konnte nicht gestartet werden: {0}",
+        MessageKey::NodeNotInitialized => "Knoten ist nicht initialisiert",
+        MessageKey::NodeNotStarted => "Knoten ist nicht gestartet",
+        MessageKey::UploadFailed => "Hochladen fehlgeschlagen: {0}",
+        MessageKey::DownloadFailed => "Herunterladen fehlgeschlagen: {0}",
+        MessageKey::FileNotFound => "Datei nicht gefunden: {0}",
+        MessageKey::InvalidCid => "Ungültige CID: {0}",
+        MessageKey::IoError => "E/A-Fehler: {0}",
+        MessageKey::ConfigurationError => "Konfigurationsfehler: {0}",
+        MessageKey::QuotaExceeded => {
+            "Speicherkontingent überschritten: {0} Bytes benötigt, {1} Bytes verfügbar"
+        }
+        MessageKey::Cancelled => "Vorgang wurde abgebrochen",
+        MessageKey::Unsupported => "Von diesem Knoten nicht unterstützt: {0}",
+        MessageKey::PortInUse => {
+            "Der Port des Knotens ist belegt, beende die andere Anwendung oder den anderen \
+             Knoten: {0}"
+        }
+        MessageKey::RepoLocked => {
+            "Die Daten des Knotens sind gesperrt, stelle sicher, dass keine andere Instanz der \
+             App läuft: {0}"
+        }
+        MessageKey::MissingLibrary => {
+            "Die Knotenbibliothek konnte nicht geladen werden, installiere die App neu: {0}"
+        }
+        MessageKey::IncompatibleRepo => {
+            "Die Daten des Knotens stammen von einer inkompatiblen Version, aktualisiere die \
+             App oder verschiebe das Datenverzeichnis: {0}"
+        }
+        MessageKey::SourceChanged => "Die Datei wurde während des Hochladens geändert: {0}",
+        MessageKey::NodeUnresponsive => "Der Knoten reagiert nicht mehr: {0}",
+        MessageKey::NodeBusy => {
+            "Der Knoten wird gerade gestartet oder gestoppt, bitte gleich erneut versuchen"
+        }
+        MessageKey::ReadOnlyMode => {
+            "Der Nur-Lese-Modus ist aktiv, es kann nichts Neues gespeichert werden"
+        }
+        MessageKey::DownloadOnlyMode => {
+            "Der Nur-Download-Modus ist aktiv, es wird nichts mit anderen Peers geteilt"
+        }
+        MessageKey::TransferStalled => "Seit {0} Sekunden wurden keine Daten übertragen",
+        MessageKey::NotConfirmed => {
+            "Die Aktion wurde nicht bestätigt, bestätige sie und versuche es erneut"
+        }
+        MessageKey::PathNotAllowed => {
+            "Die App darf nicht auf diesen Pfad zugreifen, wähle ihn in einem Dateidialog aus: {0}"
+        }
+        MessageKey::UploadConfirmationRequired => {
+            "Der Upload ist {0} Bytes groß und überschreitet die Grenze von {1} Bytes, bestätige \
+             ihn, um fortzufahren"
+        }
+        MessageKey::ConfirmEmptyTrash => {
+            "Alle Datensätze im Papierkorb endgültig löschen? Das kann nicht rückgängig \
+             gemacht werden."
+        }
+        MessageKey::ConfirmRunCleanup => {
+            "Die von der Aufräumregel erfassten Downloads löschen? Das kann nicht rückgängig \
+             gemacht werden."
+        }
+        MessageKey::ConfirmExportIdentity => {
+            "Den privaten Schlüssel des Knotens exportieren? Wer die Datei besitzt, kann als \
+             dieser Knoten auftreten."
+        }
+        MessageKey::ConfirmImportIdentity => {
+            "Die Identität des Knotens durch den importierten Schlüssel ersetzen? Der aktuelle \
+             Schlüssel geht verloren, wenn er nicht exportiert wurde."
+        }
+        MessageKey::ConfirmRotateIdentity => {
+            "Die Identität des Knotens durch einen neuen Schlüssel ersetzen? Der aktuelle \
+             Schlüssel geht verloren, wenn er nicht exportiert wurde."
+        }
+        MessageKey::ConfirmRevokeShare => {
+            "Die Freigabe widerrufen und ihre Daten von diesem Knoten löschen? Das kann nicht \
+             rückgängig gemacht werden."
+        }
+        MessageKey::ConfirmPurgeCatalogEntry => {
+            "Den Datensatz mit Name, Notiz und Signatur aus dem Katalog entfernen? Das kann \
+             nicht rückgängig gemacht werden."
+        }
+        MessageKey::FetchedBytes => "{0} Bytes abgerufen",
+        MessageKey::StagedBytes => "{0} Bytes bereitgestellt",
+        MessageKey::StartingUpload => "Starte Hochladen von {0} Bytes",
+        MessageKey::UploadedBytes => "{0} Bytes hochgeladen",
+        MessageKey::VerifyingUpload => "Hochladen wird überprüft",
+        MessageKey::UploadCompleted => "Hochladen erfolgreich abgeschlossen",
+        MessageKey::ConnectedToProviders => "Mit {0} Anbieter(n) verbunden",
+        MessageKey::ConnectedToLanProviders => "Mit {0} Anbieter(n) im lokalen Netzwerk verbunden",
+        MessageKey::StartingDownload => "Starte Herunterladen der CID: {0}",
+        MessageKey::DownloadedBytes => "{0} Bytes heruntergeladen",
+        MessageKey::VerifyingDownload => "Herunterladen wird überprüft",
+        MessageKey::ComparingHash => "Vergleiche Datei-Hash mit dem Datensatz",
+        MessageKey::DownloadCompleted => "Herunterladen erfolgreich abgeschlossen",
+    }
+}
+
+/// Renders `key` in the current locale, filling `{0}`, `{1}`, ... from `args`.
+pub fn translate(key: MessageKey, args: &[String]) -> String {
+    let template = match current_locale() {
+        Locale::En => english(key),
+        Locale::De => german(key),
+    };
+    args.iter()
+        .enumerate()
+        .fold(template.to_string(), |message, (index, arg)| {
+            message.replace(&format!("{{{}}}", index), arg)
+        })
+}
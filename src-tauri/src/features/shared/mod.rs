@@ -1,7 +1,19 @@
+pub mod cache;
+pub mod content_uri;
 pub mod error;
+pub mod i18n;
+pub mod paths;
+pub mod persistence;
 pub mod progress;
+pub mod staging;
 pub mod types;
 
+pub use cache::*;
+pub use content_uri::*;
 pub use error::*;
+pub use i18n::*;
+pub use paths::*;
+pub use persistence::*;
 pub use progress::*;
+pub use staging::*;
 pub use types::*;
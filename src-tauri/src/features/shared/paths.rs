@@ -0,0 +1,78 @@
+use std::path::{Component, Path, PathBuf};
+use tauri::AppHandle;
+use tauri_plugin_fs::FsExt;
+
+use crate::features::shared::{is_content_uri, StorageError};
+
+/// Checks a path the frontend handed over before anything reads or writes
+/// it, returning it with symlinks resolved.
+///
+/// The path has to be absolute, free of `..` and resolve to somewhere in the
+/// fs scope: the app's data and downloads directories, plus whatever the
+/// user picked in a file dialog, which grants access to the picked path.
+/// Paths that don't exist yet are checked through their parent directory.
+/// Android content URIs are grants of their own and pass unchanged.
+pub fn validate_user_path(app_handle: &AppHandle, path: &str) -> Result<PathBuf, StorageError> {
+    let raw = Path::new(path);
+    if is_content_uri(raw) {
+        return Ok(raw.to_path_buf());
+    }
+    let not_allowed = || StorageError::PathNotAllowed(path.to_string());
+    if !raw.is_absolute()
+        || raw
+            .components()
+            .any(|component| component == Component::ParentDir)
+    {
+        return Err(not_allowed());
+    }
+
+    let resolved = match std::fs::canonicalize(raw) {
+        Ok(resolved) => resolved,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            let (Some(parent), Some(name)) = (raw.parent(), raw.file_name()) else {
+                return Err(not_allowed());
+            };
+            std::fs::canonicalize(parent)
+                .map_err(|_| StorageError::FileNotFound(parent.to_string_lossy().to_string()))?
+                .join(name)
+        }
+        Err(e) => return Err(StorageError::Io(e.to_string())),
+    };
+
+    if !app_handle.fs_scope().is_allowed(&resolved) {
+        return Err(not_allowed());
+    }
+    Ok(resolved)
+}
+
+/// Whether a directory is too broad to hand to the app: a filesystem root,
+/// a top-level system directory or the user's home directory.
+pub fn is_broad_directory(dir: &Path) -> bool {
+    let parent = dir.parent();
+    parent.is_none()
+        || parent.is_some_and(|parent| parent.parent().is_none())
+        || dirs::home_dir().is_some_and(|home| home == dir)
+}
+
+/// Checks a directory named in the settings: absolute, free of `..` and
+/// not one of the broad directories.
+pub fn validate_directory_setting(name: &str, dir: &str) -> Result<(), StorageError> {
+    let dir = Path::new(dir);
+    if !dir.is_absolute()
+        || dir
+            .components()
+            .any(|component| component == Component::ParentDir)
+    {
+        return Err(StorageError::Configuration(format!(
+            "{} must be an absolute path without ..",
+            name
+        )));
+    }
+    if is_broad_directory(dir) {
+        return Err(StorageError::Configuration(format!(
+            "{} can't be a filesystem root, a system directory or the home directory",
+            name
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+use crate::features::shared::StorageError;
+
+/// Directory holding the app's own data (settings, queue, node repo).
+///
+/// On mobile this is the app-private internal storage, which is always
+/// writable without storage permissions; desktop uses the roaming app data
+/// directory.
+pub fn app_storage_dir(app_handle: &AppHandle) -> Result<PathBuf, StorageError> {
+    #[cfg(mobile)]
+    let dir = app_handle.path().app_local_data_dir();
+    #[cfg(not(mobile))]
+    let dir = app_handle.path().app_data_dir();
+
+    dir.map_err(|e| {
+        StorageError::Configuration(format!("Unable to resolve app data directory: {}", e))
+    })
+}
+
+/// Resolves a file inside the app data directory, creating the directory if needed.
+pub fn app_data_file(app_handle: &AppHandle, file_name: &str) -> Result<PathBuf, StorageError> {
+    let app_data_dir = app_storage_dir(app_handle)?;
+    std::fs::create_dir_all(&app_data_dir).map_err(|e| StorageError::Io(e.to_string()))?;
+    Ok(app_data_dir.join(file_name))
+}
+
+/// Reads a JSON file, returning `None` if it is missing or can't be parsed.
+pub fn read_json_file<T: DeserializeOwned>(path: &Path) -> Option<T> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    match serde_json::from_str(&contents) {
+        Ok(value) => Some(value),
+        Err(e) => {
+            eprintln!("Failed to parse {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+/// Writes a JSON file through a temporary file so a crash mid-write can't
+/// leave a truncated file behind.
+pub fn write_json_file<T: Serialize>(path: &Path, value: &T) -> Result<(), StorageError> {
+    let contents =
+        serde_json::to_string_pretty(value).map_err(|e| StorageError::Io(e.to_string()))?;
+    let tmp_file = path.with_extension("json.tmp");
+    std::fs::write(&tmp_file, contents).map_err(|e| StorageError::Io(e.to_string()))?;
+    std::fs::rename(&tmp_file, path).map_err(|e| StorageError::Io(e.to_string()))
+}
@@ -1,23 +1,57 @@
 use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::time::{Duration, Instant};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use crate::features::shared::{translate, MessageKey};
+
+/// Local work an upload does before anything reaches the network.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+pub enum PreparingStep {
+    /// Copying the source into the staging directory.
+    Staging,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub enum OperationStage {
     Initializing,
+    /// Preparing the source of an upload, with byte progress of the step.
+    Preparing(PreparingStep),
+    /// Fetching the source of an upload from a remote URL.
+    Fetching,
     Uploading,
     Downloading,
+    /// No bytes moved for the stall timeout, the transfer is still running.
+    Stalled,
     Verifying,
     Completed,
     Failed(String),
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct ProgressMessage {
     pub operation_id: String,
     pub progress: f64,
     pub bytes_processed: usize,
     pub total_bytes: Option<usize>,
     pub stage: OperationStage,
+    /// `message_key` rendered in the backend's locale.
     pub message: Option<String>,
+    #[serde(default)]
+    pub message_key: Option<MessageKey>,
+    #[serde(default)]
+    pub message_args: Vec<String>,
+    /// Set on the completion message of a transfer.
+    #[serde(default)]
+    pub duration_ms: Option<u64>,
+    #[serde(default)]
+    pub verified: Option<bool>,
+    /// Intermediate files removed after a failed transfer.
+    #[serde(default)]
+    pub rolled_back: Vec<String>,
+    /// The byte count is extrapolated between sparse node callbacks rather
+    /// than reported by the node.
+    #[serde(default)]
+    pub estimated: bool,
 }
 
 impl ProgressMessage {
@@ -29,6 +63,12 @@ impl ProgressMessage {
             total_bytes: None,
             stage: OperationStage::Initializing,
             message: None,
+            message_key: None,
+            message_args: Vec::new(),
+            duration_ms: None,
+            verified: None,
+            rolled_back: Vec::new(),
+            estimated: false,
         }
     }
 
@@ -46,8 +86,89 @@ impl ProgressMessage {
         self
     }
 
-    pub fn with_message(mut self, message: String) -> Self {
-        self.message = Some(message);
+    pub fn with_message(mut self, key: MessageKey, args: Vec<String>) -> Self {
+        self.message = Some(translate(key, &args));
+        self.message_key = Some(key);
+        self.message_args = args;
+        self
+    }
+
+    pub fn with_result(mut self, duration_ms: u64, verified: bool) -> Self {
+        self.duration_ms = Some(duration_ms);
+        self.verified = Some(verified);
         self
     }
+
+    pub fn with_rolled_back(mut self, rolled_back: Vec<String>) -> Self {
+        self.rolled_back = rolled_back;
+        self
+    }
+
+    pub fn as_estimate(mut self) -> Self {
+        self.estimated = true;
+        self
+    }
+}
+
+/// Fraction of the average rate estimates advance at, so a real report
+/// rarely lands behind an estimate already shown.
+const ESTIMATE_RATE_FACTOR: f64 = 0.9;
+
+/// Extrapolates a transfer's byte count from the node's progress reports,
+/// which can be minutes apart on multi-GB files.
+#[derive(Debug)]
+pub struct ProgressEstimator {
+    started_at: Instant,
+    reported_bytes: usize,
+    reported_at: Instant,
+    total_bytes: Option<usize>,
+}
+
+impl ProgressEstimator {
+    pub fn new(total_bytes: Option<usize>) -> Self {
+        let now = Instant::now();
+        Self {
+            started_at: now,
+            reported_bytes: 0,
+            reported_at: now,
+            total_bytes,
+        }
+    }
+
+    /// Records a byte count reported by the node.
+    pub fn observe(&mut self, bytes: usize, total_bytes: Option<usize>) {
+        self.reported_bytes = self.reported_bytes.max(bytes);
+        self.reported_at = Instant::now();
+        self.total_bytes = total_bytes.or(self.total_bytes);
+    }
+
+    /// Time since the node last reported progress.
+    pub fn since_report(&self) -> Duration {
+        self.reported_at.elapsed()
+    }
+
+    /// Bytes probably done by now at the average rate so far, short of the
+    /// total so only the node's own report completes the transfer. Stays at
+    /// the last report until there is a rate to go by.
+    pub fn estimate(&self) -> usize {
+        let elapsed = self
+            .reported_at
+            .duration_since(self.started_at)
+            .as_secs_f64();
+        if self.reported_bytes == 0 || elapsed <= 0.0 {
+            return self.reported_bytes;
+        }
+        let rate = self.reported_bytes as f64 / elapsed * ESTIMATE_RATE_FACTOR;
+        let estimate = self.reported_bytes + (rate * self.since_report().as_secs_f64()) as usize;
+        match self.total_bytes {
+            Some(total) => estimate
+                .min(total.saturating_sub(1))
+                .max(self.reported_bytes),
+            None => estimate,
+        }
+    }
+
+    pub fn total_bytes(&self) -> Option<usize> {
+        self.total_bytes
+    }
 }
@@ -0,0 +1,177 @@
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use tauri::AppHandle;
+
+use crate::features::settings::get_settings_store_with_handle;
+use crate::features::shared::{app_storage_dir, StorageError};
+
+const STAGING_DIR_NAME: &str = "staging";
+/// Subdirectory staged into when a staging directory is configured, so
+/// cleaning it up never touches the user's own files.
+const CONFIGURED_STAGING_DIR_NAME: &str = "storeman-staging";
+/// Free space always left over on the staging disk.
+const MIN_STAGING_FREE_BYTES: u64 = 64 * 1024 * 1024;
+/// Bytes copied between two progress reports while staging.
+const STAGING_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// Directory for intermediate files of transfers, `storeman-staging` inside
+/// the configured one or `staging` inside the app data directory.
+pub async fn staging_dir(app_handle: &AppHandle) -> Result<PathBuf, StorageError> {
+    let configured = get_settings_store_with_handle(app_handle)
+        .await?
+        .get()
+        .await
+        .staging_dir;
+    let dir = match configured {
+        Some(dir) => PathBuf::from(dir).join(CONFIGURED_STAGING_DIR_NAME),
+        None => app_storage_dir(app_handle)?.join(STAGING_DIR_NAME),
+    };
+    std::fs::create_dir_all(&dir).map_err(|e| StorageError::Io(e.to_string()))?;
+    Ok(dir)
+}
+
+/// Refuses to stage `required_bytes` more than the disk can take.
+pub fn ensure_staging_space(dir: &Path, required_bytes: u64) -> Result<(), StorageError> {
+    let available = fs2::available_space(dir).map_err(|e| StorageError::Io(e.to_string()))?;
+    if available < required_bytes.saturating_add(MIN_STAGING_FREE_BYTES) {
+        return Err(StorageError::Io(format!(
+            "Not enough free space in staging directory {}: {} bytes needed, {} available",
+            dir.display(),
+            required_bytes,
+            available.saturating_sub(MIN_STAGING_FREE_BYTES)
+        )));
+    }
+    Ok(())
+}
+
+/// A fresh path inside the staging directory, checked for at least the
+/// minimum free space.
+pub async fn staging_file(app_handle: &AppHandle) -> Result<PathBuf, StorageError> {
+    let dir = staging_dir(app_handle).await?;
+    ensure_staging_space(&dir, 0)?;
+    Ok(dir.join(uuid::Uuid::new_v4().to_string()))
+}
+
+/// Removes what earlier runs left in the staging directory.
+///
+/// Every staged file or directory is named after a fresh UUID, anything
+/// else was put there by someone else and stays. Only call this before any
+/// transfer has started.
+pub async fn clean_staging_dir(app_handle: &AppHandle) -> Result<usize, StorageError> {
+    let dir = staging_dir(app_handle).await?;
+    let entries = std::fs::read_dir(&dir).map_err(|e| StorageError::Io(e.to_string()))?;
+
+    let mut removed = 0;
+    for entry in entries.flatten() {
+        let staged_by_us = entry
+            .file_name()
+            .to_str()
+            .is_some_and(|name| uuid::Uuid::parse_str(name).is_ok());
+        if !staged_by_us {
+            continue;
+        }
+        let path = entry.path();
+        let result = if path.is_dir() {
+            std::fs::remove_dir_all(&path)
+        } else {
+            std::fs::remove_file(&path)
+        };
+        if result.is_ok() {
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+/// Size and modification time of a file, compared to notice it changing.
+pub fn source_state(path: &Path) -> Result<(u64, Option<SystemTime>), StorageError> {
+    let metadata = std::fs::metadata(path).map_err(|e| StorageError::Io(e.to_string()))?;
+    Ok((metadata.len(), metadata.modified().ok()))
+}
+
+/// Copies a file into the staging directory so it can be uploaded while
+/// the original keeps changing.
+///
+/// Fails with `SourceChanged` if it changed during the copy too.
+pub async fn snapshot_file(app_handle: &AppHandle, path: &Path) -> Result<PathBuf, StorageError> {
+    let before = source_state(path)?;
+    let dir = staging_dir(app_handle).await?;
+    ensure_staging_space(&dir, before.0)?;
+
+    let snapshot = dir.join(uuid::Uuid::new_v4().to_string());
+    let copied = tokio::fs::copy(path, &snapshot)
+        .await
+        .map_err(|e| StorageError::Io(e.to_string()));
+    if copied.is_err() || source_state(path)? != before {
+        let _ = std::fs::remove_file(&snapshot);
+        copied?;
+        return Err(StorageError::SourceChanged(
+            path.to_string_lossy().to_string(),
+        ));
+    }
+    Ok(snapshot)
+}
+
+/// Copies `source` into `target`, reporting the bytes copied after every chunk.
+pub fn copy_with_progress(
+    source: &mut impl Read,
+    target: &mut impl Write,
+    total: Option<u64>,
+    mut on_progress: impl FnMut(u64, Option<u64>),
+) -> Result<u64, StorageError> {
+    let mut buffer = vec![0u8; STAGING_CHUNK_SIZE];
+    let mut copied = 0u64;
+    loop {
+        let read = source
+            .read(&mut buffer)
+            .map_err(|e| StorageError::Io(e.to_string()))?;
+        if read == 0 {
+            break;
+        }
+        target
+            .write_all(&buffer[..read])
+            .map_err(|e| StorageError::Io(e.to_string()))?;
+        copied += read as u64;
+        on_progress(copied, total);
+    }
+    target
+        .flush()
+        .map_err(|e| StorageError::Io(e.to_string()))?;
+    Ok(copied)
+}
+
+/// Intermediate files and directories of one transfer, removed together
+/// once it ends, including when it is cancelled and its future dropped.
+#[derive(Debug, Default)]
+pub struct StagedArtifacts {
+    paths: Vec<PathBuf>,
+}
+
+impl StagedArtifacts {
+    pub fn track(&mut self, path: PathBuf) {
+        self.paths.push(path);
+    }
+
+    /// Removes every tracked path that still exists, returning those removed.
+    pub fn clean_up(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.paths)
+            .into_iter()
+            .filter(|path| {
+                let result = if path.is_dir() {
+                    std::fs::remove_dir_all(path)
+                } else {
+                    std::fs::remove_file(path)
+                };
+                result.is_ok()
+            })
+            .map(|path| path.to_string_lossy().to_string())
+            .collect()
+    }
+}
+
+impl Drop for StagedArtifacts {
+    fn drop(&mut self) {
+        self.clean_up();
+    }
+}
@@ -1,12 +1,16 @@
 use codex_bindings::DebugInfo;
 use serde::{Deserialize, Serialize};
+use specta::Type;
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
 pub enum StorageConnectionStatus {
     Disconnected,
     Initialized,
     Connecting,
     Connected,
+    /// The node is running but may have lost its connections, e.g. after the
+    /// system woke from sleep or the network changed.
+    Degraded,
     Error,
 }
 
@@ -16,15 +20,53 @@ impl Default for StorageConnectionStatus {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct NodeInfo {
     pub peer_id: Option<String>,
     pub version: Option<String>,
     pub repo_path: Option<String>,
+    // The bindings' debug info has no TypeScript definition of its own
+    #[specta(type = Option<serde_json::Value>)]
     pub debug_info: Option<DebugInfo>,
+    /// Peer limit the node was configured with, filled in from the settings.
+    #[serde(default)]
+    pub max_peers: Option<u32>,
+    /// Set while the node is running.
+    #[serde(default)]
+    pub connectivity: Option<Connectivity>,
+    /// Off in download-only mode, when the node keeps nothing to serve.
+    #[serde(default)]
+    pub hosting: bool,
+    /// Whether the node can look up who provides a dataset. Neither the
+    /// embedded node nor the REST API offer it, so the lookup is hidden.
+    #[serde(default)]
+    pub provider_lookup: bool,
+    /// Whether a dataset can be announced on demand; otherwise the node
+    /// only announces on its own schedule.
+    #[serde(default)]
+    pub announce: bool,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+/// How other peers reach the node.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq)]
+pub enum Connectivity {
+    /// The node announces a public address peers can dial.
+    Direct,
+    /// Peers only reach the node through a relay, transfers are slower.
+    Relayed,
+    /// Only private addresses are announced, the node is behind NAT and
+    /// can only be dialed from the same network.
+    LocalOnly,
+}
+
+/// A peer advertising a dataset in the DHT.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+pub struct ProviderInfo {
+    pub peer_id: String,
+    pub addresses: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Type, Debug, Clone)]
 pub struct UploadResultResponse {
     pub cid: String,
     pub size: usize,
@@ -32,7 +74,17 @@ pub struct UploadResultResponse {
     pub verified: bool,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+/// An upload above the configured size limit, held back until the user
+/// confirms it.
+#[derive(Serialize, Deserialize, Type, Debug, Clone)]
+pub struct LargeUploadConfirmation {
+    /// Pass back to the upload command to go ahead.
+    pub token: String,
+    pub file_size: u64,
+    pub limit_bytes: u64,
+}
+
+#[derive(Serialize, Deserialize, Type, Debug, Clone)]
 pub struct DownloadResultResponse {
     pub cid: String,
     pub size: usize,
@@ -40,3 +92,38 @@ pub struct DownloadResultResponse {
     pub verified: bool,
     pub filepath: Option<String>,
 }
+
+#[derive(Serialize, Deserialize, Type, Debug, Clone)]
+pub enum OperationOutcome {
+    Upload(UploadResultResponse),
+    Download(DownloadResultResponse),
+    Failed(String),
+}
+
+#[derive(Serialize, Deserialize, Type, Debug, Clone, PartialEq)]
+pub enum LogLevel {
+    Info,
+    Error,
+}
+
+#[derive(Serialize, Deserialize, Type, Debug, Clone)]
+pub struct OperationLogLine {
+    /// Milliseconds since the Unix epoch.
+    pub timestamp: i64,
+    pub level: LogLevel,
+    pub message: String,
+}
+
+/// Outcome of a finished transfer, kept around for clients that missed the
+/// completion event.
+#[derive(Serialize, Deserialize, Type, Debug, Clone)]
+pub struct OperationResult {
+    pub operation_id: String,
+    pub outcome: OperationOutcome,
+    /// Milliseconds since the Unix epoch.
+    pub started_at: i64,
+    pub finished_at: i64,
+    /// What was logged for the transfer while it ran.
+    #[serde(default)]
+    pub log: Vec<OperationLogLine>,
+}
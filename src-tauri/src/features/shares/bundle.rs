@@ -0,0 +1,187 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+use tokio::sync::Mutex;
+
+use crate::features::backend::StorageBackend;
+use crate::features::catalog::{get_catalog_with_handle, ManifestSignature};
+use crate::features::connection::get_storage_manager_with_handle;
+use crate::features::events::{emit_event, SHARE_FILE_OPENED_EVENT};
+use crate::features::peers::{node_address_report, node_spr};
+use crate::features::settings::get_settings_store_with_handle;
+use crate::features::shared::StorageError;
+
+pub const SHARE_FILE_EXTENSION: &str = "dexshare";
+
+/// Format version written by this build; newer files are refused.
+const SHARE_FILE_VERSION: u32 = 1;
+
+/// How the content was encrypted before upload, for the recipient to undo.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+pub struct EncryptionHint {
+    pub algorithm: String,
+    /// Public key the content was encrypted for.
+    #[serde(default)]
+    pub recipient_key: Option<String>,
+}
+
+/// A peer known to hold the content.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+pub struct ShareProvider {
+    pub peer_id: String,
+    pub addresses: Vec<String>,
+    /// Signed peer record, lets the recipient dial without a DHT lookup.
+    #[serde(default)]
+    pub spr: Option<String>,
+}
+
+/// Contents of a `.dexshare` file.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ShareFile {
+    pub version: u32,
+    pub cid: String,
+    #[serde(default)]
+    pub filename: Option<String>,
+    #[serde(default)]
+    pub size: Option<u64>,
+    /// The sender's note about the content.
+    #[serde(default)]
+    pub note: Option<String>,
+    #[serde(default)]
+    pub encryption: Option<EncryptionHint>,
+    #[serde(default)]
+    pub signature: Option<ManifestSignature>,
+    #[serde(default)]
+    pub providers: Vec<ShareProvider>,
+}
+
+/// Writes a share file for a catalogued dataset, listing this node as its
+/// provider ahead of `extra_providers`.
+pub async fn write_share_file(
+    app_handle: &AppHandle,
+    cid: &str,
+    path: &Path,
+    encryption: Option<EncryptionHint>,
+    extra_providers: Vec<ShareProvider>,
+) -> Result<ShareFile, StorageError> {
+    let catalog = get_catalog_with_handle(app_handle).await?;
+    let entry = catalog
+        .get(cid)
+        .await
+        .ok_or_else(|| StorageError::InvalidCid(format!("{} is not in the catalog", cid)))?;
+
+    let manager = get_storage_manager_with_handle(Some(app_handle.clone())).await?;
+    let node = manager.get_node().await?;
+    let mut providers = Vec::new();
+    if node.is_started() {
+        let settings = get_settings_store_with_handle(app_handle)
+            .await?
+            .get()
+            .await;
+        let addresses = node_address_report(&node, &settings).await?;
+        providers.push(ShareProvider {
+            peer_id: addresses.peer_id,
+            addresses: addresses.announced_addresses,
+            spr: node.debug().await.ok().as_ref().and_then(node_spr),
+        });
+    }
+    for provider in extra_providers {
+        if !providers
+            .iter()
+            .any(|known| known.peer_id == provider.peer_id)
+        {
+            providers.push(provider);
+        }
+    }
+
+    let share_file = ShareFile {
+        version: SHARE_FILE_VERSION,
+        cid: entry.cid,
+        filename: entry.name,
+        size: entry.size,
+        note: entry.note,
+        encryption,
+        signature: entry.signature,
+        providers,
+    };
+    let contents =
+        serde_json::to_vec_pretty(&share_file).map_err(|e| StorageError::Io(e.to_string()))?;
+    std::fs::write(path, contents).map_err(|e| StorageError::Io(e.to_string()))?;
+    Ok(share_file)
+}
+
+/// Reads a share file, catalogs its dataset and dials its providers so a
+/// download can start right away.
+pub async fn read_share_file(
+    app_handle: &AppHandle,
+    path: &Path,
+) -> Result<ShareFile, StorageError> {
+    let contents = std::fs::read(path).map_err(|e| StorageError::Io(e.to_string()))?;
+    let share_file: ShareFile = serde_json::from_slice(&contents)
+        .map_err(|e| StorageError::Configuration(format!("Invalid share file: {}", e)))?;
+    if share_file.version > SHARE_FILE_VERSION {
+        return Err(StorageError::Unsupported(format!(
+            "share file version {} is newer than this app supports",
+            share_file.version
+        )));
+    }
+    if share_file.cid.trim().is_empty() {
+        return Err(StorageError::InvalidCid("CID cannot be empty".to_string()));
+    }
+
+    let catalog = get_catalog_with_handle(app_handle).await?;
+    catalog
+        .upsert(&share_file.cid, |entry| {
+            entry.name = entry.name.take().or(share_file.filename.clone());
+            entry.size = entry.size.or(share_file.size);
+            entry.note = entry.note.take().or(share_file.note.clone());
+            entry.signature = entry.signature.take().or(share_file.signature.clone());
+        })
+        .await?;
+
+    // Best effort, the node can still find providers through the DHT
+    if let Ok(manager) = get_storage_manager_with_handle(Some(app_handle.clone())).await {
+        for provider in &share_file.providers {
+            if let Err(e) = manager
+                .connect_to_peer(provider.peer_id.clone(), provider.addresses.clone())
+                .await
+            {
+                eprintln!("Failed to dial share provider {}: {}", provider.peer_id, e);
+            }
+        }
+    }
+    Ok(share_file)
+}
+
+/// Share file the app was launched with, until the frontend picks it up.
+static OPENED_SHARE_FILE: Mutex<Option<ShareFile>> = Mutex::const_new(None);
+
+/// Share files passed on the command line, which is how the OS hands over
+/// a double-clicked file on Windows and Linux.
+pub fn share_files_in_args() -> Vec<PathBuf> {
+    std::env::args()
+        .skip(1)
+        .map(PathBuf::from)
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == SHARE_FILE_EXTENSION)
+        })
+        .collect()
+}
+
+/// Imports a share file opened from the OS and tells the frontend, which
+/// may not be listening yet and can call `take_opened_share_file` instead.
+pub async fn open_share_file(app_handle: &AppHandle, path: &Path) {
+    match read_share_file(app_handle, path).await {
+        Ok(share_file) => {
+            *OPENED_SHARE_FILE.lock().await = Some(share_file.clone());
+            let _ = emit_event(app_handle, SHARE_FILE_OPENED_EVENT, share_file);
+        }
+        Err(e) => eprintln!("Failed to open share file {}: {}", path.display(), e),
+    }
+}
+
+pub async fn take_opened_share() -> Option<ShareFile> {
+    OPENED_SHARE_FILE.lock().await.take()
+}
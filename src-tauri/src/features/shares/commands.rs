@@ -0,0 +1,81 @@
+use crate::features::confirmation::{consume_confirmation, ProtectedAction};
+use crate::features::shared::{map_storage_error, validate_user_path, CommandError};
+use crate::features::shares::{
+    add_share, get_share_registry_with_handle, read_share_file, remove_share, take_opened_share,
+    write_share_file, Share, ShareFile,
+};
+use tauri::AppHandle;
+
+/// Names a locally stored dataset, optionally until `expires_at` in
+/// milliseconds since the Unix epoch.
+#[tauri::command]
+#[specta::specta]
+pub async fn create_share(
+    name: String,
+    cid: String,
+    expires_at: Option<i64>,
+    app_handle: AppHandle,
+) -> Result<Share, CommandError> {
+    add_share(&app_handle, name, cid, expires_at)
+        .await
+        .map_err(map_storage_error)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_shares(app_handle: AppHandle) -> Result<Vec<Share>, CommandError> {
+    let registry = get_share_registry_with_handle(&app_handle)
+        .await
+        .map_err(map_storage_error)?;
+    Ok(registry.list().await)
+}
+
+/// Removes a share and the local copy of its dataset.
+#[tauri::command]
+#[specta::specta]
+pub async fn revoke_share(
+    name: String,
+    confirmation_token: String,
+    app_handle: AppHandle,
+) -> Result<Share, CommandError> {
+    consume_confirmation(ProtectedAction::RevokeShare, &confirmation_token)
+        .await
+        .map_err(map_storage_error)?;
+    remove_share(&app_handle, &name)
+        .await
+        .map_err(map_storage_error)
+}
+
+/// Writes a `.dexshare` file for a catalogued dataset.
+#[tauri::command]
+#[specta::specta]
+pub async fn export_share_file(
+    cid: String,
+    path: String,
+    app_handle: AppHandle,
+) -> Result<ShareFile, CommandError> {
+    let path = validate_user_path(&app_handle, &path).map_err(map_storage_error)?;
+    write_share_file(&app_handle, &cid, &path, None, Vec::new())
+        .await
+        .map_err(map_storage_error)
+}
+
+/// Reads a `.dexshare` file so its dataset is ready to download.
+#[tauri::command]
+#[specta::specta]
+pub async fn import_share_file(
+    path: String,
+    app_handle: AppHandle,
+) -> Result<ShareFile, CommandError> {
+    let path = validate_user_path(&app_handle, &path).map_err(map_storage_error)?;
+    read_share_file(&app_handle, &path)
+        .await
+        .map_err(map_storage_error)
+}
+
+/// The share file the app was opened with, once.
+#[tauri::command]
+#[specta::specta]
+pub async fn take_opened_share_file() -> Result<Option<ShareFile>, CommandError> {
+    Ok(take_opened_share().await)
+}
@@ -0,0 +1,7 @@
+pub mod bundle;
+pub mod commands;
+pub mod shares;
+
+pub use bundle::*;
+pub use commands::*;
+pub use shares::*;
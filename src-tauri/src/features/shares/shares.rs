@@ -0,0 +1,176 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tauri::AppHandle;
+use tokio::sync::{Mutex, OnceCell};
+
+use crate::features::audit::record_audit;
+use crate::features::backend::StorageBackend;
+use crate::features::catalog::{get_catalog_with_handle, validate_expiry};
+use crate::features::connection::get_storage_manager_with_handle;
+use crate::features::shared::{
+    app_data_file, read_json_file, write_json_file, StorageConnectionStatus, StorageError,
+};
+use crate::features::storage::dataset_exists_locally;
+
+pub const SHARES_FILE_NAME: &str = "shares.json";
+
+/// A friendly name handed out for a dataset.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct Share {
+    pub name: String,
+    pub cid: String,
+    /// Milliseconds since the Unix epoch.
+    pub created_at: i64,
+    /// When the local copy gets deleted, in milliseconds since the Unix epoch.
+    pub expires_at: Option<i64>,
+}
+
+/// Named shares, keyed by name and mirrored to disk.
+pub struct ShareRegistry {
+    shares: Mutex<BTreeMap<String, Share>>,
+    shares_file: PathBuf,
+}
+
+impl ShareRegistry {
+    pub fn load(shares_file: PathBuf) -> Self {
+        let shares = read_json_file(&shares_file).unwrap_or_default();
+        Self {
+            shares: Mutex::new(shares),
+            shares_file,
+        }
+    }
+
+    pub async fn insert(&self, share: Share) -> Result<Share, StorageError> {
+        let mut shares = self.shares.lock().await;
+        if shares.contains_key(&share.name) {
+            return Err(StorageError::Configuration(format!(
+                "A share named {} already exists",
+                share.name
+            )));
+        }
+        shares.insert(share.name.clone(), share.clone());
+        write_json_file(&self.shares_file, &*shares)?;
+        Ok(share)
+    }
+
+    pub async fn remove(&self, name: &str) -> Result<Option<Share>, StorageError> {
+        let mut shares = self.shares.lock().await;
+        let removed = shares.remove(name);
+        if removed.is_some() {
+            write_json_file(&self.shares_file, &*shares)?;
+        }
+        Ok(removed)
+    }
+
+    pub async fn list(&self) -> Vec<Share> {
+        self.shares.lock().await.values().cloned().collect()
+    }
+}
+
+// Global share registry instance
+pub static SHARE_REGISTRY: OnceCell<Arc<ShareRegistry>> = OnceCell::const_new();
+
+pub async fn get_share_registry_with_handle(
+    app_handle: &AppHandle,
+) -> Result<Arc<ShareRegistry>, StorageError> {
+    SHARE_REGISTRY
+        .get_or_try_init(|| async {
+            let shares_file = app_data_file(app_handle, SHARES_FILE_NAME)?;
+            Ok(Arc::new(ShareRegistry::load(shares_file)))
+        })
+        .await
+        .map(Arc::clone)
+}
+
+/// Names a locally stored dataset. An expiry is also set on the catalog
+/// entry, so the expiry schedule trashes the local copy when it passes.
+pub async fn add_share(
+    app_handle: &AppHandle,
+    name: String,
+    cid: String,
+    expires_at: Option<i64>,
+) -> Result<Share, StorageError> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err(StorageError::Configuration(
+            "Share name cannot be empty".to_string(),
+        ));
+    }
+    validate_expiry(expires_at)?;
+
+    // Only content the node can serve can be shared
+    let manager = get_storage_manager_with_handle(Some(app_handle.clone())).await?;
+    let node = manager.get_node().await?;
+    if !node.is_started() {
+        return Err(StorageError::NodeNotStarted);
+    }
+    if !dataset_exists_locally(&node, &cid).await? {
+        return Err(StorageError::InvalidCid(format!(
+            "{} is not stored locally",
+            cid
+        )));
+    }
+
+    let registry = get_share_registry_with_handle(app_handle).await?;
+    let share = registry
+        .insert(Share {
+            name,
+            cid: cid.clone(),
+            created_at: chrono::Utc::now().timestamp_millis(),
+            expires_at,
+        })
+        .await?;
+
+    if expires_at.is_some() {
+        let catalog = get_catalog_with_handle(app_handle).await?;
+        catalog
+            .upsert(&cid, |entry| entry.expires_at = expires_at)
+            .await?;
+    }
+    Ok(share)
+}
+
+/// Removes a share and deletes its dataset from the repo, unless another
+/// share still names it. The catalog entry is kept and flagged as revoked.
+pub async fn remove_share(app_handle: &AppHandle, name: &str) -> Result<Share, StorageError> {
+    let registry = get_share_registry_with_handle(app_handle).await?;
+    let shares = registry.list().await;
+    let share = shares
+        .iter()
+        .find(|share| share.name == name)
+        .cloned()
+        .ok_or_else(|| StorageError::Configuration(format!("No share named {}", name)))?;
+    let still_shared = shares
+        .iter()
+        .any(|other| other.name != share.name && other.cid == share.cid);
+
+    if !still_shared {
+        let manager = get_storage_manager_with_handle(Some(app_handle.clone())).await?;
+        if manager.get_status().await != StorageConnectionStatus::Connected {
+            return Err(StorageError::NodeNotStarted);
+        }
+        let node = manager.get_node().await?;
+        let result = node.delete(&share.cid).await;
+        record_audit(
+            app_handle,
+            "revoke_share",
+            &[&share.name, &share.cid],
+            &result,
+        )
+        .await;
+        result?;
+
+        let catalog = get_catalog_with_handle(app_handle).await?;
+        catalog
+            .upsert(&share.cid, |entry| {
+                entry.revoked_at = Some(chrono::Utc::now().timestamp_millis())
+            })
+            .await?;
+    }
+
+    registry.remove(name).await?;
+    Ok(share)
+}
@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::features::backend::StorageBackend;
+use crate::features::catalog::{Catalog, DatasetOrigin};
+use crate::features::shared::StorageError;
+use crate::features::storage::{read_storage_usage, StorageUsage};
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+pub enum DatasetRetention {
+    /// Published from here, the repo keeps it for others to fetch.
+    Pinned,
+    /// Downloaded from the network, safe to drop and fetch again.
+    Cache,
+    /// Not in the catalog, e.g. stored before the catalog existed.
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct DatasetUsage {
+    pub cid: String,
+    pub name: Option<String>,
+    /// `None` when the catalog doesn't know the dataset's size.
+    pub size: Option<u64>,
+    pub retention: DatasetRetention,
+    pub last_accessed_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct StorageBreakdown {
+    pub usage: StorageUsage,
+    /// Largest first, datasets of unknown size last.
+    pub datasets: Vec<DatasetUsage>,
+}
+
+/// Attributes the repo's local datasets to what the catalog knows about them.
+pub async fn storage_breakdown<B: StorageBackend>(
+    node: &B,
+    catalog: &Catalog,
+) -> Result<StorageBreakdown, StorageError> {
+    let usage = read_storage_usage(node).await?;
+
+    let mut datasets = Vec::new();
+    for cid in node.manifests().await? {
+        let dataset = match catalog.get(&cid).await {
+            Some(entry) => DatasetUsage {
+                retention: match entry.origin {
+                    Some(DatasetOrigin::Uploaded) => DatasetRetention::Pinned,
+                    Some(DatasetOrigin::Downloaded) => DatasetRetention::Cache,
+                    None => DatasetRetention::Unknown,
+                },
+                cid,
+                name: entry.name,
+                size: entry.size,
+                last_accessed_at: entry.last_accessed_at,
+            },
+            None => DatasetUsage {
+                cid,
+                name: None,
+                size: None,
+                retention: DatasetRetention::Unknown,
+                last_accessed_at: None,
+            },
+        };
+        datasets.push(dataset);
+    }
+    datasets.sort_by(|a, b| b.size.cmp(&a.size));
+
+    Ok(StorageBreakdown { usage, datasets })
+}
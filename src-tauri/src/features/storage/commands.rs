@@ -0,0 +1,94 @@
+use crate::features::catalog::{get_catalog_with_handle, move_to_trash};
+use crate::features::connection::get_storage_manager_with_handle;
+use crate::features::shared::{map_storage_error, CachedValue, CommandError};
+use crate::features::storage::{
+    block_exists, check_repo_integrity, dataset_exists_locally, read_storage_usage,
+    storage_breakdown, RepoCheckReport, StorageBreakdown, StorageUsage,
+};
+use std::time::Duration;
+use tauri::AppHandle;
+
+// Polled by the status bar, reading the repo's usage takes the node's lock
+static STORAGE_USAGE_CACHE: CachedValue<StorageUsage> = CachedValue::new(Duration::from_secs(2));
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_storage_usage(app_handle: AppHandle) -> Result<StorageUsage, CommandError> {
+    STORAGE_USAGE_CACHE
+        .get_or_refresh(|| async {
+            let manager = get_storage_manager_with_handle(Some(app_handle)).await?;
+            let node = manager.get_node().await?;
+            read_storage_usage(&node).await
+        })
+        .await
+        .map_err(map_storage_error)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn has_block(cid: String, app_handle: AppHandle) -> Result<bool, CommandError> {
+    let manager = get_storage_manager_with_handle(Some(app_handle))
+        .await
+        .map_err(map_storage_error)?;
+    let node = manager.get_node().await.map_err(map_storage_error)?;
+    block_exists(&node, &cid).await.map_err(map_storage_error)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn has_dataset_locally(cid: String, app_handle: AppHandle) -> Result<bool, CommandError> {
+    let manager = get_storage_manager_with_handle(Some(app_handle))
+        .await
+        .map_err(map_storage_error)?;
+    let node = manager.get_node().await.map_err(map_storage_error)?;
+    dataset_exists_locally(&node, &cid)
+        .await
+        .map_err(map_storage_error)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn check_repo(
+    repair: bool,
+    app_handle: AppHandle,
+) -> Result<RepoCheckReport, CommandError> {
+    let manager = get_storage_manager_with_handle(Some(app_handle))
+        .await
+        .map_err(map_storage_error)?;
+    if repair {
+        // Repairs re-fetch missing datasets into the repo
+        manager.ensure_writable().map_err(map_storage_error)?;
+    }
+    let node = manager.get_node().await.map_err(map_storage_error)?;
+    check_repo_integrity(&node, repair)
+        .await
+        .map_err(map_storage_error)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_storage_breakdown(
+    app_handle: AppHandle,
+) -> Result<StorageBreakdown, CommandError> {
+    let manager = get_storage_manager_with_handle(Some(app_handle.clone()))
+        .await
+        .map_err(map_storage_error)?;
+    let node = manager.get_node().await.map_err(map_storage_error)?;
+    let catalog = get_catalog_with_handle(&app_handle)
+        .await
+        .map_err(map_storage_error)?;
+    storage_breakdown(&node, &catalog)
+        .await
+        .map_err(map_storage_error)
+}
+
+/// Moves a dataset to the trash, its blocks are removed once the retention
+/// period runs out or the trash is emptied.
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_dataset(cid: String, app_handle: AppHandle) -> Result<(), CommandError> {
+    move_to_trash(&app_handle, &cid)
+        .await
+        .map(|_| ())
+        .map_err(map_storage_error)
+}
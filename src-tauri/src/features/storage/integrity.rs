@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::features::backend::StorageBackend;
+use crate::features::shared::StorageError;
+use crate::features::storage::{block_exists, ensure_quota_for_dataset};
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct RepoIssue {
+    pub cid: String,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Default)]
+pub struct RepoCheckReport {
+    pub checked: usize,
+    pub missing: Vec<String>,
+    pub repaired: Vec<String>,
+    pub errors: Vec<RepoIssue>,
+}
+
+impl RepoCheckReport {
+    pub fn is_healthy(&self) -> bool {
+        self.errors.is_empty() && self.missing.len() == self.repaired.len()
+    }
+}
+
+/// Verifies that the content referenced by every stored manifest is present
+/// in the repo, re-fetching missing datasets from the network when `repair` is set.
+pub async fn check_repo_integrity<B: StorageBackend>(
+    node: &B,
+    repair: bool,
+) -> Result<RepoCheckReport, StorageError> {
+    if !node.is_started() {
+        return Err(StorageError::NodeNotStarted);
+    }
+
+    let local_manifests = node.manifests().await?;
+
+    let mut report = RepoCheckReport::default();
+
+    for cid in local_manifests {
+        report.checked += 1;
+
+        match block_exists(node, &cid).await {
+            Ok(true) => continue,
+            Ok(false) => report.missing.push(cid.clone()),
+            Err(e) => {
+                // A read error usually means the stored block is corrupt
+                report.errors.push(RepoIssue {
+                    cid: cid.clone(),
+                    error: e.to_string(),
+                });
+            }
+        }
+
+        if !repair {
+            continue;
+        }
+
+        let fetched = match ensure_quota_for_dataset(node, &cid).await {
+            Ok(()) => node.fetch(&cid).await,
+            Err(e) => Err(e),
+        };
+        match fetched {
+            Ok(_) => report.repaired.push(cid.clone()),
+            Err(e) => report.errors.push(RepoIssue {
+                cid: cid.clone(),
+                error: format!("Re-fetch failed: {}", e),
+            }),
+        }
+    }
+
+    Ok(report)
+}
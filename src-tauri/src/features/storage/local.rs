@@ -0,0 +1,34 @@
+use crate::features::backend::StorageBackend;
+use crate::features::shared::StorageError;
+
+fn validate_cid(cid: &str) -> Result<(), StorageError> {
+    if cid.trim().is_empty() {
+        return Err(StorageError::InvalidCid("CID cannot be empty".to_string()));
+    }
+    Ok(())
+}
+
+/// Whether the block addressed by `cid` is present in the local repo.
+pub async fn block_exists<B: StorageBackend>(node: &B, cid: &str) -> Result<bool, StorageError> {
+    validate_cid(cid)?;
+    node.exists(cid).await
+}
+
+/// Whether the dataset addressed by `cid` is stored locally, i.e. its
+/// manifest is one of the repo's local manifests and its root block exists.
+pub async fn dataset_exists_locally<B: StorageBackend>(
+    node: &B,
+    cid: &str,
+) -> Result<bool, StorageError> {
+    validate_cid(cid)?;
+
+    let local_manifests = node.manifests().await?;
+    if !local_manifests
+        .iter()
+        .any(|manifest_cid| manifest_cid == cid)
+    {
+        return Ok(false);
+    }
+
+    block_exists(node, cid).await
+}
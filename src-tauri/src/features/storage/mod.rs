@@ -0,0 +1,11 @@
+pub mod breakdown;
+pub mod commands;
+pub mod integrity;
+pub mod local;
+pub mod quota;
+
+pub use breakdown::*;
+pub use commands::*;
+pub use integrity::*;
+pub use local::*;
+pub use quota::*;
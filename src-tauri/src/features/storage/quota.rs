@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::AppHandle;
+use tokio::sync::Mutex;
+
+use crate::features::backend::StorageBackend;
+use crate::features::events::{emit_event, QuotaWarning, QUOTA_WARNING_EVENT};
+use crate::features::settings::get_settings_store_with_handle;
+use crate::features::shared::StorageError;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct StorageUsage {
+    pub used_bytes: u64,
+    pub quota_bytes: u64,
+    pub total_blocks: u64,
+}
+
+impl StorageUsage {
+    pub fn available_bytes(&self) -> u64 {
+        self.quota_bytes.saturating_sub(self.used_bytes)
+    }
+
+    pub fn used_fraction(&self) -> f64 {
+        if self.quota_bytes == 0 {
+            return 0.0;
+        }
+        self.used_bytes as f64 / self.quota_bytes as f64
+    }
+}
+
+// Highest threshold we already warned about, so each one is only emitted once
+static LAST_WARNED_THRESHOLD: Mutex<Option<f64>> = Mutex::const_new(None);
+
+pub async fn read_storage_usage<B: StorageBackend>(node: &B) -> Result<StorageUsage, StorageError> {
+    node.space().await
+}
+
+/// Refuses a write of `required_bytes` that would push the repo over its quota.
+pub async fn ensure_quota_available<B: StorageBackend>(
+    node: &B,
+    required_bytes: u64,
+) -> Result<(), StorageError> {
+    let usage = read_storage_usage(node).await?;
+    if required_bytes > usage.available_bytes() {
+        return Err(StorageError::QuotaExceeded {
+            required: required_bytes,
+            available: usage.available_bytes(),
+        });
+    }
+    Ok(())
+}
+
+/// Refuses fetching `cid` into the repo when the size in its manifest
+/// wouldn't fit. Without a size in reach, such as on the embedded node
+/// before the manifest is stored, the fetch goes through.
+pub async fn ensure_quota_for_dataset<B: StorageBackend>(
+    node: &B,
+    cid: &str,
+) -> Result<(), StorageError> {
+    match node.dataset_size(cid).await? {
+        Some(size) => ensure_quota_available(node, size).await,
+        None => Ok(()),
+    }
+}
+
+/// Emits a quota warning event when usage crosses one of the configured thresholds.
+pub async fn check_quota_thresholds<B: StorageBackend>(
+    app_handle: &AppHandle,
+    node: &B,
+) -> Result<(), StorageError> {
+    let thresholds = get_settings_store_with_handle(app_handle)
+        .await?
+        .get()
+        .await
+        .quota_warning_thresholds;
+    let usage = read_storage_usage(node).await?;
+    let fraction = usage.used_fraction();
+
+    let crossed = thresholds
+        .iter()
+        .copied()
+        .filter(|threshold| fraction >= *threshold)
+        .fold(None, |highest: Option<f64>, threshold| {
+            Some(highest.map_or(threshold, |h| h.max(threshold)))
+        });
+
+    let mut last_warned = LAST_WARNED_THRESHOLD.lock().await;
+    match crossed {
+        Some(threshold) if last_warned.map_or(true, |last| threshold > last) => {
+            *last_warned = Some(threshold);
+            emit_event(
+                app_handle,
+                QUOTA_WARNING_EVENT,
+                QuotaWarning { threshold, usage },
+            )
+            .map_err(|e| StorageError::Configuration(e.to_string()))?;
+        }
+        // Usage dropped below a threshold again, allow warning about it next time
+        _ => *last_warned = crossed,
+    }
+
+    Ok(())
+}
@@ -0,0 +1,181 @@
+use serde::Serialize;
+use specta::Type;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+use crate::features::connection::get_storage_manager_with_handle;
+use crate::features::events::{emit_event, POWER_STATUS_EVENT};
+use crate::features::settings::{get_settings_store_with_handle, BatteryPolicy};
+use crate::features::shared::StorageConnectionStatus;
+use crate::features::system::{active_metered_policy, hand_over_seeding_pause};
+
+// Last detection result, `None` on desktops and where the platform doesn't tell us
+static POWER: Mutex<Option<PowerSource>> = Mutex::new(None);
+// Whether the node was stopped by the policy, so only then it is restarted
+static SEEDING_PAUSED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Type)]
+pub struct PowerSource {
+    pub on_battery: bool,
+    /// Remaining charge from 0 to 100.
+    pub charge_percent: Option<u8>,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct PowerStatus {
+    /// `None` without a battery or when the platform doesn't report one.
+    pub source: Option<PowerSource>,
+    /// Whether the battery policy currently holds back uploads and seeding.
+    pub policy_active: bool,
+    pub seeding_paused: bool,
+}
+
+/// Reads the power supplies the kernel exposes in sysfs.
+#[cfg(target_os = "linux")]
+async fn detect_power_source() -> Option<PowerSource> {
+    let read = |path: std::path::PathBuf| {
+        std::fs::read_to_string(path)
+            .ok()
+            .map(|value| value.trim().to_string())
+    };
+
+    let mut battery = None;
+    let mut on_mains = false;
+    for supply in std::fs::read_dir("/sys/class/power_supply").ok()?.flatten() {
+        let path = supply.path();
+        match read(path.join("type")).as_deref() {
+            Some("Battery") if battery.is_none() => {
+                battery = Some(read(path.join("capacity")).and_then(|c| c.parse().ok()))
+            }
+            Some("Mains") | Some("USB") => {
+                on_mains |= read(path.join("online")).as_deref() == Some("1")
+            }
+            _ => {}
+        }
+    }
+
+    battery.map(|charge_percent| PowerSource {
+        on_battery: !on_mains,
+        charge_percent,
+    })
+}
+
+/// Parses `pmset -g batt`, e.g. "Now drawing from 'Battery Power'" followed
+/// by a line with "87%;".
+#[cfg(target_os = "macos")]
+async fn detect_power_source() -> Option<PowerSource> {
+    let output = tokio::process::Command::new("pmset")
+        .args(["-g", "batt"])
+        .output()
+        .await
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let output = String::from_utf8_lossy(&output.stdout);
+    let charge_percent = output
+        .split_whitespace()
+        .find(|word| word.contains('%'))
+        .and_then(|word| word.split('%').next())
+        .and_then(|percent| percent.parse().ok());
+    // Machines without a battery don't report a charge
+    charge_percent?;
+
+    Some(PowerSource {
+        on_battery: output.contains("'Battery Power'"),
+        charge_percent,
+    })
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+async fn detect_power_source() -> Option<PowerSource> {
+    None
+}
+
+fn policy_applies(policy: &BatteryPolicy, source: Option<PowerSource>) -> bool {
+    source.is_some_and(|source| {
+        source.on_battery
+            && source
+                .charge_percent
+                .map_or(true, |percent| percent < policy.min_charge_percent)
+    })
+}
+
+/// The battery policy currently in force, if any.
+pub async fn active_battery_policy(app_handle: &AppHandle) -> Option<BatteryPolicy> {
+    let source = *POWER.lock().unwrap();
+    let policy = get_settings_store_with_handle(app_handle)
+        .await
+        .ok()?
+        .get()
+        .await
+        .battery_policy?;
+    policy_applies(&policy, source).then_some(policy)
+}
+
+pub async fn power_status(app_handle: &AppHandle) -> PowerStatus {
+    PowerStatus {
+        source: *POWER.lock().unwrap(),
+        policy_active: active_battery_policy(app_handle).await.is_some(),
+        seeding_paused: SEEDING_PAUSED.load(Ordering::SeqCst),
+    }
+}
+
+/// Stops or restarts the node to match the seeding part of the policy.
+///
+/// Runs on every check rather than only on changes, so a node another
+/// policy restarted is stopped again while the battery is still low.
+async fn apply_battery_policy(app_handle: &AppHandle) {
+    let pause_seeding = active_battery_policy(app_handle)
+        .await
+        .is_some_and(|policy| policy.pause_seeding);
+
+    let Ok(manager) = get_storage_manager_with_handle(Some(app_handle.clone())).await else {
+        return;
+    };
+
+    if pause_seeding {
+        if manager.get_status().await == StorageConnectionStatus::Connected {
+            println!("Low battery, pausing seeding");
+            match manager.stop_node().await {
+                Ok(()) => SEEDING_PAUSED.store(true, Ordering::SeqCst),
+                Err(e) => eprintln!("Failed to pause seeding: {}", e),
+            }
+        }
+    } else if SEEDING_PAUSED.swap(false, Ordering::SeqCst) {
+        if active_metered_policy(app_handle)
+            .await
+            .is_some_and(|policy| policy.pause_seeding)
+        {
+            // Still held back by the metered connection, which resumes it later
+            hand_over_seeding_pause();
+        } else {
+            println!("Back on power, resuming seeding");
+            if let Err(e) = manager.start_node().await {
+                eprintln!("Failed to resume seeding: {}", e);
+            }
+        }
+    }
+}
+
+/// Detects the power source again and applies the battery policy.
+pub async fn refresh_power(app_handle: &AppHandle) {
+    let source = detect_power_source().await;
+    let changed = {
+        let mut current = POWER.lock().unwrap();
+        let changed = *current != source;
+        *current = source;
+        changed
+    };
+
+    apply_battery_policy(app_handle).await;
+    if changed {
+        let _ = emit_event(
+            app_handle,
+            POWER_STATUS_EVENT,
+            power_status(app_handle).await,
+        );
+    }
+}
@@ -0,0 +1,80 @@
+use std::time::Duration;
+use tauri::AppHandle;
+
+use crate::features::events::{emit_event, ClipboardCid, CLIPBOARD_CID_EVENT};
+use crate::features::settings::get_settings_store_with_handle;
+
+const CLIPBOARD_POLL_INTERVAL: Duration = Duration::from_secs(1);
+const CID_LINK_PREFIX: &str = "codex://";
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Pulls a CID out of copied text, either bare or as a `codex://<cid>` link.
+///
+/// Codex CIDs are CIDv1 in multibase base58btc, so this checks the `z`
+/// prefix, alphabet and a plausible length rather than decoding them.
+pub fn extract_cid(text: &str) -> Option<String> {
+    let text = text.trim();
+    let candidate = text.strip_prefix(CID_LINK_PREFIX).unwrap_or(text);
+    let candidate = candidate.split(['/', '?', '#']).next().unwrap_or_default();
+
+    let is_cid = candidate.starts_with('z')
+        && (40..=100).contains(&candidate.len())
+        && candidate[1..]
+            .chars()
+            .all(|character| BASE58_ALPHABET.contains(character));
+    is_cid.then(|| candidate.to_string())
+}
+
+#[cfg(desktop)]
+fn clipboard_text(clipboard: &mut Option<arboard::Clipboard>) -> Option<String> {
+    if clipboard.is_none() {
+        *clipboard = arboard::Clipboard::new().ok();
+    }
+    clipboard.as_mut()?.get_text().ok()
+}
+
+/// Polls the clipboard while the opt-in setting is on and emits
+/// `storage://clipboard-cid` each time something new parses as a CID.
+///
+/// Reading from Rust keeps this working while the webview is unfocused.
+#[cfg(desktop)]
+pub async fn run_clipboard_watcher(app_handle: AppHandle) {
+    let mut clipboard = None;
+    let mut last_text: Option<String> = None;
+
+    loop {
+        tokio::time::sleep(CLIPBOARD_POLL_INTERVAL).await;
+
+        let enabled = match get_settings_store_with_handle(&app_handle).await {
+            Ok(store) => store.get().await.watch_clipboard,
+            Err(_) => false,
+        };
+        if !enabled {
+            // Release the clipboard connection and don't offer whatever was
+            // copied before the watcher got switched on
+            clipboard = None;
+            last_text = None;
+            continue;
+        }
+
+        let text = clipboard_text(&mut clipboard);
+        // The first read after enabling only sets the baseline
+        let first_read = last_text.is_none();
+        if text.is_none() || text == last_text {
+            continue;
+        }
+        last_text = text;
+        if first_read {
+            continue;
+        }
+
+        if let Some(cid) = last_text.as_deref().and_then(extract_cid) {
+            println!("Detected CID on the clipboard: {}", cid);
+            let _ = emit_event(&app_handle, CLIPBOARD_CID_EVENT, ClipboardCid { cid });
+        }
+    }
+}
+
+/// Mobile platforms have no background clipboard access.
+#[cfg(mobile)]
+pub async fn run_clipboard_watcher(_app_handle: AppHandle) {}
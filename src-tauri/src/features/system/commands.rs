@@ -0,0 +1,28 @@
+use crate::features::shared::CommandError;
+use crate::features::system::{
+    network_status, power_status, set_metered_override, NetworkStatus, PowerStatus,
+};
+use tauri::AppHandle;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_network_status() -> Result<NetworkStatus, CommandError> {
+    Ok(network_status())
+}
+
+/// Ignores the metered policy until the connection changes.
+#[tauri::command]
+#[specta::specta]
+pub async fn override_metered_policy(
+    overridden: bool,
+    app_handle: AppHandle,
+) -> Result<NetworkStatus, CommandError> {
+    set_metered_override(&app_handle, overridden).await;
+    Ok(network_status())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_power_status(app_handle: AppHandle) -> Result<PowerStatus, CommandError> {
+    Ok(power_status(&app_handle).await)
+}
@@ -0,0 +1,147 @@
+use serde::Serialize;
+use specta::Type;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+use crate::features::connection::get_storage_manager_with_handle;
+use crate::features::events::{emit_event, NETWORK_STATUS_EVENT};
+use crate::features::queue::TransferRequest;
+use crate::features::settings::{get_settings_store_with_handle, MeteredPolicy};
+use crate::features::shared::StorageConnectionStatus;
+
+// Last detection result, `None` where the platform doesn't tell us
+static METERED: Mutex<Option<bool>> = Mutex::new(None);
+// Set by the user to ignore the policy until the connection changes
+static POLICY_OVERRIDDEN: AtomicBool = AtomicBool::new(false);
+// Whether the node was stopped by the policy, so only then it is restarted
+static SEEDING_PAUSED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct NetworkStatus {
+    /// `None` when the platform doesn't expose whether the connection is metered.
+    pub metered: Option<bool>,
+    pub policy_overridden: bool,
+    pub seeding_paused: bool,
+}
+
+/// Asks NetworkManager whether the primary connection is metered.
+#[cfg(target_os = "linux")]
+async fn detect_metered() -> Option<bool> {
+    let output = tokio::process::Command::new("busctl")
+        .args([
+            "get-property",
+            "org.freedesktop.NetworkManager",
+            "/org/freedesktop/NetworkManager",
+            "org.freedesktop.NetworkManager",
+            "Metered",
+        ])
+        .output()
+        .await
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    // Prints e.g. "u 4": 1 yes, 2 no, 3 guessed yes, 4 guessed no, 0 unknown
+    match String::from_utf8_lossy(&output.stdout).trim() {
+        "u 1" | "u 3" => Some(true),
+        "u 2" | "u 4" => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+async fn detect_metered() -> Option<bool> {
+    None
+}
+
+pub fn network_status() -> NetworkStatus {
+    NetworkStatus {
+        metered: *METERED.lock().unwrap(),
+        policy_overridden: POLICY_OVERRIDDEN.load(Ordering::SeqCst),
+        seeding_paused: SEEDING_PAUSED.load(Ordering::SeqCst),
+    }
+}
+
+/// The metered policy currently in force, if any.
+pub async fn active_metered_policy(app_handle: &AppHandle) -> Option<MeteredPolicy> {
+    let status = network_status();
+    if status.metered != Some(true) || status.policy_overridden {
+        return None;
+    }
+
+    get_settings_store_with_handle(app_handle)
+        .await
+        .ok()?
+        .get()
+        .await
+        .metered_policy
+}
+
+/// Whether a queued transfer has to wait for an unmetered connection.
+///
+/// Download sizes aren't known before the transfer, so downloads always wait.
+pub fn held_back_by(policy: &MeteredPolicy, request: &TransferRequest) -> bool {
+    match request {
+        TransferRequest::Upload { file_path, .. } => std::fs::metadata(file_path)
+            .map(|metadata| metadata.len() > policy.max_transfer_bytes)
+            .unwrap_or(false),
+        TransferRequest::Download { .. } => true,
+    }
+}
+
+/// Stops or restarts the node to match the seeding part of the policy.
+pub async fn apply_metered_policy(app_handle: &AppHandle) {
+    let pause_seeding = active_metered_policy(app_handle)
+        .await
+        .is_some_and(|policy| policy.pause_seeding);
+
+    let Ok(manager) = get_storage_manager_with_handle(Some(app_handle.clone())).await else {
+        return;
+    };
+
+    if pause_seeding {
+        if manager.get_status().await == StorageConnectionStatus::Connected {
+            println!("Metered connection, pausing seeding");
+            match manager.stop_node().await {
+                Ok(()) => SEEDING_PAUSED.store(true, Ordering::SeqCst),
+                Err(e) => eprintln!("Failed to pause seeding: {}", e),
+            }
+        }
+    } else if SEEDING_PAUSED.swap(false, Ordering::SeqCst) {
+        println!("Resuming seeding");
+        if let Err(e) = manager.start_node().await {
+            eprintln!("Failed to resume seeding: {}", e);
+        }
+    }
+
+    let _ = emit_event(app_handle, NETWORK_STATUS_EVENT, network_status());
+}
+
+/// Leaves a node stopped by another policy paused until the metered
+/// policy lifts, which then starts it again.
+pub fn hand_over_seeding_pause() {
+    SEEDING_PAUSED.store(true, Ordering::SeqCst);
+}
+
+/// Detects the connection type again and applies the policy when it changed.
+pub async fn refresh_metered(app_handle: &AppHandle) {
+    let metered = detect_metered().await;
+    {
+        let mut current = METERED.lock().unwrap();
+        if *current == metered {
+            return;
+        }
+        *current = metered;
+    }
+
+    // An override only applies to the connection it was made on
+    POLICY_OVERRIDDEN.store(false, Ordering::SeqCst);
+    apply_metered_policy(app_handle).await;
+}
+
+pub async fn set_metered_override(app_handle: &AppHandle, overridden: bool) {
+    POLICY_OVERRIDDEN.store(overridden, Ordering::SeqCst);
+    apply_metered_policy(app_handle).await;
+}
@@ -0,0 +1,15 @@
+pub mod battery;
+pub mod clipboard;
+pub mod commands;
+pub mod metered;
+pub mod priority;
+pub mod taskbar;
+pub mod watcher;
+
+pub use battery::*;
+pub use clipboard::*;
+pub use commands::*;
+pub use metered::*;
+pub use priority::*;
+pub use taskbar::*;
+pub use watcher::*;
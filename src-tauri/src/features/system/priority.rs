@@ -0,0 +1,66 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::AppHandle;
+
+use crate::features::settings::get_settings_store_with_handle;
+
+/// Nice value used for background seeding, well below normal foreground work.
+#[cfg(unix)]
+const BACKGROUND_NICE: libc::c_int = 10;
+
+// Logged once, the platform won't change while running
+static UNSUPPORTED_LOGGED: AtomicBool = AtomicBool::new(false);
+
+/// Lowers the priority of every thread of the process, including the
+/// node's own worker threads which Linux schedules individually.
+#[cfg(target_os = "linux")]
+fn lower_process_priority() -> bool {
+    let Ok(tasks) = std::fs::read_dir("/proc/self/task") else {
+        return false;
+    };
+    for task in tasks.flatten() {
+        let Some(tid) = task.file_name().to_str().and_then(|tid| tid.parse().ok()) else {
+            continue;
+        };
+        // SAFETY: plain syscalls on our own threads
+        unsafe {
+            if libc::getpriority(libc::PRIO_PROCESS, tid) < BACKGROUND_NICE {
+                libc::setpriority(libc::PRIO_PROCESS, tid, BACKGROUND_NICE);
+            }
+        }
+    }
+    true
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+fn lower_process_priority() -> bool {
+    // SAFETY: plain syscalls on our own process
+    unsafe { libc::setpriority(libc::PRIO_PROCESS, 0, BACKGROUND_NICE) == 0 }
+}
+
+#[cfg(not(unix))]
+fn lower_process_priority() -> bool {
+    false
+}
+
+/// Applies background seeding while it is enabled in the settings.
+///
+/// A lower priority only costs anything while the CPU is contended, which
+/// is exactly when the user is busy with other work, so it is applied for
+/// as long as the mode is on rather than tracking input idleness. Raising
+/// the priority back needs privileges the app doesn't have, so turning the
+/// mode off takes effect on the next launch. The bindings expose no limit
+/// on concurrent block requests served to peers, so serving isn't capped.
+pub async fn apply_background_seeding(app_handle: &AppHandle) {
+    let enabled = match get_settings_store_with_handle(app_handle).await {
+        Ok(store) => store.get().await.background_seeding,
+        Err(_) => false,
+    };
+    if !enabled {
+        return;
+    }
+
+    // Reapplied on every call to catch threads the node started since
+    if !lower_process_priority() && !UNSUPPORTED_LOGGED.swap(true, Ordering::SeqCst) {
+        eprintln!("Background seeding priority is not supported on this platform");
+    }
+}
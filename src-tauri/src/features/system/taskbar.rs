@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+use tauri::window::{ProgressBarState, ProgressBarStatus};
+use tauri::{AppHandle, Manager};
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::features::connection::get_storage_manager_with_handle;
+use crate::features::settings::MAIN_WINDOW_LABEL;
+use crate::features::shared::{OperationStage, ProgressMessage};
+
+/// Bytes done and expected of every transfer still running.
+#[derive(Default)]
+struct AggregateProgress {
+    transfers: HashMap<String, (usize, Option<usize>)>,
+}
+
+impl AggregateProgress {
+    fn update(&mut self, progress: &ProgressMessage) {
+        match progress.stage {
+            OperationStage::Completed | OperationStage::Failed(_) => {
+                self.transfers.remove(&progress.operation_id);
+            }
+            // Carries no byte counts, the last ones still hold
+            OperationStage::Stalled => {}
+            _ => {
+                self.transfers.insert(
+                    progress.operation_id.clone(),
+                    (progress.bytes_processed, progress.total_bytes),
+                );
+            }
+        }
+    }
+
+    /// Percentage over the transfers with a known size, `None` when idle.
+    fn percent(&self) -> Option<u64> {
+        if self.transfers.is_empty() {
+            return None;
+        }
+        let (done, total) = self
+            .transfers
+            .values()
+            .filter_map(|(done, total)| total.map(|total| (*done, total)))
+            .fold((0, 0), |(done, total), (d, t)| (done + d, total + t));
+        Some(if total == 0 {
+            0
+        } else {
+            (done as u64 * 100 / total as u64).min(100)
+        })
+    }
+}
+
+fn show_progress(app_handle: &AppHandle, percent: Option<u64>) {
+    let Some(window) = app_handle.get_webview_window(MAIN_WINDOW_LABEL) else {
+        return;
+    };
+
+    let state = match percent {
+        Some(percent) => ProgressBarState {
+            status: Some(ProgressBarStatus::Normal),
+            progress: Some(percent),
+        },
+        None => ProgressBarState {
+            status: Some(ProgressBarStatus::None),
+            progress: None,
+        },
+    };
+    if let Err(e) = window.set_progress_bar(state) {
+        eprintln!("Failed to update taskbar progress: {}", e);
+    }
+
+    // The dock shows the progress bar too, the badge keeps it readable
+    #[cfg(target_os = "macos")]
+    {
+        let _ = window.set_badge_label(percent.map(|percent| format!("{}%", percent)));
+    }
+}
+
+/// Mirrors the combined progress of running transfers onto the Windows
+/// taskbar button and the macOS dock icon.
+///
+/// Reads the same progress stream that feeds the UI and only touches the
+/// window when the shown percentage changes.
+#[cfg(desktop)]
+pub async fn run_taskbar_progress(app_handle: AppHandle) {
+    let manager = match get_storage_manager_with_handle(Some(app_handle.clone())).await {
+        Ok(manager) => manager,
+        Err(e) => {
+            eprintln!("Taskbar progress unavailable: {}", e);
+            return;
+        }
+    };
+
+    let mut progress_events = manager.subscribe_progress();
+    let mut aggregate = AggregateProgress::default();
+    let mut shown = None;
+
+    loop {
+        match progress_events.recv().await {
+            Ok(progress) => aggregate.update(&progress),
+            // A few missed updates only delay the next percentage
+            Err(RecvError::Lagged(_)) => continue,
+            Err(RecvError::Closed) => break,
+        }
+
+        let percent = aggregate.percent();
+        if percent != shown {
+            shown = percent;
+            show_progress(&app_handle, percent);
+        }
+    }
+}
+
+/// Mobile platforms have no taskbar or dock.
+#[cfg(mobile)]
+pub async fn run_taskbar_progress(_app_handle: AppHandle) {}
@@ -0,0 +1,129 @@
+use std::net::IpAddr;
+use std::time::{Duration, SystemTime};
+use tauri::AppHandle;
+
+use crate::features::bandwidth::apply_bandwidth_cap;
+use crate::features::connection::get_storage_manager_with_handle;
+use crate::features::events::{emit_event, SystemEvent, SYSTEM_EVENT};
+use crate::features::queue::get_transfer_queue_with_handle;
+use crate::features::settings::get_settings_store_with_handle;
+use crate::features::system::{apply_background_seeding, refresh_metered, refresh_power};
+
+const WATCH_INTERVAL: Duration = Duration::from_secs(5);
+// The wall clock keeps running while the system sleeps but our timer doesn't,
+// a gap well beyond the interval means we were suspended
+const SUSPEND_GAP: Duration = Duration::from_secs(30);
+
+/// Addresses of the non-loopback interfaces, sorted so they can be compared.
+pub fn network_addresses() -> Vec<IpAddr> {
+    let mut addresses: Vec<IpAddr> = if_addrs::get_if_addrs()
+        .map(|interfaces| {
+            interfaces
+                .into_iter()
+                .filter(|interface| !interface.is_loopback())
+                .map(|interface| interface.ip())
+                .collect()
+        })
+        .unwrap_or_default();
+    addresses.sort();
+    addresses
+}
+
+/// Holds back queued transfers and flags the node as degraded.
+async fn suspend(app_handle: &AppHandle, event: SystemEvent) {
+    println!("System event: {:?}, pausing transfers", event);
+    let _ = emit_event(app_handle, SYSTEM_EVENT, event);
+
+    if let Ok(queue) = get_transfer_queue_with_handle(app_handle).await {
+        queue.set_paused(true);
+    }
+    if let Some(manager) = crate::features::connection::STORAGE_MANAGER.get() {
+        manager.mark_degraded().await;
+    }
+}
+
+/// Checks the node again, reconnects to bookmarked peers and resumes the queue.
+async fn resume(app_handle: &AppHandle, event: SystemEvent) {
+    println!("System event: {:?}, checking connectivity", event);
+    let _ = emit_event(app_handle, SYSTEM_EVENT, event);
+
+    if let Ok(manager) = get_storage_manager_with_handle(Some(app_handle.clone())).await {
+        match manager.restore_connectivity().await {
+            Ok(()) => {
+                let peers = match get_settings_store_with_handle(app_handle).await {
+                    Ok(store) => store.get().await.bookmarked_peers,
+                    Err(_) => Vec::new(),
+                };
+                for peer in peers {
+                    if let Err(e) = manager
+                        .connect_to_peer(peer.peer_id.clone(), peer.addresses)
+                        .await
+                    {
+                        eprintln!("Failed to reconnect to {}: {}", peer.peer_id, e);
+                    }
+                }
+            }
+            Err(e) => eprintln!("Failed to restore node connectivity: {}", e),
+        }
+    }
+
+    if let Ok(queue) = get_transfer_queue_with_handle(app_handle).await {
+        queue.set_paused(false);
+    }
+}
+
+/// Watches for system sleep and network interface changes for as long as
+/// the app is running.
+///
+/// There is no portable suspend notification, so sleep is detected after the
+/// fact from the wall clock jumping ahead; both halves of the handling then
+/// run on wake.
+pub async fn run_system_watcher(app_handle: AppHandle) {
+    let mut last_tick = SystemTime::now();
+    let mut addresses = network_addresses();
+    let mut network_lost = addresses.is_empty();
+    refresh_metered(&app_handle).await;
+    refresh_power(&app_handle).await;
+    apply_bandwidth_cap(&app_handle).await;
+    apply_background_seeding(&app_handle).await;
+
+    loop {
+        tokio::time::sleep(WATCH_INTERVAL).await;
+        refresh_metered(&app_handle).await;
+        refresh_power(&app_handle).await;
+        apply_bandwidth_cap(&app_handle).await;
+        apply_background_seeding(&app_handle).await;
+
+        let now = SystemTime::now();
+        let elapsed = now.duration_since(last_tick).unwrap_or_default();
+        last_tick = now;
+
+        if elapsed > WATCH_INTERVAL + SUSPEND_GAP {
+            suspend(&app_handle, SystemEvent::Suspended).await;
+            // Interfaces usually come back a little after the wake itself
+            addresses = network_addresses();
+            network_lost = addresses.is_empty();
+            if !network_lost {
+                resume(&app_handle, SystemEvent::Resumed).await;
+            }
+            continue;
+        }
+
+        let current = network_addresses();
+        if current == addresses {
+            continue;
+        }
+        addresses = current;
+
+        if addresses.is_empty() {
+            network_lost = true;
+            suspend(&app_handle, SystemEvent::NetworkLost).await;
+        } else {
+            if !network_lost {
+                suspend(&app_handle, SystemEvent::NetworkChanged).await;
+            }
+            network_lost = false;
+            resume(&app_handle, SystemEvent::NetworkChanged).await;
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod tasks;
+
+pub use tasks::*;
@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+/// How long shutdown waits for cancelled tasks before aborting them.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+struct BackgroundTask {
+    id: u64,
+    cancel: CancellationToken,
+    handle: JoinHandle<()>,
+}
+
+/// Owns the app's background tasks under unique names, so they can be
+/// stopped one by one or all together on exit instead of running detached.
+///
+/// Cancelled tasks are dropped at their next await point. Tasks that end
+/// on their own drop out of the registry.
+pub struct TaskManager {
+    tasks: Mutex<HashMap<String, BackgroundTask>>,
+    next_id: AtomicU64,
+    shutdown: CancellationToken,
+}
+
+static TASK_MANAGER: OnceLock<TaskManager> = OnceLock::new();
+
+pub fn task_manager() -> &'static TaskManager {
+    TASK_MANAGER.get_or_init(|| TaskManager {
+        tasks: Mutex::new(HashMap::new()),
+        next_id: AtomicU64::new(0),
+        shutdown: CancellationToken::new(),
+    })
+}
+
+impl TaskManager {
+    /// Runs `task` in the background, replacing and cancelling any task
+    /// already running under `name`. Nothing starts once shutdown began.
+    pub fn spawn<F>(&'static self, name: &str, task: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        if self.shutdown.is_cancelled() {
+            return;
+        }
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let cancel = self.shutdown.child_token();
+        let cancelled = cancel.clone();
+        let finished_name = name.to_string();
+
+        // Held until the task is registered, so one that ends right away
+        // still finds its entry to remove
+        let mut tasks = self.tasks.lock().unwrap();
+        let handle = tauri::async_runtime::spawn(async move {
+            tokio::select! {
+                _ = cancelled.cancelled() => {}
+                _ = task => {}
+            }
+            let mut tasks = self.tasks.lock().unwrap();
+            if tasks.get(&finished_name).is_some_and(|task| task.id == id) {
+                tasks.remove(&finished_name);
+            }
+        });
+
+        let replaced = tasks.insert(name.to_string(), BackgroundTask { id, cancel, handle });
+        if let Some(replaced) = replaced {
+            replaced.cancel.cancel();
+        }
+    }
+
+    /// Cancels the task running under `name` and waits for it to end.
+    /// Returns whether there was one.
+    pub async fn stop(&self, name: &str) -> bool {
+        let Some(mut task) = self.tasks.lock().unwrap().remove(name) else {
+            return false;
+        };
+        task.cancel.cancel();
+        if tokio::time::timeout(SHUTDOWN_TIMEOUT, &mut task.handle)
+            .await
+            .is_err()
+        {
+            eprintln!("Background task {} didn't stop in time, aborting it", name);
+            task.handle.abort();
+        }
+        true
+    }
+
+    /// Cancels every task and waits for them to end, aborting the ones that
+    /// take longer than the timeout.
+    pub async fn shutdown(&self) {
+        self.shutdown.cancel();
+        let tasks: Vec<(String, BackgroundTask)> = self.tasks.lock().unwrap().drain().collect();
+
+        let deadline = tokio::time::Instant::now() + SHUTDOWN_TIMEOUT;
+        for (name, mut task) in tasks {
+            if tokio::time::timeout_at(deadline, &mut task.handle)
+                .await
+                .is_err()
+            {
+                eprintln!("Background task {} didn't stop in time, aborting it", name);
+                task.handle.abort();
+            }
+        }
+    }
+}
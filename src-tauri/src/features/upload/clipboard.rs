@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::{Path, PathBuf};
+
+use crate::features::connection::get_storage_manager_with_handle;
+use crate::features::shared::{staging_file, StagedArtifacts, StorageError, UploadResultResponse};
+use crate::features::upload::{report_rollback, upload_file_with_progress};
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+pub enum ClipboardContentType {
+    Image,
+    Text,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ClipboardUploadResponse {
+    pub upload: UploadResultResponse,
+    pub content_type: ClipboardContentType,
+}
+
+#[cfg(desktop)]
+fn write_png(path: &Path, image: &arboard::ImageData) -> Result<(), StorageError> {
+    let file = std::fs::File::create(path).map_err(|e| StorageError::Io(e.to_string()))?;
+    let mut encoder = png::Encoder::new(
+        std::io::BufWriter::new(file),
+        image.width as u32,
+        image.height as u32,
+    );
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&image.bytes))
+        .map_err(|e| StorageError::Io(format!("Failed to encode clipboard image: {}", e)))
+}
+
+/// Writes the clipboard's image as PNG, or else its text, into `staging_dir`.
+#[cfg(desktop)]
+fn stage_clipboard(staging_dir: &Path) -> Result<(PathBuf, ClipboardContentType), StorageError> {
+    let mut clipboard = arboard::Clipboard::new()
+        .map_err(|e| StorageError::Io(format!("Failed to open clipboard: {}", e)))?;
+    let timestamp = chrono::Utc::now().format("%Y%m%d-%H%M%S");
+
+    if let Ok(image) = clipboard.get_image() {
+        let path = staging_dir.join(format!("clipboard-{}.png", timestamp));
+        write_png(&path, &image)?;
+        return Ok((path, ClipboardContentType::Image));
+    }
+
+    match clipboard.get_text() {
+        Ok(text) if !text.is_empty() => {
+            let path = staging_dir.join(format!("clipboard-{}.txt", timestamp));
+            std::fs::write(&path, text).map_err(|e| StorageError::Io(e.to_string()))?;
+            Ok((path, ClipboardContentType::Text))
+        }
+        _ => Err(StorageError::Upload(
+            "The clipboard holds no image or text".to_string(),
+        )),
+    }
+}
+
+#[cfg(mobile)]
+fn stage_clipboard(_staging_dir: &Path) -> Result<(PathBuf, ClipboardContentType), StorageError> {
+    Err(StorageError::Unsupported(
+        "clipboard uploads are only available on desktop".to_string(),
+    ))
+}
+
+/// Stages the clipboard's contents to a file and uploads it.
+pub async fn upload_clipboard_with_progress(
+    operation_id: String,
+    app_handle: tauri::AppHandle,
+) -> Result<ClipboardUploadResponse, StorageError> {
+    let staging_dir = staging_file(&app_handle).await?;
+    std::fs::create_dir_all(&staging_dir).map_err(|e| StorageError::Io(e.to_string()))?;
+    let mut artifacts = StagedArtifacts::default();
+    artifacts.track(staging_dir.clone());
+
+    let staging_dir_clone = staging_dir.clone();
+    let staged = tokio::task::spawn_blocking(move || stage_clipboard(&staging_dir_clone))
+        .await
+        .map_err(|e| StorageError::Io(e.to_string()))?;
+
+    let result = match staged {
+        Ok((staged_file, content_type)) => upload_file_with_progress(
+            operation_id.clone(),
+            staged_file,
+            None,
+            None,
+            app_handle.clone(),
+        )
+        .await
+        .map(|upload| ClipboardUploadResponse {
+            upload,
+            content_type,
+        }),
+        Err(e) => Err(e),
+    };
+    let rolled_back = artifacts.clean_up();
+    let manager = get_storage_manager_with_handle(Some(app_handle)).await?;
+    report_rollback(
+        &manager,
+        &operation_id,
+        result.map_err(|e| (e, rolled_back)),
+    )
+    .await
+}
@@ -1,13 +1,84 @@
-use crate::features::shared::map_storage_error;
-use crate::features::upload::upload_file_with_progress;
+use crate::features::audit::record_audit;
+use crate::features::shared::{map_storage_error, validate_user_path, CommandError, StorageError};
+use crate::features::upload::{
+    ensure_file_upload_confirmed, upload_clipboard_with_progress, upload_file_with_progress,
+    upload_from_url_with_progress, ClipboardUploadResponse, UploadFileResult,
+};
 use tauri::AppHandle;
+use uuid::Uuid;
 
+/// Uploads a file, or asks for confirmation first when it is above the
+/// configured size limit; confirm by calling again with the returned token.
 #[tauri::command]
+#[specta::specta]
 pub async fn upload_file_to_storage(
     file_path: String,
+    block_size: Option<usize>,
+    ttl_secs: Option<u64>,
+    confirmation_token: Option<String>,
     app_handle: AppHandle,
-) -> Result<crate::features::shared::UploadResultResponse, String> {
-    upload_file_with_progress(file_path.into(), app_handle)
+) -> Result<UploadFileResult, CommandError> {
+    let resolved_path = validate_user_path(&app_handle, &file_path).map_err(map_storage_error)?;
+    match ensure_file_upload_confirmed(&app_handle, &resolved_path, confirmation_token.as_deref())
         .await
+    {
+        Ok(()) => {}
+        Err(StorageError::UploadConfirmationRequired(confirmation)) => {
+            return Ok(UploadFileResult::ConfirmationRequired(confirmation))
+        }
+        Err(e) => return Err(map_storage_error(e)),
+    }
+
+    let result = upload_file_with_progress(
+        Uuid::new_v4().to_string(),
+        resolved_path,
+        block_size,
+        ttl_secs,
+        app_handle.clone(),
+    )
+    .await;
+    record_audit(
+        &app_handle,
+        "upload_file_to_storage",
+        &[&file_path],
+        &result,
+    )
+    .await;
+    result
+        .map(UploadFileResult::Uploaded)
         .map_err(map_storage_error)
 }
+
+/// Fetches `url` and uploads the result, returning the new dataset's CID.
+///
+/// Content above the configured size limit fails with
+/// `UploadConfirmationRequired`, confirm by calling again with its token.
+#[tauri::command]
+#[specta::specta]
+pub async fn upload_from_url(
+    url: String,
+    confirmation_token: Option<String>,
+    app_handle: AppHandle,
+) -> Result<crate::features::shared::UploadResultResponse, CommandError> {
+    let result = upload_from_url_with_progress(
+        Uuid::new_v4().to_string(),
+        url.clone(),
+        confirmation_token,
+        app_handle.clone(),
+    )
+    .await;
+    record_audit(&app_handle, "upload_from_url", &[&url], &result).await;
+    result.map_err(map_storage_error)
+}
+
+/// Uploads whatever is on the clipboard, an image as PNG or text as .txt.
+#[tauri::command]
+#[specta::specta]
+pub async fn upload_clipboard(
+    app_handle: AppHandle,
+) -> Result<ClipboardUploadResponse, CommandError> {
+    let result =
+        upload_clipboard_with_progress(Uuid::new_v4().to_string(), app_handle.clone()).await;
+    record_audit(&app_handle, "upload_clipboard", &[], &result).await;
+    result.map_err(map_storage_error)
+}
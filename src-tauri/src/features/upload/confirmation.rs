@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::Path;
+use tauri::AppHandle;
+
+use crate::features::confirmation::{consume_token, issue_confirmation, Confirmed};
+use crate::features::settings::get_settings_store_with_handle;
+use crate::features::shared::{LargeUploadConfirmation, StorageError, UploadResultResponse};
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub enum UploadFileResult {
+    Uploaded(UploadResultResponse),
+    /// The file is above the configured size limit, nothing was uploaded.
+    ConfirmationRequired(LargeUploadConfirmation),
+}
+
+/// Holds back uploads above the configured size until the user confirms,
+/// failing with `UploadConfirmationRequired` and a token to confirm with.
+///
+/// Every upload entry point runs this before transferring anything.
+/// `source` is what the user picked, a path or a URL. A token is good for
+/// one upload of the same source at the same size, so a file that grew
+/// after confirming is asked about again.
+pub async fn ensure_upload_confirmed(
+    app_handle: &AppHandle,
+    source: &str,
+    file_size: u64,
+    confirmation_token: Option<&str>,
+) -> Result<(), StorageError> {
+    let Some(limit_gb) = get_settings_store_with_handle(app_handle)
+        .await?
+        .get()
+        .await
+        .upload_confirmation_gb
+    else {
+        return Ok(());
+    };
+    let limit_bytes = limit_gb.saturating_mul(1024 * 1024 * 1024);
+    if file_size <= limit_bytes {
+        return Ok(());
+    }
+
+    let confirmed = Confirmed::LargeUpload {
+        source: source.to_string(),
+        file_size,
+    };
+    if let Some(token) = confirmation_token {
+        if consume_token(&confirmed, token).await.is_ok() {
+            return Ok(());
+        }
+    }
+
+    let token = issue_confirmation(confirmed).await;
+    Err(StorageError::UploadConfirmationRequired(
+        LargeUploadConfirmation {
+            token,
+            file_size,
+            limit_bytes,
+        },
+    ))
+}
+
+/// `ensure_upload_confirmed` for a local file.
+///
+/// Content URIs have no size up front, they are let through.
+pub async fn ensure_file_upload_confirmed(
+    app_handle: &AppHandle,
+    file_path: &Path,
+    confirmation_token: Option<&str>,
+) -> Result<(), StorageError> {
+    let Ok(metadata) = std::fs::metadata(file_path) else {
+        return Ok(());
+    };
+    ensure_upload_confirmed(
+        app_handle,
+        &file_path.to_string_lossy(),
+        metadata.len(),
+        confirmation_token,
+    )
+    .await
+}
@@ -1,5 +1,11 @@
+pub mod clipboard;
 pub mod commands;
+pub mod confirmation;
 pub mod upload;
+pub mod url;
 
+pub use clipboard::*;
 pub use commands::*;
+pub use confirmation::*;
 pub use upload::*;
+pub use url::*;
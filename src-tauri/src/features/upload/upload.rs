@@ -1,19 +1,313 @@
-use codex_bindings::{upload_file, UploadOptions};
+use std::convert::Infallible;
 use std::path::PathBuf;
-use uuid::Uuid;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
 
-use crate::features::connection::get_storage_manager_with_handle;
+use crate::features::backend::StorageBackend;
+use crate::features::bandwidth::{record_bandwidth, TransferDirection};
+use crate::features::catalog::{get_catalog_with_handle, record_in_catalog, DatasetOrigin};
+use crate::features::connection::{get_storage_manager_with_handle, StorageManager};
+use crate::features::recent::{record_recent_item, RecentAction};
+use crate::features::settings::{get_settings_store_with_handle, validate_block_size};
 use crate::features::shared::{
-    OperationStage, ProgressMessage, StorageError, UploadResultResponse,
+    is_content_uri, snapshot_file, source_state, stage_content_uri, LogLevel, MessageKey,
+    OperationOutcome, OperationResult, OperationStage, PreparingStep, ProgressEstimator,
+    ProgressMessage, StagedArtifacts, StorageError, UploadResultResponse,
 };
+use crate::features::storage::{check_quota_thresholds, ensure_quota_available};
 
+/// How long the node may go without reporting progress before an estimate
+/// fills in.
+const PROGRESS_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Uploads with the app's progress, catalog and quota handling.
+///
+/// `block_size` overrides the default block size from the settings.
+/// `ttl_secs` schedules the local copy for deletion that long after the
+/// upload.
 pub async fn upload_file_with_progress(
+    operation_id: String,
     file_path: PathBuf,
+    block_size: Option<usize>,
+    ttl_secs: Option<u64>,
     app_handle: tauri::AppHandle,
 ) -> Result<UploadResultResponse, StorageError> {
-    let manager = get_storage_manager_with_handle(Some(app_handle)).await?;
+    if ttl_secs == Some(0) {
+        return Err(StorageError::Configuration(
+            "Upload TTL must be at least one second".to_string(),
+        ));
+    }
+    let manager = get_storage_manager_with_handle(Some(app_handle.clone())).await?;
+    let settings = get_settings_store_with_handle(&app_handle)
+        .await?
+        .get()
+        .await;
+    let block_size = block_size.or(settings
+        .upload_block_size
+        .map(|block_size| block_size as usize));
+    validate_block_size(block_size)?;
+    // Remote nodes always chunk with their own configured block size
+    let recorded_block_size = block_size.filter(|_| settings.remote_node.is_none());
+
+    // The node can't read content URIs (Android SAF), stage them first
+    let mut artifacts = StagedArtifacts::default();
+    let staged_file = if is_content_uri(&file_path) {
+        let manager_clone = manager.clone();
+        let operation_id_clone = operation_id.clone();
+        let on_progress = move |bytes_staged: u64, total_bytes: Option<u64>| {
+            let manager = manager_clone.clone();
+            let operation_id = operation_id_clone.clone();
+            tokio::spawn(async move {
+                let progress_msg = ProgressMessage::new(operation_id.clone())
+                    .with_stage(OperationStage::Preparing(PreparingStep::Staging))
+                    .with_bytes(
+                        bytes_staged as usize,
+                        total_bytes.map(|total| total as usize),
+                    )
+                    .with_message(MessageKey::StagedBytes, vec![bytes_staged.to_string()]);
+                manager.send_progress(&operation_id, progress_msg).await;
+            });
+        };
+        let staged_file = stage_content_uri(&app_handle, &file_path, on_progress).await?;
+        artifacts.track(staged_file.clone());
+        Some(staged_file)
+    } else {
+        None
+    };
+    let original_path = file_path.to_string_lossy().to_string();
+    let file_name = file_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string());
+    let upload_path = staged_file.unwrap_or(file_path);
+
+    let mut result = upload_file_with_block_size(
+        &manager,
+        operation_id.clone(),
+        upload_path.clone(),
+        block_size,
+    )
+    .await;
+
+    // Publish what the file looks like now rather than a mix of versions
+    if matches!(result, Err(StorageError::SourceChanged(_))) && settings.snapshot_changed_uploads {
+        manager
+            .log_operation(
+                &operation_id,
+                LogLevel::Info,
+                "Source changed during upload, retrying from a snapshot".to_string(),
+            )
+            .await;
+        result = match snapshot_file(&app_handle, &upload_path).await {
+            Ok(snapshot) => {
+                artifacts.track(snapshot.clone());
+                upload_file_with_block_size(&manager, operation_id.clone(), snapshot, block_size)
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+    }
+
+    let rolled_back = artifacts.clean_up();
+    let result = result.map_err(|e| (e, rolled_back));
+    let result = report_rollback(&manager, &operation_id, result).await?;
+
+    record_in_catalog(
+        &app_handle,
+        &result.cid,
+        file_name,
+        result.size as u64,
+        DatasetOrigin::Uploaded,
+        Some(original_path.clone()),
+    )
+    .await;
+    if let Some(block_size) = recorded_block_size {
+        if let Ok(catalog) = get_catalog_with_handle(&app_handle).await {
+            let _ = catalog
+                .upsert(&result.cid, |entry| {
+                    entry.block_size = Some(block_size as u64)
+                })
+                .await;
+        }
+    }
+    if let Some(ttl_secs) = ttl_secs {
+        let expires_at = chrono::Utc::now().timestamp_millis() + ttl_secs as i64 * 1000;
+        set_upload_expiry(
+            &app_handle,
+            &manager,
+            &operation_id,
+            &result.cid,
+            expires_at,
+        )
+        .await;
+    }
+    record_recent_item(
+        &app_handle,
+        &result.cid,
+        RecentAction::Uploaded,
+        Some(original_path),
+    )
+    .await;
+    record_bandwidth(&app_handle, TransferDirection::Upload, result.size as u64).await;
+
+    let node = manager.get_node().await?;
+    if let Err(e) = check_quota_thresholds(&app_handle, &node).await {
+        eprintln!("Failed to check storage quota: {}", e);
+    }
 
-    let operation_id = Uuid::new_v4().to_string();
+    Ok(result)
+}
+
+/// Sets when the local copy of an upload expires.
+///
+/// There are no storage contracts to keep the data on the network, so once
+/// the local copy is gone only peers that fetched it can still serve it.
+async fn set_upload_expiry(
+    app_handle: &tauri::AppHandle,
+    manager: &StorageManager,
+    operation_id: &str,
+    cid: &str,
+    expires_at: i64,
+) {
+    let expiry = match get_catalog_with_handle(app_handle).await {
+        Ok(catalog) => catalog
+            .upsert(cid, |entry| entry.expires_at = Some(expires_at))
+            .await
+            .map(|_| ()),
+        Err(e) => Err(e),
+    };
+    let (level, message) = match expiry {
+        Ok(()) => (
+            LogLevel::Info,
+            format!(
+                "Local copy expires at {}, no storage contract covers {} after that",
+                chrono::DateTime::from_timestamp_millis(expires_at)
+                    .map(|at| at.to_rfc3339())
+                    .unwrap_or_default(),
+                cid
+            ),
+        ),
+        Err(e) => (LogLevel::Error, format!("Failed to set the expiry: {}", e)),
+    };
+    manager.log_operation(operation_id, level, message).await;
+}
+
+/// Sends a failure event listing the intermediate files removed after a
+/// failed upload, then hands back the error.
+pub async fn report_rollback<B: StorageBackend, T>(
+    manager: &StorageManager<B>,
+    operation_id: &str,
+    result: Result<T, (StorageError, Vec<String>)>,
+) -> Result<T, StorageError> {
+    match result {
+        Ok(value) => Ok(value),
+        Err((e, rolled_back)) => {
+            if !rolled_back.is_empty() {
+                manager
+                    .log_operation(
+                        operation_id,
+                        LogLevel::Info,
+                        format!("Rolled back {} staged file(s)", rolled_back.len()),
+                    )
+                    .await;
+                let failed_progress = ProgressMessage::new(operation_id.to_string())
+                    .with_stage(OperationStage::Failed(e.to_string()))
+                    .with_rolled_back(rolled_back);
+                manager.send_progress(operation_id, failed_progress).await;
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Uploads with the node's default block size.
+pub async fn upload_file_with_manager<B: StorageBackend>(
+    manager: &StorageManager<B>,
+    operation_id: String,
+    file_path: PathBuf,
+) -> Result<UploadResultResponse, StorageError> {
+    upload_file_with_block_size(manager, operation_id, file_path, None).await
+}
+
+pub async fn upload_file_with_block_size<B: StorageBackend>(
+    manager: &StorageManager<B>,
+    operation_id: String,
+    file_path: PathBuf,
+    block_size: Option<usize>,
+) -> Result<UploadResultResponse, StorageError> {
+    manager.ensure_writable()?;
+    manager.ensure_hosting()?;
+    manager.wake_from_inactivity().await?;
+    let started_at = chrono::Utc::now().timestamp_millis();
+    let cancel = manager.register_operation(operation_id.clone()).await;
+    let result = tokio::select! {
+        result = run_upload(manager, operation_id.clone(), file_path, block_size) => result,
+        _ = cancel.cancelled() => Err(StorageError::Cancelled),
+        stalled = manager.watch_for_stall(&operation_id) => Err(stalled),
+    };
+    manager.finish_operation(&operation_id).await;
+
+    // Let progress listeners know the transfer is over
+    if let Err(e) = &result {
+        let reason = match e {
+            StorageError::Cancelled => "Cancelled".to_string(),
+            e => e.to_string(),
+        };
+        let failed_progress =
+            ProgressMessage::new(operation_id.clone()).with_stage(OperationStage::Failed(reason));
+        manager.send_progress(&operation_id, failed_progress).await;
+        manager.unregister_progress_sender(&operation_id).await;
+    }
+
+    let outcome = match &result {
+        Ok(response) => OperationOutcome::Upload(response.clone()),
+        Err(e) => OperationOutcome::Failed(e.to_string()),
+    };
+    manager
+        .record_operation_result(OperationResult {
+            operation_id,
+            outcome,
+            started_at,
+            finished_at: chrono::Utc::now().timestamp_millis(),
+            log: Vec::new(),
+        })
+        .await;
+
+    result
+}
+
+/// Fills the gaps between the node's progress reports with estimates, so a
+/// large upload never looks frozen.
+async fn send_progress_estimates<B: StorageBackend>(
+    manager: &StorageManager<B>,
+    operation_id: &str,
+    estimator: &Mutex<ProgressEstimator>,
+) -> Infallible {
+    loop {
+        tokio::time::sleep(PROGRESS_HEARTBEAT_INTERVAL).await;
+        let (estimate, total_bytes) = {
+            let estimator = estimator.lock().unwrap();
+            if estimator.since_report() < PROGRESS_HEARTBEAT_INTERVAL {
+                continue;
+            }
+            (estimator.estimate(), estimator.total_bytes())
+        };
+        let heartbeat = ProgressMessage::new(operation_id.to_string())
+            .with_stage(OperationStage::Uploading)
+            .with_bytes(estimate, total_bytes)
+            .with_message(MessageKey::UploadedBytes, vec![estimate.to_string()])
+            .as_estimate();
+        manager.send_progress(operation_id, heartbeat).await;
+    }
+}
+
+async fn run_upload<B: StorageBackend>(
+    manager: &StorageManager<B>,
+    operation_id: String,
+    file_path: PathBuf,
+    block_size: Option<usize>,
+) -> Result<UploadResultResponse, StorageError> {
+    let started_at = Instant::now();
 
     // Register progress sender
     let _rx = manager.register_progress_sender(operation_id.clone()).await;
@@ -41,42 +335,86 @@ pub async fn upload_file_with_progress(
         .map_err(|e| StorageError::Io(e.to_string()))?
         .len() as usize;
 
+    ensure_quota_available(&node, file_size as u64).await?;
+
     // Send file size info
     let size_progress = ProgressMessage::new(operation_id.clone())
         .with_stage(OperationStage::Uploading)
         .with_bytes(0, Some(file_size))
-        .with_message(format!("Starting upload of {} bytes", file_size));
+        .with_message(MessageKey::StartingUpload, vec![file_size.to_string()]);
     manager.send_progress(&operation_id, size_progress).await;
 
-    // Create upload options with progress callback
+    // Checked after every chunk, a file modified mid-upload would publish a
+    // dataset matching neither version
+    let initial_state = source_state(&file_path)?;
+    let source_changed = CancellationToken::new();
+    let source_changed_error = StorageError::SourceChanged(file_path.to_string_lossy().to_string());
+
+    // Forward backend progress to the operation's progress sender
     let operation_id_clone = operation_id.clone();
     let manager_clone = manager.clone();
-    let upload_options = UploadOptions::new()
-        .filepath(&file_path)
-        .on_progress(move |progress| {
-            let manager = manager_clone.clone();
-            let operation_id_for_callback = operation_id_clone.clone();
-            tokio::spawn(async move {
-                let progress_msg = ProgressMessage::new(operation_id_for_callback.clone())
-                    .with_stage(OperationStage::Uploading)
-                    .with_bytes(progress.bytes_uploaded, progress.total_bytes)
-                    .with_message(format!("Uploaded {} bytes", progress.bytes_uploaded));
-                manager
-                    .send_progress(&operation_id_for_callback, progress_msg)
-                    .await;
-            });
+    let source_changed_clone = source_changed.clone();
+    let file_path_clone = file_path.clone();
+    let estimator = Arc::new(Mutex::new(ProgressEstimator::new(Some(file_size))));
+    let estimator_clone = Arc::clone(&estimator);
+    let on_progress = Box::new(move |bytes_uploaded: usize, total_bytes: Option<usize>| {
+        if source_state(&file_path_clone).ok() != Some(initial_state) {
+            source_changed_clone.cancel();
+        }
+        estimator_clone
+            .lock()
+            .unwrap()
+            .observe(bytes_uploaded, total_bytes);
+        let manager = manager_clone.clone();
+        let operation_id_for_callback = operation_id_clone.clone();
+        tokio::spawn(async move {
+            let progress_msg = ProgressMessage::new(operation_id_for_callback.clone())
+                .with_stage(OperationStage::Uploading)
+                .with_bytes(bytes_uploaded, total_bytes)
+                .with_message(MessageKey::UploadedBytes, vec![bytes_uploaded.to_string()]);
+            manager
+                .send_progress(&operation_id_for_callback, progress_msg)
+                .await;
         });
+    });
+
+    // Perform the upload, giving up as soon as the source changes
+    let cid = tokio::select! {
+        cid = node.upload(&file_path, block_size, on_progress) => cid?,
+        _ = source_changed.cancelled() => return Err(source_changed_error),
+        never = send_progress_estimates(manager, &operation_id, &estimator) => match never {},
+    };
+    // A change during the last chunk only shows up now
+    if source_state(&file_path)? != initial_state {
+        if let Err(e) = node.delete(&cid).await {
+            manager
+                .log_operation(
+                    &operation_id,
+                    LogLevel::Error,
+                    format!("Failed to remove inconsistent upload {}: {}", cid, e),
+                )
+                .await;
+        }
+        return Err(source_changed_error);
+    }
 
-    // Perform the upload
-    let result = upload_file(&node, upload_options)
-        .await
-        .map_err(|e| StorageError::Upload(e.to_string()))?;
+    // The upload only counts as verified once the node has the manifest
+    let verifying_progress = ProgressMessage::new(operation_id.clone())
+        .with_stage(OperationStage::Verifying)
+        .with_bytes(file_size, Some(file_size))
+        .with_message(MessageKey::VerifyingUpload, vec![]);
+    manager
+        .send_progress(&operation_id, verifying_progress)
+        .await;
+    let verified = node.exists(&cid).await.unwrap_or(false);
+    let duration_ms = started_at.elapsed().as_millis() as u64;
 
     // Send completion progress
     let completion_progress = ProgressMessage::new(operation_id.clone())
         .with_stage(OperationStage::Completed)
         .with_bytes(file_size, Some(file_size))
-        .with_message("Upload completed successfully".to_string());
+        .with_message(MessageKey::UploadCompleted, vec![])
+        .with_result(duration_ms, verified);
     manager
         .send_progress(&operation_id, completion_progress)
         .await;
@@ -85,9 +423,9 @@ pub async fn upload_file_with_progress(
     manager.unregister_progress_sender(&operation_id).await;
 
     Ok(UploadResultResponse {
-        cid: result.cid,
+        cid,
         size: file_size,
-        duration_ms: 0, // TODO: Track actual duration
-        verified: true,
+        duration_ms,
+        verified,
     })
 }
@@ -0,0 +1,146 @@
+use futures_util::StreamExt;
+use std::path::{Path, PathBuf};
+use tokio::io::AsyncWriteExt;
+
+use crate::features::catalog::get_catalog_with_handle;
+use crate::features::connection::get_storage_manager_with_handle;
+use crate::features::settings::{get_settings_store_with_handle, http_client};
+use crate::features::shared::{
+    ensure_staging_space, staging_file, MessageKey, OperationStage, ProgressMessage,
+    StagedArtifacts, StorageError, UploadResultResponse,
+};
+use crate::features::upload::{
+    ensure_upload_confirmed, report_rollback, upload_file_with_progress,
+};
+
+const DEFAULT_URL_FILE_NAME: &str = "download";
+
+/// Last path segment of the URL, used as the uploaded file's name.
+fn file_name_from_url(url: &reqwest::Url) -> String {
+    url.path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| segment.replace(['/', '\\'], "_"))
+        .unwrap_or_else(|| DEFAULT_URL_FILE_NAME.to_string())
+}
+
+/// Downloads `url` to `destination`, checking the upload size limit before
+/// the body is fetched when the server announces its length, after it
+/// otherwise.
+async fn fetch_to_file(
+    operation_id: &str,
+    url: reqwest::Url,
+    destination: &Path,
+    confirmation_token: Option<&str>,
+    app_handle: &tauri::AppHandle,
+) -> Result<(), StorageError> {
+    let manager = get_storage_manager_with_handle(Some(app_handle.clone())).await?;
+
+    let settings = get_settings_store_with_handle(app_handle)
+        .await?
+        .get()
+        .await;
+    let response = http_client(settings.proxy.as_ref())?
+        .get(url.clone())
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| StorageError::Download(format!("Failed to fetch {}: {}", url, e)))?;
+    let total = response.content_length().map(|length| length as usize);
+    if let Some(total) = total {
+        ensure_upload_confirmed(app_handle, url.as_str(), total as u64, confirmation_token).await?;
+    }
+    if let (Some(total), Some(dir)) = (total, destination.parent()) {
+        ensure_staging_space(dir, total as u64)?;
+    }
+
+    let mut file = tokio::fs::File::create(destination)
+        .await
+        .map_err(|e| StorageError::Io(e.to_string()))?;
+    let mut stream = response.bytes_stream();
+    let mut fetched = 0;
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| StorageError::Download(e.to_string()))?;
+        file.write_all(&chunk)
+            .await
+            .map_err(|e| StorageError::Io(e.to_string()))?;
+        fetched += chunk.len();
+
+        let progress = ProgressMessage::new(operation_id.to_string())
+            .with_stage(OperationStage::Fetching)
+            .with_bytes(fetched, total)
+            .with_message(MessageKey::FetchedBytes, vec![fetched.to_string()]);
+        manager.send_progress(operation_id, progress).await;
+    }
+
+    file.flush()
+        .await
+        .map_err(|e| StorageError::Io(e.to_string()))?;
+    if total.is_none() {
+        ensure_upload_confirmed(app_handle, url.as_str(), fetched as u64, confirmation_token)
+            .await?;
+    }
+    Ok(())
+}
+
+/// Fetches a remote resource into a staging file and uploads it, recording
+/// where it came from in the catalog.
+pub async fn upload_from_url_with_progress(
+    operation_id: String,
+    url: String,
+    confirmation_token: Option<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<UploadResultResponse, StorageError> {
+    let parsed = reqwest::Url::parse(&url)
+        .map_err(|e| StorageError::Configuration(format!("Invalid URL: {}", e)))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(StorageError::Configuration(
+            "Only http and https URLs can be uploaded".to_string(),
+        ));
+    }
+
+    // A directory per fetch keeps the original file name for the upload
+    let staging_dir: PathBuf = staging_file(&app_handle).await?;
+    std::fs::create_dir_all(&staging_dir).map_err(|e| StorageError::Io(e.to_string()))?;
+    let mut artifacts = StagedArtifacts::default();
+    artifacts.track(staging_dir.clone());
+    let staged_file = staging_dir.join(file_name_from_url(&parsed));
+
+    let result = match fetch_to_file(
+        &operation_id,
+        parsed,
+        &staged_file,
+        confirmation_token.as_deref(),
+        &app_handle,
+    )
+    .await
+    {
+        Ok(()) => {
+            upload_file_with_progress(
+                operation_id.clone(),
+                staged_file,
+                None,
+                None,
+                app_handle.clone(),
+            )
+            .await
+        }
+        Err(e) => Err(e),
+    };
+    let rolled_back = artifacts.clean_up();
+    let manager = get_storage_manager_with_handle(Some(app_handle.clone())).await?;
+    let result = report_rollback(
+        &manager,
+        &operation_id,
+        result.map_err(|e| (e, rolled_back)),
+    )
+    .await?;
+
+    let catalog = get_catalog_with_handle(&app_handle).await?;
+    catalog
+        .upsert(&result.cid, |entry| entry.source_url = Some(url))
+        .await?;
+
+    Ok(result)
+}
@@ -0,0 +1,12 @@
+use crate::features::shared::{map_storage_error, CommandError};
+use crate::features::uptime::{get_uptime_tracker_with_handle, UptimeStats};
+use tauri::AppHandle;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_uptime_stats(app_handle: AppHandle) -> Result<UptimeStats, CommandError> {
+    let tracker = get_uptime_tracker_with_handle(&app_handle)
+        .await
+        .map_err(map_storage_error)?;
+    Ok(tracker.stats().await)
+}
@@ -0,0 +1,5 @@
+pub mod commands;
+pub mod uptime;
+
+pub use commands::*;
+pub use uptime::*;
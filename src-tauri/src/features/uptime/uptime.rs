@@ -0,0 +1,137 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::AppHandle;
+use tokio::sync::{Mutex, OnceCell};
+
+use crate::features::connection::STORAGE_MANAGER;
+use crate::features::shared::{
+    app_data_file, read_json_file, write_json_file, StorageConnectionStatus, StorageError,
+};
+
+pub const UPTIME_FILE_NAME: &str = "uptime_stats.json";
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct UptimeRecord {
+    /// Milliseconds since the Unix epoch when tracking started.
+    tracking_since: i64,
+    tracked_secs: u64,
+    connected_secs: u64,
+    /// Times the node came up after having been down, app launches included.
+    restarts: u64,
+    #[serde(skip)]
+    was_running: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct UptimeStats {
+    pub tracking_since: i64,
+    pub tracked_secs: u64,
+    pub connected_secs: u64,
+    pub restarts: u64,
+    /// Fraction of tracked time spent connected, from 0 to 1.
+    pub availability: f64,
+}
+
+/// Node availability since install, sampled while the app is running.
+///
+/// Time the app isn't running isn't tracked, so availability reflects how
+/// reliably the node is connected while it is meant to be.
+pub struct UptimeTracker {
+    record: Mutex<UptimeRecord>,
+    uptime_file: PathBuf,
+}
+
+impl UptimeTracker {
+    pub fn load(uptime_file: PathBuf) -> Self {
+        let record = read_json_file(&uptime_file).unwrap_or_else(|| UptimeRecord {
+            tracking_since: chrono::Utc::now().timestamp_millis(),
+            ..Default::default()
+        });
+        Self {
+            record: Mutex::new(record),
+            uptime_file,
+        }
+    }
+
+    /// Accounts `elapsed` to the given status.
+    pub async fn sample(
+        &self,
+        status: StorageConnectionStatus,
+        elapsed: Duration,
+    ) -> Result<(), StorageError> {
+        let mut record = self.record.lock().await;
+        // A degraded node is still running, it just isn't reachable right now
+        let running = matches!(
+            status,
+            StorageConnectionStatus::Connected | StorageConnectionStatus::Degraded
+        );
+        record.tracked_secs += elapsed.as_secs();
+        if status == StorageConnectionStatus::Connected {
+            record.connected_secs += elapsed.as_secs();
+        }
+        if running && !record.was_running {
+            record.restarts += 1;
+        }
+        record.was_running = running;
+
+        write_json_file(&self.uptime_file, &*record)
+    }
+
+    pub async fn stats(&self) -> UptimeStats {
+        let record = self.record.lock().await;
+        let availability = if record.tracked_secs == 0 {
+            0.0
+        } else {
+            record.connected_secs as f64 / record.tracked_secs as f64
+        };
+        UptimeStats {
+            tracking_since: record.tracking_since,
+            tracked_secs: record.tracked_secs,
+            connected_secs: record.connected_secs,
+            restarts: record.restarts,
+            availability,
+        }
+    }
+}
+
+// Global uptime tracker instance
+pub static UPTIME_TRACKER: OnceCell<Arc<UptimeTracker>> = OnceCell::const_new();
+
+pub async fn get_uptime_tracker_with_handle(
+    app_handle: &AppHandle,
+) -> Result<Arc<UptimeTracker>, StorageError> {
+    UPTIME_TRACKER
+        .get_or_try_init(|| async {
+            let uptime_file = app_data_file(app_handle, UPTIME_FILE_NAME)?;
+            Ok(Arc::new(UptimeTracker::load(uptime_file)))
+        })
+        .await
+        .map(Arc::clone)
+}
+
+/// Samples the node status once a minute for the uptime statistics.
+pub async fn run_uptime_tracking(app_handle: AppHandle) {
+    let tracker = match get_uptime_tracker_with_handle(&app_handle).await {
+        Ok(tracker) => tracker,
+        Err(e) => {
+            eprintln!("Failed to load uptime statistics: {}", e);
+            return;
+        }
+    };
+
+    loop {
+        tokio::time::sleep(SAMPLE_INTERVAL).await;
+
+        let status = match STORAGE_MANAGER.get() {
+            Some(manager) => manager.get_status().await,
+            None => StorageConnectionStatus::Disconnected,
+        };
+        if let Err(e) = tracker.sample(status, SAMPLE_INTERVAL).await {
+            eprintln!("Failed to record uptime: {}", e);
+        }
+    }
+}
@@ -3,13 +3,166 @@ mod features;
 use tauri::Manager;
 use tauri_plugin_fs::FsExt;
 
+/// Where debug builds write the TypeScript definitions of the commands and
+/// event payloads, relative to `src-tauri`.
+#[cfg(all(debug_assertions, not(mobile)))]
+const BINDINGS_PATH: &str = "../src/bindings.ts";
+
+/// Registers the commands, and the types the frontend only sees in events
+/// or error messages, for both invoking and generating bindings.
+fn bindings_builder() -> tauri_specta::Builder<tauri::Wry> {
+    tauri_specta::Builder::<tauri::Wry>::new()
+        .commands(tauri_specta::collect_commands![
+            features::connection::get_node_status,
+            features::upload::upload_file_to_storage,
+            features::upload::upload_from_url,
+            features::upload::upload_clipboard,
+            features::download::download_file_from_storage,
+            features::download::download_to_default,
+            features::download::verify_sidecar,
+            features::connection::connect_to_peer,
+            features::connection::get_node_info,
+            features::connection::start_node,
+            features::connection::stop_node,
+            features::queue::enqueue_upload,
+            features::queue::enqueue_download,
+            features::queue::get_transfer_queue,
+            features::queue::remove_queued_transfer,
+            features::queue::force_start,
+            features::queue::pause_all_transfers,
+            features::queue::resume_all_transfers,
+            features::settings::get_settings,
+            features::settings::update_settings,
+            features::settings::set_locale,
+            features::cleanup::preview_cleanup,
+            features::cleanup::run_cleanup,
+            features::storage::get_storage_usage,
+            features::storage::has_block,
+            features::storage::has_dataset_locally,
+            features::storage::check_repo,
+            features::storage::get_storage_breakdown,
+            features::storage::delete_dataset,
+            features::audit::export_audit_log,
+            features::connection::get_recovery_report,
+            features::crash::get_last_crash_report,
+            features::connection::get_operation_result,
+            features::connection::get_operation_log,
+            features::connection::cancel_all_operations,
+            features::connection::cancel_operation,
+            features::connection::set_max_peers,
+            features::connection::get_node_api_endpoint,
+            features::migration::get_data_version,
+            features::onboarding::run_onboarding_checks,
+            features::rpc::get_rpc_api_info,
+            features::system::get_network_status,
+            features::system::override_metered_policy,
+            features::system::get_power_status,
+            features::peers::find_providers,
+            features::peers::announce_dataset,
+            features::peers::get_local_peers,
+            features::peers::connect_local_peer,
+            features::peers::get_node_addresses,
+            features::peers::measure_peer_throughput,
+            features::identity::export_identity,
+            features::identity::import_identity,
+            features::identity::rotate_identity,
+            features::identity::sign_manifest,
+            features::identity::verify_manifest,
+            features::catalog::get_catalog,
+            features::catalog::get_catalog_entry,
+            features::catalog::set_dataset_expiry,
+            features::catalog::set_note,
+            features::catalog::search_catalog,
+            features::catalog::import_cid_list,
+            features::catalog::export_catalog,
+            features::catalog::import_catalog,
+            features::catalog::check_catalog_consistency,
+            features::catalog::resolve_catalog_mismatch,
+            features::catalog::get_trash,
+            features::catalog::restore_from_trash,
+            features::catalog::empty_trash,
+            features::recent::get_recent_items,
+            features::recent::mark_item_opened,
+            features::uptime::get_uptime_stats,
+            features::bandwidth::get_bandwidth_history,
+            features::bandwidth::get_bandwidth_status,
+            features::shares::create_share,
+            features::shares::list_shares,
+            features::shares::revoke_share,
+            features::shares::export_share_file,
+            features::shares::import_share_file,
+            features::shares::take_opened_share_file,
+            features::contacts::list_contacts,
+            features::contacts::add_contact,
+            features::contacts::update_contact,
+            features::contacts::remove_contact,
+            features::contacts::get_own_public_key,
+            features::contacts::share_with_contact,
+            features::contacts::decrypt_shared_file,
+            features::offers::send_offer,
+            features::offers::list_offers,
+            features::offers::accept_offer,
+            features::offers::decline_offer,
+            features::confirmation::confirm_action,
+        ])
+        .typ::<features::shared::ProgressMessage>()
+        .typ::<features::shared::MessageKey>()
+        .typ::<features::shared::StorageError>()
+        .typ::<features::events::TransferSummary>()
+        .typ::<features::events::NodeHealth>()
+        .typ::<features::events::QuotaWarning>()
+        .typ::<features::events::PeerEvent>()
+        .typ::<features::events::SystemEvent>()
+        .typ::<features::events::ClipboardCid>()
+        .typ::<features::connection::RecoveryReport>()
+        .typ::<features::bandwidth::BandwidthStatus>()
+        .typ::<features::catalog::CatalogReconciliation>()
+        .typ::<features::peers::LocalPeer>()
+        .typ::<features::offers::Offer>()
+        .typ::<features::system::NetworkStatus>()
+        .typ::<features::system::PowerStatus>()
+        .typ::<features::onboarding::OnboardingReport>()
+        .typ::<features::shares::ShareFile>()
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // Servers can run the node without opening a window
+    if features::cli::headless_requested() {
+        std::process::exit(features::cli::run_headless());
+    }
+
+    let bindings = bindings_builder();
+    #[cfg(all(debug_assertions, not(mobile)))]
+    bindings
+        .export(
+            specta_typescript::Typescript::default()
+                // The generated globals aren't all used, which the frontend's
+                // unused-locals check would reject
+                .header("// @ts-nocheck")
+                .bigint(specta_typescript::BigIntExportBehavior::Number),
+            BINDINGS_PATH,
+        )
+        .expect("Failed to export TypeScript bindings");
+
     tauri::Builder::default()
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_opener::init())
         .setup(|app| {
+            // First, so whatever goes wrong after this is reported next time
+            if let Err(e) = crate::features::crash::start_crash_reporting(app.handle()) {
+                eprintln!("Failed to set up crash reporting: {}", e);
+            }
+
+            // Bring data files up to date before any store reads them. Stores
+            // would misread files left half migrated or written by a newer
+            // version and save over them, so don't start at all.
+            if let Err(e) = crate::features::migration::run_migrations(app.handle()) {
+                eprintln!("Failed to migrate app data: {}", e);
+                return Err(Box::new(e));
+            }
+
             let fs = app.fs_scope();
 
             if let Ok(app_data_dir) = app.path().app_data_dir() {
@@ -29,28 +182,203 @@ pub fn run() {
                 );
             }
 
+            // Reopen the window where it was left, before it gets painted
+            if let Err(e) = tauri::async_runtime::block_on(
+                crate::features::settings::restore_window_state(app.handle()),
+            ) {
+                eprintln!("Failed to restore window state: {}", e);
+            }
+
+            // Nothing is transferring yet, whatever is staged was orphaned
+            // by an earlier run
+            match tauri::async_runtime::block_on(crate::features::shared::clean_staging_dir(
+                app.handle(),
+            )) {
+                Ok(removed) if removed > 0 => {
+                    println!("Removed {} orphaned staging file(s)", removed)
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!("Failed to clean staging directory: {}", e),
+            }
+
+            // The managed downloads directory is opened from the frontend
+            let app_handle = app.handle().clone();
+            crate::features::tasks::task_manager().spawn("load-settings", async move {
+                match crate::features::settings::get_settings_store_with_handle(&app_handle).await {
+                    Ok(store) => {
+                        let settings = store.get().await;
+                        crate::features::shared::set_current_locale(
+                            settings.locale.unwrap_or_default(),
+                        );
+                        crate::features::download::allow_downloads_dir(&app_handle, &settings)
+                    }
+                    Err(e) => eprintln!("Failed to load settings: {}", e),
+                }
+            });
+
             // Initialize the storage node on app startup
             let app_handle = app.handle().clone();
-            tauri::async_runtime::spawn(async move {
-                if let Err(e) =
-                    crate::features::connection::get_storage_manager_with_handle(Some(app_handle))
-                        .await
+            crate::features::tasks::task_manager().spawn("node-startup", async move {
+                // Before the node grabs its port on the first launch
+                crate::features::onboarding::run_first_launch_checks(&app_handle).await;
+
+                if let Err(e) = crate::features::connection::get_storage_manager_with_handle(Some(
+                    app_handle.clone(),
+                ))
+                .await
                 {
                     eprintln!("Failed to initialize storage manager: {}", e);
                 }
+
+                // Catch datasets lost or left behind while the app was closed
+                crate::features::tasks::task_manager().spawn(
+                    "startup-reconciliation",
+                    crate::features::catalog::run_startup_reconciliation(app_handle.clone()),
+                );
+
+                // Launched by double-clicking a share file
+                for path in crate::features::shares::share_files_in_args() {
+                    crate::features::shares::open_share_file(&app_handle, &path).await;
+                }
             });
 
+            // Resume transfers left in the queue by a previous run
+            crate::features::tasks::task_manager().spawn(
+                "transfer-queue",
+                crate::features::queue::run_transfer_queue(app.handle().clone()),
+            );
+
+            // Transfer progress for the UI
+            crate::features::tasks::task_manager().spawn(
+                "progress-events",
+                crate::features::events::run_progress_events(app.handle().clone()),
+            );
+
+            // Aggregate stats for the status bar
+            crate::features::tasks::task_manager().spawn(
+                "transfer-summary",
+                crate::features::queue::run_transfer_summary(app.handle().clone()),
+            );
+
+            crate::features::tasks::task_manager().spawn(
+                "cleanup-schedule",
+                crate::features::cleanup::run_cleanup_schedule(app.handle().clone()),
+            );
+
+            // Delete datasets whose catalog expiry has passed
+            crate::features::tasks::task_manager().spawn(
+                "expiry-schedule",
+                crate::features::catalog::run_expiry_schedule(app.handle().clone()),
+            );
+
+            // Recover from laptop sleep and network switches
+            crate::features::tasks::task_manager().spawn(
+                "system-watcher",
+                crate::features::system::run_system_watcher(app.handle().clone()),
+            );
+
+            // Offer copied CIDs for download, when opted in
+            crate::features::tasks::task_manager().spawn(
+                "clipboard-watcher",
+                crate::features::system::run_clipboard_watcher(app.handle().clone()),
+            );
+
+            // Show transfer progress while the window is minimized
+            crate::features::tasks::task_manager().spawn(
+                "taskbar-progress",
+                crate::features::system::run_taskbar_progress(app.handle().clone()),
+            );
+
+            // Keeps the UI's peer list current
+            crate::features::tasks::task_manager().spawn(
+                "peer-polling",
+                crate::features::peers::run_peer_polling(app.handle().clone()),
+            );
+
+            // Find other nodes on the LAN, when opted in
+            crate::features::tasks::task_manager().spawn(
+                "local-discovery",
+                crate::features::peers::run_local_discovery(app.handle().clone()),
+            );
+
+            // Catch a node that stopped answering while marked connected
+            crate::features::tasks::task_manager().spawn(
+                "connection-watchdog",
+                crate::features::connection::run_connection_watchdog(app.handle().clone()),
+            );
+
+            // Stop the node when it sits idle, if configured
+            crate::features::tasks::task_manager().spawn(
+                "inactivity-policy",
+                crate::features::connection::run_inactivity_policy(app.handle().clone()),
+            );
+
+            // Availability statistics for the node
+            crate::features::tasks::task_manager().spawn(
+                "uptime-tracking",
+                crate::features::uptime::run_uptime_tracking(app.handle().clone()),
+            );
+
+            // Opt-in local API for scripts and other apps
+            crate::features::tasks::task_manager().spawn(
+                "rpc-api",
+                crate::features::rpc::run_rpc_api(app.handle().clone()),
+            );
+
+            // Opt-in inbox for files other users offer us
+            crate::features::tasks::task_manager().spawn(
+                "offer-inbox",
+                crate::features::offers::run_offer_inbox(app.handle().clone()),
+            );
+
             Ok(())
         })
-        .invoke_handler(tauri::generate_handler![
-            features::connection::get_node_status,
-            features::upload::upload_file_to_storage,
-            features::download::download_file_from_storage,
-            features::connection::connect_to_peer,
-            features::connection::get_node_info,
-            features::connection::start_node,
-            features::connection::stop_node
-        ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .invoke_handler(bindings.invoke_handler())
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app_handle, event| {
+            if let tauri::RunEvent::WindowEvent {
+                label,
+                event: tauri::WindowEvent::CloseRequested { .. },
+                ..
+            } = &event
+            {
+                if label == crate::features::settings::MAIN_WINDOW_LABEL {
+                    if let Err(e) = tauri::async_runtime::block_on(
+                        crate::features::settings::save_window_state(app_handle),
+                    ) {
+                        eprintln!("Failed to save window state: {}", e);
+                    }
+                }
+            }
+
+            // macOS hands over double-clicked files as an event instead
+            #[cfg(any(target_os = "macos", target_os = "ios"))]
+            if let tauri::RunEvent::Opened { urls } = &event {
+                for url in urls {
+                    if let Ok(path) = url.to_file_path() {
+                        let app_handle = app_handle.clone();
+                        crate::features::tasks::task_manager().spawn(
+                            &format!("open-share-file-{}", path.display()),
+                            async move {
+                                crate::features::shares::open_share_file(&app_handle, &path).await;
+                            },
+                        );
+                    }
+                }
+            }
+
+            if let tauri::RunEvent::Exit = event {
+                // Before the node goes, so no task trips over it stopping
+                tauri::async_runtime::block_on(crate::features::tasks::task_manager().shutdown());
+                // Stop the node cleanly so the next launch doesn't treat this
+                // run as a crash
+                if let Some(manager) = crate::features::connection::STORAGE_MANAGER.get() {
+                    if let Err(e) = tauri::async_runtime::block_on(manager.stop_node()) {
+                        eprintln!("Failed to stop node on exit: {}", e);
+                    }
+                }
+                crate::features::crash::clear_app_running_marker();
+            }
+        });
 }
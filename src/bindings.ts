@@ -0,0 +1,1791 @@
+// @ts-nocheck
+// This file was generated by [tauri-specta](https://github.com/oscartbeaumont/tauri-specta). Do not edit this file manually.
+
+/** user-defined commands **/
+
+
+export const commands = {
+async getNodeStatus() : Promise<Result<StorageConnectionStatus, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_node_status") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Uploads a file, or asks for confirmation first when it is above the
+ * configured size limit; confirm by calling again with the returned token.
+ */
+async uploadFileToStorage(filePath: string, blockSize: number | null, ttlSecs: number | null, confirmationToken: string | null) : Promise<Result<UploadFileResult, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("upload_file_to_storage", { filePath, blockSize, ttlSecs, confirmationToken }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetches `url` and uploads the result, returning the new dataset's CID.
+ *
+ * Content above the configured size limit fails with
+ * `UploadConfirmationRequired`, confirm by calling again with its token.
+ */
+async uploadFromUrl(url: string, confirmationToken: string | null) : Promise<Result<UploadResultResponse, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("upload_from_url", { url, confirmationToken }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Uploads whatever is on the clipboard, an image as PNG or text as .txt.
+ */
+async uploadClipboard() : Promise<Result<ClipboardUploadResponse, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("upload_clipboard") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async downloadFileFromStorage(cid: string, savePath: string) : Promise<Result<DownloadResultResponse, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("download_file_from_storage", { cid, savePath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Downloads into the managed downloads directory, named from the manifest.
+ */
+async downloadToDefault(cid: string) : Promise<Result<DownloadResultResponse, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("download_to_default", { cid }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Checks a file against its `.sha256`/`.blake3` sidecar, given either path.
+ */
+async verifySidecar(path: string) : Promise<Result<SidecarVerification, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("verify_sidecar", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async connectToPeer(peerId: string, addresses: string[]) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("connect_to_peer", { peerId, addresses }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getNodeInfo() : Promise<Result<NodeInfo, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_node_info") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async startNode() : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("start_node") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async stopNode() : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("stop_node") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Queues an upload. Files above the configured size limit fail with
+ * `UploadConfirmationRequired` instead, confirm by calling again with its
+ * token.
+ */
+async enqueueUpload(filePath: string, ttlSecs: number | null, confirmationToken: string | null) : Promise<Result<string, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("enqueue_upload", { filePath, ttlSecs, confirmationToken }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Queues a download, or reports where the CID already exists unless `force` is set.
+ */
+async enqueueDownload(cid: string, savePath: string, force: boolean | null) : Promise<Result<EnqueueDownloadResult, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("enqueue_download", { cid, savePath, force }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getTransferQueue() : Promise<Result<TransferJob[], CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_transfer_queue") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async removeQueuedTransfer(operationId: string) : Promise<Result<boolean, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("remove_queued_transfer", { operationId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async forceStart(operationId: string) : Promise<Result<boolean, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("force_start", { operationId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Stops running transfers and holds back the queue until `resume_all_transfers`.
+ *
+ * Interrupted queued transfers start over once resumed, direct transfers
+ * are cancelled.
+ */
+async pauseAllTransfers() : Promise<Result<number, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("pause_all_transfers") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async resumeAllTransfers() : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("resume_all_transfers") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getSettings() : Promise<Result<AppSettings, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_settings") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateSettings(settings: AppSettings) : Promise<Result<AppSettings, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_settings", { settings }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Switches the language of messages produced by the backend.
+ */
+async setLocale(lang: string) : Promise<Result<Locale, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_locale", { lang }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async previewCleanup() : Promise<Result<CleanupReport, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("preview_cleanup") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async runCleanup(confirmationToken: string) : Promise<Result<CleanupReport, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("run_cleanup", { confirmationToken }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getStorageUsage() : Promise<Result<StorageUsage, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_storage_usage") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async hasBlock(cid: string) : Promise<Result<boolean, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("has_block", { cid }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async hasDatasetLocally(cid: string) : Promise<Result<boolean, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("has_dataset_locally", { cid }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async checkRepo(repair: boolean) : Promise<Result<RepoCheckReport, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_repo", { repair }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getStorageBreakdown() : Promise<Result<StorageBreakdown, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_storage_breakdown") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Moves a dataset to the trash, its blocks are removed once the retention
+ * period runs out or the trash is emptied.
+ */
+async deleteDataset(cid: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_dataset", { cid }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Copies the audit log to `path`, reporting whether its hash chain is intact.
+ */
+async exportAuditLog(path: string) : Promise<Result<AuditLogExport, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_audit_log", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getRecoveryReport() : Promise<Result<RecoveryReport | null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_recovery_report") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * What ended the previous run, `None` if it exited cleanly.
+ */
+async getLastCrashReport() : Promise<Result<CrashReport | null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_last_crash_report") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Looks up a recently finished transfer, `None` if it is unknown or still running.
+ */
+async getOperationResult(operationId: string) : Promise<Result<OperationResult | null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_operation_result", { operationId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Log lines of a running or recently finished transfer.
+ */
+async getOperationLog(operationId: string) : Promise<Result<OperationLogLine[] | null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_operation_log", { operationId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Cancels every running transfer, returns how many were cancelled.
+ */
+async cancelAllOperations() : Promise<Result<number, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("cancel_all_operations") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Cancels one running transfer, returns whether it was running.
+ *
+ * A cancelled queued transfer leaves the queue for good.
+ */
+async cancelOperation(operationId: string) : Promise<Result<boolean, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("cancel_operation", { operationId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Changes the embedded node's peer limit and restarts it in place.
+ */
+async setMaxPeers(maxPeers: number) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_max_peers", { maxPeers }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Base URL of the node's REST API, for driving it with curl or the codex CLI.
+ *
+ * Only a remote node has one: codex-bindings runs the embedded node
+ * in-process without its REST server and offers no API port to configure.
+ */
+async getNodeApiEndpoint() : Promise<Result<string, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_node_api_endpoint") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getDataVersion() : Promise<Result<DataVersionInfo, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_data_version") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async runOnboardingChecks() : Promise<Result<OnboardingReport, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("run_onboarding_checks") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getRpcApiInfo() : Promise<Result<RpcApiInfo, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_rpc_api_info") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getNetworkStatus() : Promise<Result<NetworkStatus, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_network_status") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Ignores the metered policy until the connection changes.
+ */
+async overrideMeteredPolicy(overridden: boolean) : Promise<Result<NetworkStatus, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("override_metered_policy", { overridden }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getPowerStatus() : Promise<Result<PowerStatus, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_power_status") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Asks the DHT which peers provide a dataset.
+ *
+ * Only nodes with `NodeInfo::provider_lookup` set can, the others fail
+ * with `Unsupported`.
+ */
+async findProviders(cid: string, limit: number | null) : Promise<Result<ProviderInfo[], CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("find_providers", { cid, limit }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Makes a locally stored dataset discoverable without waiting for the
+ * node's periodic announcements.
+ *
+ * Only nodes with `NodeInfo::announce` set can, the others fail with
+ * `Unsupported`.
+ */
+async announceDataset(cid: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("announce_dataset", { cid }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Peers found on the local network through mDNS.
+ */
+async getLocalPeers() : Promise<Result<LocalPeer[], CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_local_peers") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Dials a peer found on the local network.
+ */
+async connectLocalPeer(peerId: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("connect_local_peer", { peerId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Addresses to share so other peers can dial this node.
+ */
+async getNodeAddresses() : Promise<Result<NodeAddresses, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_node_addresses") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Measures how fast a dataset the peer provides can be fetched, reading
+ * at most `sample_size` bytes.
+ */
+async measurePeerThroughput(peerId: string, cid: string, sampleSize: number | null) : Promise<Result<PeerThroughput, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("measure_peer_throughput", { peerId, cid, sampleSize }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async exportIdentity(path: string, confirmationToken: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_identity", { path, confirmationToken }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async importIdentity(path: string, confirmationToken: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("import_identity", { path, confirmationToken }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async rotateIdentity(confirmationToken: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("rotate_identity", { confirmationToken }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Signs a locally stored dataset with the node key and keeps the signature
+ * in the catalog, ready to be shared alongside the CID.
+ */
+async signManifest(cid: string) : Promise<Result<ManifestSignature, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("sign_manifest", { cid }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async verifyManifest(cid: string, signature: string, peerId: string) : Promise<Result<boolean, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("verify_manifest", { cid, signature, peerId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getCatalog() : Promise<Result<CatalogEntry[], CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_catalog") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getCatalogEntry(cid: string) : Promise<Result<CatalogEntry | null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_catalog_entry", { cid }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Sets or clears when the local copy of a catalogued dataset moves to the
+ * trash.
+ */
+async setDatasetExpiry(cid: string, expiresAt: number | null) : Promise<Result<CatalogEntry, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_dataset_expiry", { cid, expiresAt }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Sets or clears the free-text note kept about a catalogued dataset.
+ */
+async setNote(cid: string, text: string | null) : Promise<Result<CatalogEntry, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_note", { cid, text }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Catalogued datasets whose CID, name or note contains `query`.
+ */
+async searchCatalog(query: string) : Promise<Result<CatalogEntry[], CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("search_catalog", { query }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Catalogs a published list of CIDs, optionally queueing downloads to mirror them.
+ */
+async importCidList(path: string, mirror: boolean | null) : Promise<Result<CidListImport, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("import_cid_list", { path, mirror }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Writes the catalog and share records, not the blocks, to a file for
+ * moving to another machine. Returns how many entries were written.
+ */
+async exportCatalog(path: string) : Promise<Result<number, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_catalog", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Merges a file written by `export_catalog` into this machine's catalog.
+ */
+async importCatalog(path: string) : Promise<Result<CatalogArchiveImport, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("import_catalog", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Lists where the catalog and the repo disagree, as done at launch.
+ */
+async checkCatalogConsistency() : Promise<Result<CatalogReconciliation, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_catalog_consistency") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Re-pins, adopts or purges a dataset flagged by the consistency check.
+ * Purging needs a confirmation token.
+ */
+async resolveCatalogMismatch(cid: string, action: ReconcileAction, confirmationToken: string | null) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("resolve_catalog_mismatch", { cid, action, confirmationToken }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getTrash() : Promise<Result<CatalogEntry[], CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_trash") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async restoreFromTrash(cid: string) : Promise<Result<CatalogEntry, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("restore_from_trash", { cid }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Deletes everything in the trash now, returning the removed CIDs.
+ */
+async emptyTrash(confirmationToken: string) : Promise<Result<string[], CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("empty_trash", { confirmationToken }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getRecentItems(limit: number | null) : Promise<Result<RecentItem[], CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_recent_items", { limit }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Lets the frontend note that the user opened a downloaded file.
+ */
+async markItemOpened(cid: string, path: string | null) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("mark_item_opened", { cid, path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getUptimeStats() : Promise<Result<UptimeStats, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_uptime_stats") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Daily transfer totals of the last `days` days, oldest first.
+ */
+async getBandwidthHistory(days: number) : Promise<Result<DailyBandwidth[], CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_bandwidth_history", { days }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * This month's usage against the monthly cap.
+ */
+async getBandwidthStatus() : Promise<Result<BandwidthStatus, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_bandwidth_status") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Names a locally stored dataset, optionally until `expires_at` in
+ * milliseconds since the Unix epoch.
+ */
+async createShare(name: string, cid: string, expiresAt: number | null) : Promise<Result<Share, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("create_share", { name, cid, expiresAt }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listShares() : Promise<Result<Share[], CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_shares") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Removes a share and the local copy of its dataset.
+ */
+async revokeShare(name: string, confirmationToken: string) : Promise<Result<Share, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("revoke_share", { name, confirmationToken }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Writes a `.dexshare` file for a catalogued dataset.
+ */
+async exportShareFile(cid: string, path: string) : Promise<Result<ShareFile, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_share_file", { cid, path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Reads a `.dexshare` file so its dataset is ready to download.
+ */
+async importShareFile(path: string) : Promise<Result<ShareFile, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("import_share_file", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * The share file the app was opened with, once.
+ */
+async takeOpenedShareFile() : Promise<Result<ShareFile | null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("take_opened_share_file") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listContacts() : Promise<Result<Contact[], CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_contacts") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async addContact(contact: Contact) : Promise<Result<Contact, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("add_contact", { contact }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateContact(contact: Contact) : Promise<Result<Contact, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_contact", { contact }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async removeContact(name: string) : Promise<Result<Contact, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("remove_contact", { name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Our public key, for contacts to encrypt what they share with us.
+ */
+async getOwnPublicKey() : Promise<Result<string, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_own_public_key") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Uploads a file only the named contact can decrypt and writes the
+ * `.dexshare` file to send them.
+ */
+async shareWithContact(name: string, filePath: string, sharePath: string) : Promise<Result<ShareFile, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("share_with_contact", { name, filePath, sharePath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Decrypts a downloaded file a contact encrypted for us.
+ */
+async decryptSharedFile(path: string, outputPath: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("decrypt_shared_file", { path, outputPath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Offers a stored dataset to a peer, returning the offer manifest's CID.
+ */
+async sendOffer(peerId: string, cid: string, message: string | null) : Promise<Result<string, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("send_offer", { peerId, cid, message }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listOffers() : Promise<Result<Offer[], CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_offers") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Queues the download of an offered dataset.
+ */
+async acceptOffer(id: string, savePath: string) : Promise<Result<EnqueueDownloadResult, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("accept_offer", { id, savePath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async declineOffer(id: string) : Promise<Result<Offer, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("decline_offer", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Asks the user to confirm a protected action, the returned token has to
+ * be passed to the command carrying it out.
+ */
+async confirmAction(action: ProtectedAction) : Promise<Result<string, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("confirm_action", { action }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+}
+}
+
+/** user-defined events **/
+
+
+
+/** user-defined constants **/
+
+
+
+/** user-defined types **/
+
+export type AppSettings = { transfer_window: TransferWindow | null; 
+/**
+ * Directory managed by the app for downloaded files, `None` for the default.
+ * Has to be picked in a dialog, it is added to the fs scope.
+ */
+downloads_dir: string | null; cleanup_policy: CleanupPolicy | null; 
+/**
+ * Fractions of the storage quota at which a warning event is emitted.
+ */
+quota_warning_thresholds: number[]; rpc_api: RpcApiSettings | null; offer_inbox: OfferInboxSettings | null; remote_node: RemoteNodeSettings | null; bookmarked_peers: BookmarkedPeer[]; metered_policy: MeteredPolicy | null; battery_policy: BatteryPolicy | null; 
+/**
+ * Seeding pauses once this many GiB were transferred in the calendar
+ * month, `None` for no cap.
+ */
+monthly_cap_gb: number | null; 
+/**
+ * Peer limit of the embedded node, `None` for the default.
+ */
+max_peers: number | null; private_network: PrivateNetworkSettings | null; 
+/**
+ * Find peers through the DHT; off means bookmarked peers only.
+ */
+dht_discovery: boolean; 
+/**
+ * Advertise the node and find other nodes on the LAN over mDNS.
+ */
+local_discovery: boolean; 
+/**
+ * Relays to reserve a slot on when the node can't be dialed directly,
+ * as multiaddresses ending in `/p2p/<peer id>`.
+ */
+relay_peers: string[]; 
+/**
+ * Replaces the announced addresses shown for a remote node; the
+ * embedded node can't be told to announce it.
+ */
+external_address: ExternalAddress | null; ip_family: IpFamily; proxy: ProxySettings | null; 
+/**
+ * Only serve stored content: no uploads, no fetching from the network.
+ */
+read_only: boolean; 
+/**
+ * Fetch without hosting: no uploads or announcements, and downloaded
+ * datasets aren't kept in the repo.
+ */
+download_only: boolean; 
+/**
+ * Concurrent range requests per download with a remote node; the
+ * embedded node's bindings only offer a sequential stream.
+ *
+ * Changes take effect on the next launch.
+ */
+download_parallelism: number; 
+/**
+ * Default dataset block size for uploads in bytes, `None` for the node's default.
+ */
+upload_block_size: number | null; 
+/**
+ * Cap in MiB on transfer data buffered in memory, `None` for no cap.
+ * Only remote node downloads buffer whole ranges; the embedded node
+ * and sequential streams write through as data arrives.
+ *
+ * Changes take effect on the next launch.
+ */
+transfer_memory_limit_mb: number | null; 
+/**
+ * Seconds without transfer progress before a transfer is reported as
+ * stalled; it fails after three times as long. `None` to wait forever.
+ */
+stall_timeout_secs: number | null; 
+/**
+ * Checks run on finished downloads.
+ */
+download_verification: DownloadVerification; 
+/**
+ * Checksum file written next to each download, `None` for none.
+ */
+download_checksum_sidecar: ChecksumAlgorithm | null; 
+/**
+ * Offer to download CIDs copied to the clipboard, off unless opted in.
+ */
+watch_clipboard: boolean; 
+/**
+ * Main window geometry per monitor layout, saved when the window closes.
+ */
+window_states: Partial<{ [key in string]: WindowGeometry }>; 
+/**
+ * Days a deleted dataset stays restorable before its blocks are removed.
+ */
+trash_retention_days: number; 
+/**
+ * Directory for intermediate transfer files, staged into a
+ * `storeman-staging` subdirectory; `None` for one inside the app data
+ * directory. Orphaned staged files are removed at every launch.
+ */
+staging_dir: string | null; 
+/**
+ * Run at a lower CPU priority so seeding doesn't compete with
+ * foreground work. Turning it off takes effect on the next launch.
+ */
+background_seeding: boolean; 
+/**
+ * Stop the embedded node after this many minutes without transfers,
+ * it starts again with the next one.
+ */
+auto_disconnect_minutes: number | null; 
+/**
+ * The first-launch checks passed once and aren't run on startup anymore.
+ */
+onboarding_completed: boolean; 
+/**
+ * Language of messages produced by the backend, `None` for English.
+ */
+locale: Locale | null; 
+/**
+ * Upload a copy of files that change mid-upload instead of failing.
+ */
+snapshot_changed_uploads: boolean; 
+/**
+ * Uploads above this many GiB need to be confirmed, `None` for no limit.
+ */
+upload_confirmation_gb: number | null }
+export type AuditLogExport = { entries: number; 
+/**
+ * Whether every entry still links to the one before it.
+ */
+chain_intact: boolean }
+export type BandwidthStatus = { 
+/**
+ * Bytes transferred so far in the current calendar month.
+ */
+month_bytes: number; cap_bytes: number | null; seeding_paused: boolean }
+/**
+ * What to hold back while running on a low battery.
+ */
+export type BatteryPolicy = { 
+/**
+ * The policy applies on battery below this charge, in percent.
+ */
+min_charge_percent: number; 
+/**
+ * Queued uploads wait until the machine is plugged in.
+ */
+pause_uploads: boolean; 
+/**
+ * Stop the node so it doesn't serve content to other peers.
+ */
+pause_seeding: boolean }
+/**
+ * A peer the node reconnects to after the system wakes up or the network changes.
+ */
+export type BookmarkedPeer = { peer_id: string; addresses: string[] }
+export type CatalogArchiveImport = { entries_added: number; 
+/**
+ * Entries already catalogued, which only had missing fields filled in.
+ */
+entries_merged: number; shares_added: number; 
+/**
+ * Shares whose name is already taken here.
+ */
+shares_skipped: string[] }
+/**
+ * What the app knows about a dataset it uploaded or downloaded.
+ */
+export type CatalogEntry = { cid: string; name: string | null; size: number | null; 
+/**
+ * Milliseconds since the Unix epoch.
+ */
+added_at: number; signature: ManifestSignature | null; 
+/**
+ * Where the content was fetched from when uploaded from a URL.
+ */
+source_url: string | null; origin: DatasetOrigin | null; 
+/**
+ * Last upload, download or open, in milliseconds since the Unix epoch.
+ */
+last_accessed_at: number | null; 
+/**
+ * Block size chosen for an upload, `None` for the node's default.
+ */
+block_size: number | null; 
+/**
+ * When the local copy moves to the trash, in milliseconds since the
+ * Unix epoch.
+ */
+expires_at: number | null; 
+/**
+ * Where the content was last uploaded from or downloaded to.
+ */
+local_path: string | null; 
+/**
+ * Set while the dataset sits in the trash, in milliseconds since the Unix epoch.
+ */
+trashed_at: number | null; 
+/**
+ * Set when a share of the dataset was revoked and its local copy
+ * deleted, in milliseconds since the Unix epoch.
+ */
+revoked_at: number | null; 
+/**
+ * Free text the user keeps about the content.
+ */
+note: string | null }
+/**
+ * Where the catalog and the repo disagree.
+ */
+export type CatalogReconciliation = { 
+/**
+ * Uploaded or downloaded datasets whose data is gone from the repo.
+ */
+missing_locally: string[]; 
+/**
+ * Datasets in the repo the catalog doesn't know about.
+ */
+unknown_to_catalog: string[] }
+export type CheckStatus = "Passed" | 
+/**
+ * The node can start but the user should know about it.
+ */
+"Warning" | 
+/**
+ * The node won't start until this is fixed.
+ */
+"Failed" | "Skipped"
+export type ChecksumAlgorithm = "Sha256" | "Blake3"
+export type CidListImport = { 
+/**
+ * CIDs added to or already in the catalog.
+ */
+imported: string[]; 
+/**
+ * Entries that didn't parse as a CID.
+ */
+skipped: string[]; 
+/**
+ * Operation ids of the downloads queued to mirror the list.
+ */
+queued: string[] }
+export type CleanupCandidate = { path: string; size: number; age_days: number }
+/**
+ * Deletes files the app downloaded into the downloads directory once they
+ * are older than `max_age_days`.
+ */
+export type CleanupPolicy = { max_age_days: number; 
+/**
+ * Also move downloaded datasets untouched for as long to the trash, so
+ * their repo blocks go once the trash retention runs out.
+ */
+drop_blocks: boolean }
+export type CleanupReport = { downloads_dir: string; candidates: CleanupCandidate[]; total_bytes: number; 
+/**
+ * Downloaded datasets moved to the trash, or that would be in a dry run.
+ */
+trashed_datasets: string[]; dry_run: boolean; errors: string[] }
+export type ClipboardCid = { cid: string }
+export type ClipboardContentType = "Image" | "Text"
+export type ClipboardUploadResponse = { upload: UploadResultResponse; content_type: ClipboardContentType }
+/**
+ * What a failed command returns to the frontend: the error to branch on
+ * and its message in the current locale to show.
+ */
+export type CommandError = { error: StorageError; message: string }
+/**
+ * How other peers reach the node.
+ */
+export type Connectivity = 
+/**
+ * The node announces a public address peers can dial.
+ */
+"Direct" | 
+/**
+ * Peers only reach the node through a relay, transfers are slower.
+ */
+"Relayed" | 
+/**
+ * Only private addresses are announced, the node is behind NAT and
+ * can only be dialed from the same network.
+ */
+"LocalOnly"
+export type Contact = { name: string; peer_id: string; 
+/**
+ * Encryption key content shared with the contact is encrypted for,
+ * an `age1...` X25519 recipient.
+ */
+public_key: string; addresses: string[]; 
+/**
+ * Peers the contact prefers to fetch through, listed in share files
+ * made for them.
+ */
+preferred_providers: ShareProvider[] }
+export type CrashReport = { 
+/**
+ * The previous run didn't get to exit cleanly.
+ */
+unclean_shutdown: boolean; 
+/**
+ * The panic that ended it, if it was one.
+ */
+panic: PanicReport | null }
+/**
+ * Bytes transferred on one local calendar day.
+ */
+export type DailyBandwidth = { date: string; uploaded_bytes: number; downloaded_bytes: number }
+export type DataVersionInfo = { current: number; latest: number; last_backup: string | null }
+export type DatasetOrigin = 
+/**
+ * Published from this machine.
+ */
+"Uploaded" | 
+/**
+ * Retrieved from the network.
+ */
+"Downloaded"
+export type DatasetRetention = 
+/**
+ * Published from here, the repo keeps it for others to fetch.
+ */
+"Pinned" | 
+/**
+ * Downloaded from the network, safe to drop and fetch again.
+ */
+"Cache" | 
+/**
+ * Not in the catalog, e.g. stored before the catalog existed.
+ */
+"Unknown"
+export type DatasetUsage = { cid: string; name: string | null; 
+/**
+ * `None` when the catalog doesn't know the dataset's size.
+ */
+size: number | null; retention: DatasetRetention; last_accessed_at: number | null }
+export type DownloadResultResponse = { cid: string; size: number; duration_ms: number; verified: boolean; filepath: string | null }
+/**
+ * How much checking a finished download gets.
+ */
+export type DownloadVerification = 
+/**
+ * Trust the node, for slow machines.
+ */
+"None" | 
+/**
+ * Compare the file size with the size the node reported.
+ */
+"Size" | 
+/**
+ * Size check plus re-hashing the file against the node's copy.
+ */
+"FullHash"
+/**
+ * How the content was encrypted before upload, for the recipient to undo.
+ */
+export type EncryptionHint = { algorithm: string; 
+/**
+ * Public key the content was encrypted for.
+ */
+recipient_key: string | null }
+export type EnqueueDownloadResult = { Queued: { operation_id: string } } | { AlreadyPresent: ExistingCopy }
+/**
+ * Where a copy of a dataset already exists on this machine.
+ */
+export type ExistingCopy = 
+/**
+ * A queued download of the same CID.
+ */
+{ Queued: { operation_id: string } } | 
+/**
+ * A file previously uploaded from or downloaded to this path.
+ */
+{ File: { path: string } } | 
+/**
+ * The blocks are in the node's repo, downloading only reads them back.
+ */
+"Repo"
+/**
+ * Public address the node is reachable at, for port-forwarded or VPS
+ * setups where it can't find out on its own.
+ */
+export type ExternalAddress = { ip: string; port: number }
+/**
+ * Address families the node has to listen on, and announces.
+ */
+export type IpFamily = "Ipv4" | "Ipv6" | "DualStack"
+export type JsonValue = null | boolean | number | string | JsonValue[] | Partial<{ [key in string]: JsonValue }>
+/**
+ * An upload above the configured size limit, held back until the user
+ * confirms it.
+ */
+export type LargeUploadConfirmation = { 
+/**
+ * Pass back to the upload command to go ahead.
+ */
+token: string; file_size: number; limit_bytes: number }
+/**
+ * A node found on the local network through mDNS.
+ */
+export type LocalPeer = { peer_id: string; 
+/**
+ * Multiaddresses built from the addresses the peer resolved to.
+ */
+addresses: string[]; host_name: string }
+/**
+ * Languages the backend has messages for.
+ */
+export type Locale = "En" | "De"
+export type LogLevel = "Info" | "Error"
+/**
+ * Detached signature over a dataset's CID made with the node's identity key.
+ */
+export type ManifestSignature = { peer_id: string; 
+/**
+ * `<public key>:<signature>`, both hex encoded, as shared with recipients.
+ */
+signature: string; signed_at: number }
+/**
+ * User-facing messages produced by the backend.
+ *
+ * Serialized alongside the translated text, so the frontend can use its
+ * own catalog with the same arguments instead.
+ */
+export type MessageKey = "node_creation_failed" | "node_start_failed" | "node_not_initialized" | "node_not_started" | "upload_failed" | "download_failed" | "file_not_found" | "invalid_cid" | "io_error" | "configuration_error" | "quota_exceeded" | "cancelled" | "unsupported" | "port_in_use" | "repo_locked" | "missing_library" | "incompatible_repo" | "source_changed" | "node_unresponsive" | "node_busy" | "read_only_mode" | "download_only_mode" | "transfer_stalled" | "not_confirmed" | "path_not_allowed" | "upload_confirmation_required" | "confirm_empty_trash" | "confirm_run_cleanup" | "confirm_export_identity" | "confirm_import_identity" | "confirm_rotate_identity" | "confirm_revoke_share" | "confirm_purge_catalog_entry" | "fetched_bytes" | "staged_bytes" | "starting_upload" | "uploaded_bytes" | "verifying_upload" | "upload_completed" | "connected_to_providers" | "connected_to_lan_providers" | "starting_download" | "downloaded_bytes" | "verifying_download" | "comparing_hash" | "download_completed"
+/**
+ * What to hold back while the connection is metered (cellular, tethering).
+ */
+export type MeteredPolicy = { 
+/**
+ * Stop the node so it doesn't serve content to other peers.
+ */
+pause_seeding: boolean; 
+/**
+ * Queued transfers above this size wait for an unmetered connection.
+ */
+max_transfer_bytes: number }
+export type NetworkStatus = { 
+/**
+ * `None` when the platform doesn't expose whether the connection is metered.
+ */
+metered: boolean | null; policy_overridden: boolean; seeding_paused: boolean }
+/**
+ * Where the node can be reached.
+ */
+export type NodeAddresses = { peer_id: string; listen_addresses: string[]; 
+/**
+ * Addresses to hand to other peers, each ending in `/p2p/<peer id>`.
+ * The configured external address replaces what the node announces,
+ * addresses outside the configured IP family are left out.
+ */
+announced_addresses: string[]; ipv4_bound: boolean; ipv6_bound: boolean }
+export type NodeHealth = { healthy: boolean; error: string | null }
+export type NodeInfo = { peer_id: string | null; version: string | null; repo_path: string | null; debug_info: JsonValue | null; 
+/**
+ * Peer limit the node was configured with, filled in from the settings.
+ */
+max_peers: number | null; 
+/**
+ * Set while the node is running.
+ */
+connectivity: Connectivity | null; 
+/**
+ * Off in download-only mode, when the node keeps nothing to serve.
+ */
+hosting: boolean; 
+/**
+ * Whether the node can look up who provides a dataset. Neither the
+ * embedded node nor the REST API offer it, so the lookup is hidden.
+ */
+provider_lookup: boolean; 
+/**
+ * Whether a dataset can be announced on demand; otherwise the node
+ * only announces on its own schedule.
+ */
+announce: boolean }
+/**
+ * A received offer, identified by its manifest's CID.
+ */
+export type Offer = { id: string; manifest: OfferManifest; 
+/**
+ * Milliseconds since the Unix epoch.
+ */
+received_at: number; status: OfferStatus }
+/**
+ * Inbox other users send file offers to, bound to every interface.
+ * Offers are sent to peers on the same port.
+ *
+ * Changes take effect on the next launch.
+ */
+export type OfferInboxSettings = { port: number }
+/**
+ * What the sender publishes for the recipient to look at before accepting.
+ */
+export type OfferManifest = { version: number; from_peer_id: string; from_addresses: string[]; cid: string; filename: string | null; size: number | null; message: string | null; 
+/**
+ * Milliseconds since the Unix epoch.
+ */
+created_at: number }
+export type OfferStatus = "pending" | "accepted" | "declined"
+export type OnboardingCheck = { kind: OnboardingCheckKind; status: CheckStatus; message: string }
+export type OnboardingCheckKind = "DataDirWritable" | "PortAvailable" | "BootstrapReachable" | "DiskSpace"
+export type OnboardingReport = { checks: OnboardingCheck[]; 
+/**
+ * No check failed, the first connect is expected to work.
+ */
+ready: boolean }
+export type OperationLogLine = { 
+/**
+ * Milliseconds since the Unix epoch.
+ */
+timestamp: number; level: LogLevel; message: string }
+export type OperationOutcome = { Upload: UploadResultResponse } | { Download: DownloadResultResponse } | { Failed: string }
+/**
+ * Outcome of a finished transfer, kept around for clients that missed the
+ * completion event.
+ */
+export type OperationResult = { operation_id: string; outcome: OperationOutcome; 
+/**
+ * Milliseconds since the Unix epoch.
+ */
+started_at: number; finished_at: number; 
+/**
+ * What was logged for the transfer while it ran.
+ */
+log: OperationLogLine[] }
+export type OperationStage = "Initializing" | 
+/**
+ * Preparing the source of an upload, with byte progress of the step.
+ */
+{ Preparing: PreparingStep } | 
+/**
+ * Fetching the source of an upload from a remote URL.
+ */
+"Fetching" | "Uploading" | "Downloading" | 
+/**
+ * No bytes moved for the stall timeout, the transfer is still running.
+ */
+"Stalled" | "Verifying" | "Completed" | { Failed: string }
+export type PanicReport = { 
+/**
+ * Milliseconds since the Unix epoch.
+ */
+timestamp: number; message: string; location: string | null; thread: string | null; backtrace: string; app_version: string }
+export type PeerDirection = 
+/**
+ * We dialed the peer through `connect_to_peer`.
+ */
+"Outbound" | 
+/**
+ * The peer showed up on its own, through discovery or by dialing us.
+ */
+"Inbound"
+export type PeerEvent = { peer_id: string; addresses: string[]; direction: PeerDirection }
+export type PeerThroughput = { peer_id: string; cid: string; bytes: number; duration_ms: number; bytes_per_sec: number; 
+/**
+ * Whether the measurement stopped at the timeout before the sample
+ * or the dataset was complete.
+ */
+timed_out: boolean }
+export type PowerSource = { on_battery: boolean; 
+/**
+ * Remaining charge from 0 to 100.
+ */
+charge_percent: number | null }
+export type PowerStatus = { 
+/**
+ * `None` without a battery or when the platform doesn't report one.
+ */
+source: PowerSource | null; 
+/**
+ * Whether the battery policy currently holds back uploads and seeding.
+ */
+policy_active: boolean; seeding_paused: boolean }
+/**
+ * Local work an upload does before anything reaches the network.
+ */
+export type PreparingStep = 
+/**
+ * Copying the source into the staging directory.
+ */
+"Staging"
+/**
+ * Restricts the node to peers sharing the same swarm key.
+ */
+export type PrivateNetworkSettings = { 
+/**
+ * 32-byte pre-shared key, hex encoded as in a libp2p `swarm.key` file.
+ */
+swarm_key: string }
+export type ProgressMessage = { operation_id: string; progress: number; bytes_processed: number; total_bytes: number | null; stage: OperationStage; 
+/**
+ * `message_key` rendered in the backend's locale.
+ */
+message: string | null; message_key: MessageKey | null; message_args: string[]; 
+/**
+ * Set on the completion message of a transfer.
+ */
+duration_ms: number | null; verified: boolean | null; 
+/**
+ * Intermediate files removed after a failed transfer.
+ */
+rolled_back: string[]; 
+/**
+ * The byte count is extrapolated between sparse node callbacks rather
+ * than reported by the node.
+ */
+estimated: boolean }
+/**
+ * Commands that permanently delete data or expose the node key, which
+ * only run with a token the user granted through a native dialog the
+ * webview can't click.
+ */
+export type ProtectedAction = "empty_trash" | "run_cleanup" | "export_identity" | "import_identity" | "rotate_identity" | "revoke_share" | "purge_catalog_entry"
+/**
+ * A peer advertising a dataset in the DHT.
+ */
+export type ProviderInfo = { peer_id: string; addresses: string[] }
+/**
+ * Proxy for the app's own HTTP traffic: URL fetches and the remote node's
+ * API. The embedded node's peer connections can't be proxied.
+ */
+export type ProxySettings = { 
+/**
+ * `http://`, `https://`, `socks5://` or `socks5h://` URL, credentials
+ * may be given in the URL.
+ */
+url: string }
+export type QuotaWarning = { threshold: number; usage: StorageUsage }
+export type RecentAction = "Uploaded" | "Downloaded" | "Opened"
+export type RecentItem = { cid: string; action: RecentAction; 
+/**
+ * Local file involved, if any.
+ */
+path: string | null; 
+/**
+ * Milliseconds since the Unix epoch.
+ */
+used_at: number }
+export type ReconcileAction = 
+/**
+ * Fetch a missing dataset from the network again.
+ */
+"repin" | 
+/**
+ * Add a dataset the catalog doesn't know to it.
+ */
+"adopt" | 
+/**
+ * Drop the catalog entry of a missing dataset.
+ */
+"purge"
+export type RecoveryReport = { unclean_shutdown: boolean; removed_locks: string[]; errors: string[] }
+/**
+ * Use an already running node instead of the embedded one.
+ *
+ * Changes take effect on the next launch.
+ */
+export type RemoteNodeSettings = { 
+/**
+ * Base URL of the node's REST API, e.g. `http://localhost:8080`.
+ */
+url: string }
+export type RepoCheckReport = { checked: number; missing: string[]; repaired: string[]; errors: RepoIssue[] }
+export type RepoIssue = { cid: string; error: string }
+export type RpcApiInfo = { enabled: boolean; url: string | null; token: string | null }
+/**
+ * Local JSON-RPC API for scripts, only bound to the loopback interface.
+ *
+ * Changes take effect on the next launch.
+ */
+export type RpcApiSettings = { port: number }
+/**
+ * A friendly name handed out for a dataset.
+ */
+export type Share = { name: string; cid: string; 
+/**
+ * Milliseconds since the Unix epoch.
+ */
+created_at: number; 
+/**
+ * When the local copy gets deleted, in milliseconds since the Unix epoch.
+ */
+expires_at: number | null }
+/**
+ * Contents of a `.dexshare` file.
+ */
+export type ShareFile = { version: number; cid: string; filename: string | null; size: number | null; 
+/**
+ * The sender's note about the content.
+ */
+note: string | null; encryption: EncryptionHint | null; signature: ManifestSignature | null; providers: ShareProvider[] }
+/**
+ * A peer known to hold the content.
+ */
+export type ShareProvider = { peer_id: string; addresses: string[]; 
+/**
+ * Signed peer record, lets the recipient dial without a DHT lookup.
+ */
+spr: string | null }
+export type SidecarVerification = { file_path: string; sidecar_path: string; algorithm: ChecksumAlgorithm; expected: string; actual: string; matches: boolean }
+export type StorageBreakdown = { usage: StorageUsage; 
+/**
+ * Largest first, datasets of unknown size last.
+ */
+datasets: DatasetUsage[] }
+export type StorageConnectionStatus = "Disconnected" | "Initialized" | "Connecting" | "Connected" | 
+/**
+ * The node is running but may have lost its connections, e.g. after the
+ * system woke from sleep or the network changed.
+ */
+"Degraded" | "Error"
+export type StorageError = { NodeCreation: string } | { NodeStart: string } | "NodeNotInitialized" | "NodeNotStarted" | { Upload: string } | { Download: string } | { FileNotFound: string } | { InvalidCid: string } | { Io: string } | { Configuration: string } | { QuotaExceeded: { required: number; available: number } } | "Cancelled" | 
+/**
+ * The node in use doesn't offer the requested capability.
+ */
+{ Unsupported: string } | 
+/**
+ * Another process holds the node's listening port.
+ */
+{ PortInUse: string } | 
+/**
+ * The repo is locked, usually by another instance of the app.
+ */
+{ RepoLocked: string } | 
+/**
+ * The native node library couldn't be loaded.
+ */
+{ MissingLibrary: string } | 
+/**
+ * The repo was written by a node version this one can't read.
+ */
+{ IncompatibleRepo: string } | 
+/**
+ * The file was modified while it was being uploaded.
+ */
+{ SourceChanged: string } | 
+/**
+ * The node claims to be running but stopped answering.
+ */
+{ NodeUnresponsive: string } | 
+/**
+ * Another start or stop of the node is still in progress.
+ */
+"Busy" | 
+/**
+ * The node only serves what it already stores.
+ */
+"ReadOnlyMode" | 
+/**
+ * The node doesn't host content for other peers.
+ */
+"DownloadOnlyMode" | 
+/**
+ * No bytes moved for this many seconds, retrying may find other peers.
+ */
+{ Stalled: number } | 
+/**
+ * A protected action ran without a valid confirmation from the user.
+ */
+"NotConfirmed" | 
+/**
+ * A path from the frontend lies outside the directories the app may touch.
+ */
+{ PathNotAllowed: string } | 
+/**
+ * The upload is above the configured size limit, nothing was uploaded.
+ */
+{ UploadConfirmationRequired: LargeUploadConfirmation }
+export type StorageUsage = { used_bytes: number; quota_bytes: number; total_blocks: number }
+export type SystemEvent = { kind: "Suspended" } | { kind: "Resumed" } | { kind: "NetworkLost" } | { kind: "NetworkChanged" }
+export type TransferJob = { operation_id: string; request: TransferRequest; queued_at: number; 
+/**
+ * Set by `force_start` to let the job run outside the transfer window.
+ */
+forced: boolean }
+export type TransferRequest = { Upload: { file_path: string; 
+/**
+ * Seconds until the local copy expires after the upload.
+ */
+ttl_secs: number | null } } | { Download: { cid: string; save_path: string } }
+export type TransferSummary = { active_transfers: number; 
+/**
+ * Bytes per second over the last interval.
+ */
+upload_rate: number; download_rate: number; 
+/**
+ * Jobs waiting in the transfer queue, the running one included.
+ */
+queue_depth: number; queue_paused: boolean }
+/**
+ * Hours of the day (local time) during which queued transfers may run.
+ *
+ * The window wraps around midnight when `start_hour` is greater than
+ * `end_hour`, e.g. 22 → 6.
+ */
+export type TransferWindow = { start_hour: number; end_hour: number }
+export type UploadFileResult = { Uploaded: UploadResultResponse } | 
+/**
+ * The file is above the configured size limit, nothing was uploaded.
+ */
+{ ConfirmationRequired: LargeUploadConfirmation }
+export type UploadResultResponse = { cid: string; size: number; duration_ms: number; verified: boolean }
+export type UptimeStats = { tracking_since: number; tracked_secs: number; connected_secs: number; restarts: number; 
+/**
+ * Fraction of tracked time spent connected, from 0 to 1.
+ */
+availability: number }
+/**
+ * Last geometry of the main window, in physical pixels.
+ */
+export type WindowGeometry = { x: number; y: number; width: number; height: number; maximized: boolean }
+
+/** tauri-specta globals **/
+
+import {
+	invoke as TAURI_INVOKE,
+	Channel as TAURI_CHANNEL,
+} from "@tauri-apps/api/core";
+import * as TAURI_API_EVENT from "@tauri-apps/api/event";
+import { type WebviewWindow as __WebviewWindow__ } from "@tauri-apps/api/webviewWindow";
+
+type __EventObj__<T> = {
+	listen: (
+		cb: TAURI_API_EVENT.EventCallback<T>,
+	) => ReturnType<typeof TAURI_API_EVENT.listen<T>>;
+	once: (
+		cb: TAURI_API_EVENT.EventCallback<T>,
+	) => ReturnType<typeof TAURI_API_EVENT.once<T>>;
+	emit: null extends T
+		? (payload?: T) => ReturnType<typeof TAURI_API_EVENT.emit>
+		: (payload: T) => ReturnType<typeof TAURI_API_EVENT.emit>;
+};
+
+export type Result<T, E> =
+	| { status: "ok"; data: T }
+	| { status: "error"; error: E };
+
+function __makeEvents__<T extends Record<string, any>>(
+	mappings: Record<keyof T, string>,
+) {
+	return new Proxy(
+		{} as unknown as {
+			[K in keyof T]: __EventObj__<T[K]> & {
+				(handle: __WebviewWindow__): __EventObj__<T[K]>;
+			};
+		},
+		{
+			get: (_, event) => {
+				const name = mappings[event as keyof T];
+
+				return new Proxy((() => {}) as any, {
+					apply: (_, __, [window]: [__WebviewWindow__]) => ({
+						listen: (arg: any) => window.listen(name, arg),
+						once: (arg: any) => window.once(name, arg),
+						emit: (arg: any) => window.emit(name, arg),
+					}),
+					get: (_, command: keyof __EventObj__<any>) => {
+						switch (command) {
+							case "listen":
+								return (arg: any) => TAURI_API_EVENT.listen(name, arg);
+							case "once":
+								return (arg: any) => TAURI_API_EVENT.once(name, arg);
+							case "emit":
+								return (arg: any) => TAURI_API_EVENT.emit(name, arg);
+						}
+					},
+				});
+			},
+		},
+	);
+}
@@ -16,7 +16,6 @@ import {
 	$isConnected,
 	$isConnectionDialogOpened,
 } from "../connectionStore";
-import { ConnectionStatus } from "../connectionTypes";
 import {
 	getConnectionStatusDescription,
 	getConnectionStatusText,
@@ -46,11 +45,11 @@ export default function ConnectionDialog() {
 				</DialogHeader>
 
 				<Progress
-					indeterminate={connectionStatus === ConnectionStatus.Connecting}
+					indeterminate={connectionStatus === "Connecting"}
 					value={isConnected ? 100 : undefined}
 				/>
 
-				{connectionStatus === ConnectionStatus.Error && (
+				{connectionStatus === "Error" && (
 					<div className="mt-4">
 						<Typography variant="body2" color="secondary">
 							{connectionError}
@@ -60,9 +59,9 @@ export default function ConnectionDialog() {
 
 				<div className="mt-6 flex justify-end space-x-2">
 					{![
-						ConnectionStatus.Disconnected,
-						ConnectionStatus.Initialized,
-						ConnectionStatus.Error,
+						"Disconnected",
+						"Initialized",
+						"Error",
 					].includes(connectionStatus) && (
 						<Button variant="filled" onClick={disconnectFromStorage}>
 							{isConnected ? "Disconnect" : "Abort"}
@@ -70,9 +69,9 @@ export default function ConnectionDialog() {
 					)}
 
 					{[
-						ConnectionStatus.Disconnected,
-						ConnectionStatus.Initialized,
-						ConnectionStatus.Error,
+						"Disconnected",
+						"Initialized",
+						"Error",
 					].includes(connectionStatus) && (
 						<Button variant="filled" onClick={connectToStorage}>
 							Connect
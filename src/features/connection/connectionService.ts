@@ -1,26 +1,24 @@
-import { invoke } from "@tauri-apps/api/core";
+import { commands } from "../../bindings";
 import { $connectionError, $connectionStatus } from "./connectionStore";
-import { ConnectionStatus } from "./connectionTypes";
 
 export async function connectToStorage() {
-	try {
-		$connectionStatus.set(ConnectionStatus.Connecting);
-		$connectionError.set(null);
-		await invoke("start_node");
-	} catch (error) {
-		console.error("Failed to connect to Storage:", error);
-		$connectionError.set(error as string);
-		$connectionStatus.set(ConnectionStatus.Error);
+	$connectionStatus.set("Connecting");
+	$connectionError.set(null);
+	const result = await commands.startNode();
+	if (result.status === "error") {
+		console.error("Failed to connect to Storage:", result.error.error);
+		$connectionError.set(result.error.message);
+		$connectionStatus.set("Error");
 	}
 };
 
 export async function disconnectFromStorage() {
-        try {
-            await invoke("stop_node");
-            $connectionStatus.set(ConnectionStatus.Disconnected);
-            $connectionError.set(null);
-        } catch (error) {
-            console.error("Failed to disconnect from Storage:", error);
-            $connectionError.set(error as string);
+        const result = await commands.stopNode();
+        if (result.status === "error") {
+            console.error("Failed to disconnect from Storage:", result.error.error);
+            $connectionError.set(result.error.message);
+            return;
         }
+        $connectionStatus.set("Disconnected");
+        $connectionError.set(null);
     };
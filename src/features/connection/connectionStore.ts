@@ -1,19 +1,19 @@
 import { atom, computed, onMount } from 'nanostores';
 import { updateNodeInfo, updateNodeStatus } from '../node/nodeService';
 import { connectToStorage } from './connectionService';
-import { ConnectionStatus } from './connectionTypes';
+import type { StorageConnectionStatus } from '../../bindings';
 
 export const $isConnectionDialogOpened = atom(false);
-export const $connectionStatus = atom<ConnectionStatus>(ConnectionStatus.Disconnected);
+export const $connectionStatus = atom<StorageConnectionStatus>("Disconnected");
 export const $connectionError = atom<string | null>(null);
 
-export const $isConnected = computed($connectionStatus, connectionStatus => connectionStatus === ConnectionStatus.Connected)
+export const $isConnected = computed($connectionStatus, connectionStatus => connectionStatus === "Connected")
 
 onMount($connectionStatus, () => {
   updateNodeStatus().then((status) => {
         if (
-          status === ConnectionStatus.Disconnected ||
-          status === ConnectionStatus.Initialized
+          status === "Disconnected" ||
+          status === "Initialized"
         ) {
           connectToStorage();
           $isConnectionDialogOpened.set(true);
@@ -1,7 +0,0 @@
-export enum ConnectionStatus {
-  Disconnected = 'Disconnected',
-  Initialized = 'Initialized',
-  Connecting = 'Connecting',
-  Connected = 'Connected',
-  Error = 'Error',
-}
\ No newline at end of file
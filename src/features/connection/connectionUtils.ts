@@ -1,33 +1,37 @@
-import { ConnectionStatus } from "./connectionTypes";
+import type { StorageConnectionStatus } from "../../bindings";
 
-export function getConnectionStatusText(status: ConnectionStatus) {
+export function getConnectionStatusText(status: StorageConnectionStatus) {
     switch (status) {
-                case ConnectionStatus.Connected:
+                case "Connected":
                     return "Connected";
-                case ConnectionStatus.Connecting:
+                case "Connecting":
                     return "Connecting...";
-                case ConnectionStatus.Error:
+                case "Degraded":
+                    return "Reconnecting...";
+                case "Error":
                     return "Error";
-                case ConnectionStatus.Initialized:
-                case ConnectionStatus.Disconnected:
+                case "Initialized":
+                case "Disconnected":
                     return "Disconnected";
                 default:
                     return status;
             }
 }
 
-export function getConnectionStatusDescription(status: ConnectionStatus, connectionError?: string | null) {
+export function getConnectionStatusDescription(status: StorageConnectionStatus, connectionError?: string | null) {
 		switch (status) {
-			case ConnectionStatus.Connecting:
+			case "Connecting":
 				return "Connecting to Storage network...";
-			case ConnectionStatus.Connected:
+			case "Connected":
 				return "Connected to Storage successfully";
-			case ConnectionStatus.Error:
+			case "Degraded":
+				return "Checking the connection after a system or network change...";
+			case "Error":
 				return (
 					connectionError || "An error occurred while connecting to Storage"
 				);
-			case ConnectionStatus.Disconnected:
-			case ConnectionStatus.Initialized:
+			case "Disconnected":
+			case "Initialized":
 				return "Disconnected from Storage network";
 			default:
 				return "";
@@ -1,21 +1,13 @@
 import { Button, Card, CardContent, CardDescription, CardHeader, CardTitle, Input, Progress, Typography } from '@nipsysdev/lsd-react';
-import { invoke } from '@tauri-apps/api/core';
 import { save } from '@tauri-apps/plugin-dialog';
 import { useState } from 'react';
-
-interface DownloadResult {
-  cid: string;
-  size: number;
-  duration_ms: number;
-  verified: boolean;
-  filepath?: string;
-}
+import { commands, type DownloadResultResponse } from '../../../bindings';
 
 export default function DownloadTab() {
   const [cid, setCid] = useState('');
   const [isDownloading, setIsDownloading] = useState(false);
   const [downloadProgress, setDownloadProgress] = useState(0);
-  const [downloadResult, setDownloadResult] = useState<DownloadResult | null>(null);
+  const [downloadResult, setDownloadResult] = useState<DownloadResultResponse | null>(null);
   const [error, setError] = useState<string | null>(null);
 
   const validateCid = (cid: string): boolean => {
@@ -69,17 +61,17 @@ export default function DownloadTab() {
         });
       }, 200);
 
-      const result = await invoke<DownloadResult>('download_file_from_storage', {
-        cid: cid,
-        savePath: savePath
-      });
+      const result = await commands.downloadFileFromStorage(cid, savePath);
 
       clearInterval(progressInterval);
+      if (result.status === 'error') {
+        throw new Error(result.error.message);
+      }
       setDownloadProgress(100);
-      setDownloadResult(result);
+      setDownloadResult(result.data);
     } catch (err) {
       console.error('Download failed:', err);
-      setError(`Download failed: ${err}`);
+      setError(`Download failed: ${err instanceof Error ? err.message : err}`);
     } finally {
       setIsDownloading(false);
     }
@@ -8,9 +8,11 @@ import {
 	Typography,
 } from "@nipsysdev/lsd-react";
 import { $nodeInfo } from "../nodeStore";
+import type { DebugInfo } from "../nodeTypes";
 
 export default function NodeTab() {
 	const nodeInfo = useStore($nodeInfo);
+	const debugInfo = nodeInfo?.debug_info as unknown as DebugInfo | null | undefined;
 
 	return (
 		<Card className="size-full border-0!">
@@ -41,7 +43,7 @@ export default function NodeTab() {
 						</div>
 						<div className="flex flex-col col-span-full sm:col-span-4">
 							<Typography variant="subtitle1">Addresses</Typography>
-							{nodeInfo?.debug_info?.addrs.map((address) => (
+							{debugInfo?.addrs.map((address) => (
 								<Typography
 									color="secondary"
 									className="wrap-anywhere"
@@ -53,7 +55,7 @@ export default function NodeTab() {
 						</div>
 						<div className="flex flex-col col-span-full sm:col-span-4">
 							<Typography variant="subtitle1">Announce Addresses</Typography>
-							{nodeInfo?.debug_info?.announceAddresses.map((address) => (
+							{debugInfo?.announceAddresses.map((address) => (
 								<Typography
 									color="secondary"
 									className="wrap-anywhere"
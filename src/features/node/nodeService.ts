@@ -1,17 +1,20 @@
-import { invoke } from "@tauri-apps/api/core";
+import { commands, type StorageConnectionStatus } from "../../bindings";
 import { $connectionStatus } from "../connection/connectionStore";
-import type { ConnectionStatus } from "../connection/connectionTypes";
 import { $nodeInfo } from "./nodeStore";
-import type { NodeInfo } from "./nodeTypes";
 
-export async function updateNodeStatus(): Promise<ConnectionStatus> {
-    const status = await invoke<ConnectionStatus>("get_node_status");
-	$connectionStatus.set(status);
-    return status
+export async function updateNodeStatus(): Promise<StorageConnectionStatus> {
+    const result = await commands.getNodeStatus();
+    if (result.status === "error") {
+        throw new Error(result.error.message);
+    }
+	$connectionStatus.set(result.data);
+    return result.data
 }
 
 export async function updateNodeInfo() {
-    const nodeInfo = await invoke<NodeInfo | null>("get_node_info");
-    console.log(nodeInfo)
-    $nodeInfo.set(nodeInfo);
-}
\ No newline at end of file
+    const result = await commands.getNodeInfo();
+    if (result.status === "error") {
+        throw new Error(result.error.message);
+    }
+    $nodeInfo.set(result.data);
+}
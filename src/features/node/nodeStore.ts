@@ -1,4 +1,4 @@
 import { atom } from "nanostores";
-import type { NodeInfo } from "./nodeTypes";
+import type { NodeInfo } from "../../bindings";
 
-export const $nodeInfo = atom<NodeInfo | null>(null);
\ No newline at end of file
+export const $nodeInfo = atom<NodeInfo | null>(null);
@@ -1,10 +1,5 @@
-export interface NodeInfo {
-    peer_id: string
-    repo_path: string
-    version: string
-    debug_info: DebugInfo | null
-}
-
+// The node bindings have no type of their own for their debug info, so the
+// generated bindings only know `NodeInfo.debug_info` as JSON
 export interface DebugInfo {
     id: string
     spr: string
@@ -22,4 +17,4 @@ export interface Node {
     peerId: string
     record: string
     seen: boolean
-}
\ No newline at end of file
+}
@@ -1,5 +1,5 @@
 import { atom } from "nanostores";
-import type { Peer } from "./peersTypes";
+import type { BookmarkedPeer } from "../../bindings";
 
-export const $manualPeerConnections = atom<Peer[]>([])
-export const $isAddPeerDialogOpened = atom(false)
\ No newline at end of file
+export const $manualPeerConnections = atom<BookmarkedPeer[]>([])
+export const $isAddPeerDialogOpened = atom(false)
@@ -1,4 +0,0 @@
-export interface Peer {
-    peerId: string
-    addresses: string[]
-}
\ No newline at end of file
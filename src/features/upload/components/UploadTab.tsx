@@ -1,20 +1,13 @@
 import { Button, Card, CardContent, CardDescription, CardHeader, CardTitle, Progress, Typography } from '@nipsysdev/lsd-react';
-import { invoke } from '@tauri-apps/api/core';
 import { open } from '@tauri-apps/plugin-dialog';
 import { useState } from 'react';
-
-interface UploadResult {
-  cid: string;
-  size: number;
-  duration_ms: number;
-  verified: boolean;
-}
+import { commands, type UploadResultResponse } from '../../../bindings';
 
 export default function UploadTab() {
   const [selectedFile, setSelectedFile] = useState<File | null>(null);
   const [isUploading, setIsUploading] = useState(false);
   const [uploadProgress, setUploadProgress] = useState(0);
-  const [uploadResult, setUploadResult] = useState<UploadResult | null>(null);
+  const [uploadResult, setUploadResult] = useState<UploadResultResponse | null>(null);
   const [error, setError] = useState<string | null>(null);
   const [selectedFilePath, setSelectedFilePath] = useState<string | null>(null);
 
@@ -69,20 +62,39 @@ export default function UploadTab() {
       }, 200);
 
       try {
-        const result = await invoke<UploadResult>('upload_file_to_storage', {
-          filePath: selectedFilePath
-        });
-        
+        let result = await commands.uploadFileToStorage(selectedFilePath, null, null, null);
+        if (result.status === 'error') {
+          throw new Error(result.error.message);
+        }
+
+        // Large files need a second, confirmed call
+        if ('ConfirmationRequired' in result.data) {
+          const { token, file_size } = result.data.ConfirmationRequired;
+          const sizeGb = (file_size / 1024 ** 3).toFixed(1);
+          if (!window.confirm(`This file is ${sizeGb} GB. Upload it anyway?`)) {
+            clearInterval(progressInterval);
+            setUploadProgress(0);
+            return;
+          }
+          result = await commands.uploadFileToStorage(selectedFilePath, null, null, token);
+          if (result.status === 'error') {
+            throw new Error(result.error.message);
+          }
+        }
+        if (!('Uploaded' in result.data)) {
+          throw new Error('Upload was not confirmed');
+        }
+
         clearInterval(progressInterval);
         setUploadProgress(100);
-        setUploadResult(result);
+        setUploadResult(result.data.Uploaded);
       } catch (uploadErr) {
         clearInterval(progressInterval);
         throw uploadErr;
       }
     } catch (err) {
       console.error('Upload failed:', err);
-      setError(`Upload failed: ${err}`);
+      setError(`Upload failed: ${err instanceof Error ? err.message : err}`);
     } finally {
       setIsUploading(false);
     }